@@ -0,0 +1,98 @@
+// app_settings.rs - Native-side preferences persisted in the app data dir
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::remote_server::RemoteSettings;
+use crate::update_check::UpdateCheckSettings;
+use crate::write_json_atomic;
+
+const APP_SETTINGS_FILE: &str = "app_settings.json";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Monitor the prompter was last sent fullscreen to (see `monitors::monitor_id`).
+    pub last_monitor_id: Option<String>,
+    /// SHA-256 of the PIN required to leave kiosk mode; `None` means no PIN.
+    pub kiosk_pin_hash: Option<String>,
+    pub update_check: UpdateCheckSettings,
+    pub remote: RemoteSettings,
+    /// Path components a directory needs before `delete_directory` will
+    /// remove it; `None` uses `file_io::DEFAULT_MIN_DELETE_DEPTH`.
+    pub min_delete_depth: Option<usize>,
+    /// Keys written by newer builds, kept so a downgrade doesn't erase them.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Serializes read-modify-write cycles on the settings file.
+#[derive(Default)]
+pub struct SettingsStore {
+    lock: Mutex<()>,
+}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+impl SettingsStore {
+    pub fn load(&self, app_dir: &Path) -> AppSettings {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        load(app_dir)
+    }
+
+    pub fn update(&self, app_dir: &Path, apply: impl FnOnce(&mut AppSettings)) -> Result<AppSettings, String> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut settings = load(app_dir);
+
+        apply(&mut settings);
+
+        write_json_atomic(&file_path(app_dir), &settings)?;
+        Ok(settings)
+    }
+}
+
+fn file_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(APP_SETTINGS_FILE)
+}
+
+fn load(app_dir: &Path) -> AppSettings {
+    let path = file_path(app_dir);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return AppSettings::default(),
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("⚠️  Ignoring corrupt app settings file {}: {}", path.display(), e);
+        AppSettings::default()
+    })
+}
+
+// ============================================================================
+// KIOSK PIN
+// ============================================================================
+
+pub fn hash_pin(pin: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"segitelep-kiosk:");
+    hasher.update(pin.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// True when no PIN is configured or `pin` matches it.
+pub fn kiosk_pin_matches(settings: &AppSettings, pin: Option<&str>) -> bool {
+    match &settings.kiosk_pin_hash {
+        None => true,
+        Some(expected) => pin.is_some_and(|pin| hash_pin(pin) == *expected),
+    }
+}
@@ -0,0 +1,413 @@
+// assets.rs - Content-addressed media store under the app data dir
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use base64::Engine;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+
+use crate::disk_space;
+use crate::error::CommandError;
+use crate::jobs::JobGuard;
+
+pub const ASSETS_DIR: &str = "global_assets";
+/// Copies land here first so a half-written file never appears under its
+/// final name (or gets swept by `cleanup_global_assets`).
+const STAGING_DIR: &str = "asset_staging";
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+/// Assets larger than this are refused as data URLs (mostly video, which
+/// shouldn't go through a string anyway).
+const MAX_DATA_URL_ASSET_BYTES: u64 = 20 * 1024 * 1024;
+/// Total size of cached data URLs; least recently used ones go first.
+const DATA_URL_CACHE_BYTES: usize = 64 * 1024 * 1024;
+/// Deletions are mostly waiting on the filesystem, so a few more threads
+/// than cores still help; more just contend on the directory.
+const MAX_CLEANUP_WORKERS: usize = 8;
+const CLEANUP_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// STORING
+// ============================================================================
+
+/// Stores `bytes` as `global_assets/<sha256>.<ext>`, deduplicating identical
+/// content. Returns the path relative to the app data dir.
+pub fn store_bytes(app_dir: &Path, bytes: &[u8], extension: &str) -> Result<String, CommandError> {
+    let extension = clean_extension(extension)?;
+
+    if bytes.is_empty() {
+        return Err("Cannot store empty asset".to_string().into());
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    let hash = format!("{:x}", hasher.finalize());
+
+    let assets_dir = ensure_assets_dir(app_dir)?;
+    let filename = format!("{}.{}", hash, extension);
+    let file_path = assets_dir.join(&filename);
+
+    if !file_path.exists() {
+        disk_space::ensure_space(&file_path, bytes.len() as u64)?;
+        fs::write(&file_path, bytes)
+            .map_err(|e| format!("Failed to write asset to '{}': {}", file_path.display(), e))?;
+        log::info!("💾 Stored new asset: {} ({} bytes)", filename, bytes.len());
+    } else {
+        log::info!("♻️  Asset already exists (deduplicated): {}", filename);
+    }
+
+    Ok(format!("{}/{}", ASSETS_DIR, filename))
+}
+
+/// Like `store_bytes` for a file on disk, hashing while it copies so large
+/// videos are never held in memory. The extension is taken from `source`.
+/// Blocking; run it on a blocking thread.
+pub fn store_from_path(app_dir: &Path, source: &Path) -> Result<String, CommandError> {
+    let display = source.to_string_lossy().to_string();
+    let extension = clean_extension(&source.extension().map(|ext| ext.to_string_lossy()).unwrap_or_default())?;
+
+    let metadata = fs::metadata(source).map_err(|_| CommandError::NotFound { path: display.clone() })?;
+    if !metadata.is_file() {
+        return Err(format!("'{}' is not a file", display).into());
+    }
+    if metadata.len() == 0 {
+        return Err("Cannot store empty asset".to_string().into());
+    }
+
+    let assets_dir = ensure_assets_dir(app_dir)?;
+    let staging_dir = app_dir.join(STAGING_DIR);
+    fs::create_dir_all(&staging_dir)
+        .map_err(|e| format!("Failed to create {} directory: {}", STAGING_DIR, e))?;
+    let staged = staging_dir.join(format!("{}.part", uuid::Uuid::new_v4()));
+
+    disk_space::ensure_space(&staged, metadata.len())?;
+    let hash = match copy_hashing(source, &staged) {
+        Ok(hash) => hash,
+        Err(e) => {
+            let _ = fs::remove_file(&staged);
+            return Err(format!("Failed to copy '{}' into the asset store: {}", display, e).into());
+        }
+    };
+
+    let filename = format!("{}.{}", hash, extension);
+    let file_path = assets_dir.join(&filename);
+
+    if file_path.exists() {
+        let _ = fs::remove_file(&staged);
+        log::info!("♻️  Asset already exists (deduplicated): {}", filename);
+    } else {
+        fs::rename(&staged, &file_path).map_err(|e| {
+            let _ = fs::remove_file(&staged);
+            format!("Failed to move asset into place: {}", e)
+        })?;
+        log::info!("💾 Stored new asset from {}: {} ({} bytes)", display, filename, metadata.len());
+    }
+
+    Ok(format!("{}/{}", ASSETS_DIR, filename))
+}
+
+fn copy_hashing(source: &Path, destination: &PathBuf) -> std::io::Result<String> {
+    let mut reader = BufReader::new(File::open(source)?);
+    let mut writer = File::create(destination)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+    }
+    writer.sync_all()?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn clean_extension(extension: &str) -> Result<String, CommandError> {
+    let clean = extension.trim_start_matches('.').to_lowercase();
+    if clean.is_empty() {
+        return Err("Invalid file extension".to_string().into());
+    }
+    Ok(clean)
+}
+
+fn ensure_assets_dir(app_dir: &Path) -> Result<PathBuf, String> {
+    let assets_dir = app_dir.join(ASSETS_DIR);
+
+    if !assets_dir.exists() {
+        fs::create_dir_all(&assets_dir)
+            .map_err(|e| format!("Failed to create {} directory: {}", ASSETS_DIR, e))?;
+        log::info!("📁 Created {} directory at: {:?}", ASSETS_DIR, assets_dir);
+    }
+
+    Ok(assets_dir)
+}
+
+// ============================================================================
+// DATA URLS
+// ============================================================================
+
+struct CachedDataUrl {
+    data_url: String,
+    /// Size and mtime of the file when read, so a replaced file isn't served stale.
+    len: u64,
+    modified: Option<SystemTime>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<String, CachedDataUrl>,
+    total_bytes: usize,
+    clock: u64,
+}
+
+/// `data:` URLs for assets, for webviews where the asset protocol fails
+/// (some WebKitGTK versions). Cached so scrubbing through segments doesn't
+/// re-read and re-encode the same images.
+#[derive(Default)]
+pub struct DataUrlCache {
+    inner: Mutex<CacheInner>,
+}
+
+impl DataUrlCache {
+    /// `relative_path` is as returned by `store_bytes`
+    /// (`global_assets/<file>`). Blocking on a cache miss.
+    pub fn data_url(&self, app_dir: &Path, relative_path: &str) -> Result<String, CommandError> {
+        let (key, path) = resolve_asset(app_dir, relative_path)?;
+
+        let metadata = fs::metadata(&path).map_err(|_| CommandError::NotFound { path: key.clone() })?;
+        if !metadata.is_file() {
+            return Err(CommandError::NotFound { path: key });
+        }
+        if metadata.len() > MAX_DATA_URL_ASSET_BYTES {
+            return Err(CommandError::FileTooLarge { path: key, size: metadata.len(), limit: MAX_DATA_URL_ASSET_BYTES });
+        }
+        let modified = metadata.modified().ok();
+
+        {
+            let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            inner.clock += 1;
+            let clock = inner.clock;
+            if let Some(entry) = inner.entries.get_mut(&key) {
+                if entry.len == metadata.len() && entry.modified == modified {
+                    entry.last_used = clock;
+                    return Ok(entry.data_url.clone());
+                }
+            }
+        }
+
+        let bytes = fs::read(&path).map_err(|e| format!("Failed to read asset '{}': {}", key, e))?;
+        let mime = mime_guess::from_path(&path).first_or_octet_stream();
+        let data_url = format!(
+            "data:{};base64,{}",
+            mime.essence_str(),
+            base64::engine::general_purpose::STANDARD.encode(&bytes)
+        );
+
+        self.insert(key, CachedDataUrl { data_url: data_url.clone(), len: metadata.len(), modified, last_used: 0 });
+        Ok(data_url)
+    }
+
+    fn insert(&self, key: String, mut entry: CachedDataUrl) {
+        let size = entry.data_url.len();
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(old) = inner.entries.remove(&key) {
+            inner.total_bytes -= old.data_url.len();
+        }
+        if size > DATA_URL_CACHE_BYTES {
+            return;
+        }
+
+        while inner.total_bytes + size > DATA_URL_CACHE_BYTES {
+            let Some(oldest) = inner.entries.iter().min_by_key(|(_, e)| e.last_used).map(|(k, _)| k.clone()) else { break };
+            if let Some(evicted) = inner.entries.remove(&oldest) {
+                inner.total_bytes -= evicted.data_url.len();
+            }
+        }
+
+        inner.clock += 1;
+        entry.last_used = inner.clock;
+        inner.total_bytes += size;
+        inner.entries.insert(key, entry);
+    }
+
+    /// Drops the cached URL for an asset that was deleted or replaced.
+    pub fn invalidate(&self, relative_path: &str) {
+        let key = relative_path.replace('\\', "/");
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(old) = inner.entries.remove(&key) {
+            inner.total_bytes -= old.data_url.len();
+        }
+    }
+}
+
+/// Checks that `relative_path` names a file directly inside the assets dir,
+/// including after symlinks are followed. Returns the normalized key and the
+/// full path.
+pub fn resolve_asset(app_dir: &Path, relative_path: &str) -> Result<(String, PathBuf), CommandError> {
+    let key = relative_path.replace('\\', "/");
+    let components: Vec<Component> = Path::new(&key).components().collect();
+
+    let inside_assets = matches!(
+        components.as_slice(),
+        [Component::Normal(dir), Component::Normal(_)] if *dir == ASSETS_DIR
+    );
+    if !inside_assets {
+        return Err(format!("Not an asset path: {}", relative_path).into());
+    }
+
+    let path = app_dir.join(&key);
+    if let (Ok(real), Ok(real_dir)) = (dunce::canonicalize(&path), dunce::canonicalize(app_dir.join(ASSETS_DIR))) {
+        if real.parent() != Some(real_dir.as_path()) {
+            return Err(format!("Not an asset path: {}", relative_path).into());
+        }
+    }
+
+    Ok((key, path))
+}
+
+// ============================================================================
+// CLEANUP
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupSummary {
+    /// Files found in the assets directory.
+    pub scanned: usize,
+    /// Files not in the active set, i.e. due for deletion.
+    pub orphaned: usize,
+    pub deleted: usize,
+    pub bytes_freed: u64,
+    pub failed: Vec<String>,
+    /// Stopped by `cancel_asset_cleanup`; deletions already issued still count.
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CleanupProgress {
+    job_id: String,
+    processed: usize,
+    total: usize,
+    deleted: usize,
+    bytes_freed: u64,
+}
+
+#[derive(Default)]
+struct CleanupCounters {
+    next: AtomicUsize,
+    processed: AtomicUsize,
+    deleted: AtomicUsize,
+    bytes_freed: AtomicU64,
+    failed: Mutex<Vec<String>>,
+}
+
+/// Deletes every file in the assets dir whose `global_assets/<name>` key is
+/// not in `active`, on a small pool of threads, emitting
+/// `asset-cleanup-progress` events. Cancelling stops new deletions; the
+/// summary covers what was done. Blocking; run it on a blocking thread.
+pub fn cleanup(
+    app_handle: &AppHandle,
+    job: &JobGuard<'_>,
+    app_dir: &Path,
+    active: &HashSet<String>,
+    data_urls: &DataUrlCache,
+) -> Result<CleanupSummary, String> {
+    let assets_dir = app_dir.join(ASSETS_DIR);
+    if !assets_dir.exists() {
+        log::info!("No {} directory found, nothing to clean up", ASSETS_DIR);
+        return Ok(CleanupSummary { scanned: 0, orphaned: 0, deleted: 0, bytes_freed: 0, failed: Vec::new(), cancelled: false });
+    }
+
+    let entries = fs::read_dir(&assets_dir).map_err(|e| format!("Failed to read assets directory: {}", e))?;
+    let mut scanned = 0;
+    let mut orphans: Vec<(String, PathBuf, u64)> = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else { continue };
+        let Ok(file_name) = entry.file_name().into_string() else { continue };
+        if !metadata.is_file() {
+            continue;
+        }
+        scanned += 1;
+
+        let key = format!("{}/{}", ASSETS_DIR, file_name);
+        if !active.contains(&key) {
+            orphans.push((key, entry.path(), metadata.len()));
+        }
+    }
+
+    let counters = CleanupCounters::default();
+    let workers = std::thread::available_parallelism().map_or(2, |n| n.get()).clamp(1, MAX_CLEANUP_WORKERS).min(orphans.len().max(1));
+    let emit_progress = || {
+        let _ = app_handle.emit("asset-cleanup-progress", CleanupProgress {
+            job_id: job.id().to_string(),
+            processed: counters.processed.load(Ordering::Relaxed),
+            total: orphans.len(),
+            deleted: counters.deleted.load(Ordering::Relaxed),
+            bytes_freed: counters.bytes_freed.load(Ordering::Relaxed),
+        });
+    };
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| scope.spawn(|| delete_orphans(&orphans, &counters, job, data_urls)))
+            .collect();
+
+        while !handles.iter().all(|handle| handle.is_finished()) {
+            emit_progress();
+            std::thread::sleep(CLEANUP_PROGRESS_INTERVAL);
+        }
+    });
+    emit_progress();
+
+    let summary = CleanupSummary {
+        scanned,
+        orphaned: orphans.len(),
+        deleted: counters.deleted.into_inner(),
+        bytes_freed: counters.bytes_freed.into_inner(),
+        failed: counters.failed.into_inner().unwrap(),
+        cancelled: job.is_cancelled(),
+    };
+
+    if !summary.failed.is_empty() {
+        log::warn!("⚠️  Some assets could not be deleted: {:?}", summary.failed);
+    }
+    if summary.cancelled {
+        log::info!("⏹️  Asset cleanup {} cancelled after {} deletions", job.id(), summary.deleted);
+    }
+    log::info!("✅ Cleanup complete: {} orphaned assets deleted ({} bytes freed)", summary.deleted, summary.bytes_freed);
+
+    Ok(summary)
+}
+
+/// One worker: claims orphans by index until none are left or the job is cancelled.
+fn delete_orphans(orphans: &[(String, PathBuf, u64)], counters: &CleanupCounters, job: &JobGuard<'_>, data_urls: &DataUrlCache) {
+    while !job.is_cancelled() {
+        let index = counters.next.fetch_add(1, Ordering::Relaxed);
+        let Some((key, path, size)) = orphans.get(index) else { break };
+
+        match fs::remove_file(path) {
+            Ok(_) => {
+                counters.deleted.fetch_add(1, Ordering::Relaxed);
+                counters.bytes_freed.fetch_add(*size, Ordering::Relaxed);
+                data_urls.invalidate(key);
+                log::debug!("🗑️  Deleted orphaned asset: {}", key);
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to delete {}: {}", key, e);
+                log::warn!("{}", error_msg);
+                counters.failed.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(error_msg);
+            }
+        }
+        counters.processed.fetch_add(1, Ordering::Relaxed);
+    }
+}
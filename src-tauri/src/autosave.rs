@@ -0,0 +1,148 @@
+// autosave.rs - Coalesces frequent project saves into periodic atomic writes
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{project_schema, write_json_atomic, AppState};
+
+pub const DEFAULT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(3);
+const WORKER_TICK: Duration = Duration::from_millis(250);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutosaveFlushed {
+    pub path: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub timestamp: i64,
+}
+
+struct PendingSave {
+    data: serde_json::Value,
+    due_at: Instant,
+}
+
+pub struct AutosaveQueue {
+    pending: Mutex<HashMap<PathBuf, PendingSave>>,
+    interval: Mutex<Duration>,
+    /// Held for a whole flush pass so two writes to the same path can never
+    /// race and let an older payload land last.
+    flush_lock: tokio::sync::Mutex<()>,
+}
+
+impl Default for AutosaveQueue {
+    fn default() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            interval: Mutex::new(DEFAULT_AUTOSAVE_INTERVAL),
+            flush_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+// ============================================================================
+// QUEUE
+// ============================================================================
+
+impl AutosaveQueue {
+    /// Replaces any pending payload for `path`. The flush deadline is only set
+    /// when the path has nothing pending, so a burst of edits is written once.
+    pub fn queue(&self, path: PathBuf, data: serde_json::Value) {
+        let interval = *self.interval.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut pending = self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match pending.get_mut(&path) {
+            Some(entry) => entry.data = data,
+            None => {
+                pending.insert(path, PendingSave {
+                    data,
+                    due_at: Instant::now() + interval,
+                });
+            }
+        }
+    }
+
+    /// Drops the pending payload for `path` ahead of an explicit save. The
+    /// returned guard keeps any flush from writing until that save is done.
+    pub async fn supersede(&self, path: &Path) -> tokio::sync::MutexGuard<'_, ()> {
+        let flush_guard = self.flush_lock.lock().await;
+        self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(path);
+        flush_guard
+    }
+
+    pub fn set_interval(&self, interval: Duration) {
+        *self.interval.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = interval;
+    }
+
+    fn take(&self, due_only: bool) -> Vec<(PathBuf, serde_json::Value)> {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let ready: Vec<PathBuf> = pending.iter()
+            .filter(|(_, entry)| !due_only || entry.due_at <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        ready.into_iter()
+            .filter_map(|path| pending.remove(&path).map(|entry| (path, entry.data)))
+            .collect()
+    }
+
+    /// Writes pending payloads (only those past their deadline when `due_only`)
+    /// and emits an `autosave-flushed` event for each one.
+    pub async fn flush(&self, app_handle: &AppHandle, due_only: bool) -> Vec<AutosaveFlushed> {
+        let _flush_guard = self.flush_lock.lock().await;
+        let mut results = Vec::new();
+
+        for (path, mut data) in self.take(due_only) {
+            project_schema::stamp_current_version(&mut data);
+            let write_path = path.clone();
+            let outcome = tauri::async_runtime::spawn_blocking(move || write_json_atomic(&write_path, &data))
+                .await
+                .unwrap_or_else(|e| Err(format!("Autosave task failed: {}", e)));
+
+            match &outcome {
+                Ok(()) => {
+                    app_handle.state::<AppState>().project_watcher.record_own_write(&path);
+                    log::debug!("💾 Autosaved {}", path.display());
+                }
+                Err(e) => log::error!("❌ Autosave failed for {}: {}", path.display(), e),
+            }
+
+            let flushed = AutosaveFlushed {
+                path: path.to_string_lossy().to_string(),
+                success: outcome.is_ok(),
+                error: outcome.err(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            };
+
+            if let Err(e) = app_handle.emit("autosave-flushed", flushed.clone()) {
+                log::error!("Failed to emit autosave-flushed event: {}", e);
+            }
+
+            results.push(flushed);
+        }
+
+        results
+    }
+}
+
+/// Background task writing out payloads whose deadline has passed.
+pub fn spawn_worker(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(WORKER_TICK);
+        loop {
+            ticker.tick().await;
+            let state = app_handle.state::<AppState>();
+            state.autosave.flush(&app_handle, true).await;
+        }
+    });
+}
@@ -0,0 +1,257 @@
+// command_recordings.rs - Recording remote commands with their timing and replaying them
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::remote_server::{CommandOrigin, RemoteCommand, RemoteServer};
+use crate::{write_json_atomic, AppState};
+
+const RECORDINGS_DIR: &str = "command_recordings";
+const MIN_SPEED_FACTOR: f64 = 0.1;
+const MAX_SPEED_FACTOR: f64 = 10.0;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCommand {
+    /// Milliseconds since the recording started.
+    pub offset_ms: u64,
+    #[serde(rename = "type")]
+    pub command_type: String,
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandRecording {
+    pub id: String,
+    /// Epoch milliseconds.
+    pub started_at: i64,
+    pub duration_ms: u64,
+    pub commands: Vec<RecordedCommand>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingSummary {
+    pub id: String,
+    pub started_at: i64,
+    pub duration_ms: u64,
+    pub command_count: usize,
+}
+
+struct ActiveRecording {
+    recording: CommandRecording,
+    started: Instant,
+}
+
+struct ActiveReplay {
+    /// Tells a finished replay apart from one started after it.
+    generation: u64,
+    recording_id: String,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Default)]
+pub struct CommandRecorder {
+    recording: Mutex<Option<ActiveRecording>>,
+    replay: Mutex<Option<ActiveReplay>>,
+    generation: AtomicU64,
+}
+
+// ============================================================================
+// RECORDING
+// ============================================================================
+
+impl CommandRecorder {
+    pub fn start_recording(&self) -> Result<(), String> {
+        if self.replay.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).is_some() {
+            return Err("Can't record while a replay is running".to_string());
+        }
+        let mut recording = self.recording.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if recording.is_some() {
+            return Err("A command recording is already in progress".to_string());
+        }
+
+        let now = chrono::Local::now();
+        *recording = Some(ActiveRecording {
+            recording: CommandRecording {
+                id: now.format("%Y%m%d-%H%M%S").to_string(),
+                started_at: now.timestamp_millis(),
+                duration_ms: 0,
+                commands: Vec::new(),
+            },
+            started: Instant::now(),
+        });
+        log::info!("⏺️ Started recording remote commands");
+        Ok(())
+    }
+
+    /// Saves the recording and returns its id.
+    pub fn stop_recording(&self, app_dir: &Path) -> Result<String, String> {
+        let Some(active) = self.recording.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take() else {
+            return Err("No command recording in progress".to_string());
+        };
+
+        let mut recording = active.recording;
+        recording.duration_ms = active.started.elapsed().as_millis() as u64;
+        let dir = recordings_dir(app_dir);
+        // Two recordings within a second get a suffix rather than overwrite
+        let base_id = recording.id.clone();
+        let mut suffix = 1;
+        while dir.join(format!("{}.json", recording.id)).exists() {
+            suffix += 1;
+            recording.id = format!("{}-{}", base_id, suffix);
+        }
+
+        write_json_atomic(&dir.join(format!("{}.json", recording.id)), &recording)?;
+        log::info!("⏹️ Saved command recording {} ({} command(s))", recording.id, recording.commands.len());
+        Ok(recording.id)
+    }
+
+    /// Adds an accepted command to the recording in progress, if any.
+    pub fn record(&self, command_type: &str, value: Option<&serde_json::Value>) {
+        let mut recording = self.recording.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(active) = recording.as_mut() else { return };
+
+        active.recording.commands.push(RecordedCommand {
+            offset_ms: active.started.elapsed().as_millis() as u64,
+            command_type: command_type.to_string(),
+            value: value.cloned(),
+        });
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).is_some()
+    }
+}
+
+// ============================================================================
+// REPLAY
+// ============================================================================
+
+impl CommandRecorder {
+    /// Re-drives the recording's commands on their original schedule,
+    /// `speed_factor` times as fast. Emits `command-replay-finished` when
+    /// the last one has run.
+    pub fn start_replay(&self, app_handle: &AppHandle, app_dir: &Path, id: &str, speed_factor: f64) -> Result<(), String> {
+        if !(MIN_SPEED_FACTOR..=MAX_SPEED_FACTOR).contains(&speed_factor) {
+            return Err(format!("Speed factor must be between {} and {}", MIN_SPEED_FACTOR, MAX_SPEED_FACTOR));
+        }
+        if self.is_recording() {
+            return Err("Can't replay while a command recording is in progress".to_string());
+        }
+        let recording = load(app_dir, id)?;
+
+        let mut replay = self.replay.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(active) = replay.as_ref() {
+            return Err(format!("Recording {} is already being replayed", active.recording_id));
+        }
+
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let handle = app_handle.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            run_replay(&handle, &recording, speed_factor).await;
+
+            let state = handle.state::<AppState>();
+            let mut replay = state.command_recorder.replay.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if replay.as_ref().is_some_and(|active| active.generation == generation) {
+                *replay = None;
+            }
+            drop(replay);
+            let _ = handle.emit("command-replay-finished", &recording.id);
+        });
+
+        log::info!("▶️ Replaying command recording {} at {}x", id, speed_factor);
+        *replay = Some(ActiveReplay { generation, recording_id: id.to_string(), task });
+        Ok(())
+    }
+
+    /// Cancels the running replay; false if there was none.
+    pub fn stop_replay(&self) -> bool {
+        match self.replay.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take() {
+            Some(active) => {
+                active.task.abort();
+                log::info!("⏹️ Stopped replay of command recording {}", active.recording_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+async fn run_replay(app_handle: &AppHandle, recording: &CommandRecording, speed_factor: f64) {
+    let start = tokio::time::Instant::now();
+
+    for recorded in &recording.commands {
+        let offset = Duration::from_secs_f64(recorded.offset_ms as f64 / 1000.0 / speed_factor);
+        tokio::time::sleep_until(start + offset).await;
+
+        let command = RemoteCommand {
+            command_type: recorded.command_type.clone(),
+            value: recorded.value.clone(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        RemoteServer::handle_command(command, app_handle, CommandOrigin::Replay, test_mode(app_handle).await).await;
+    }
+}
+
+async fn test_mode(app_handle: &AppHandle) -> bool {
+    let shared_state = app_handle.state::<AppState>().remote_state.lock().await.clone();
+    match shared_state {
+        Some(shared_state) => shared_state.read().await.status.test_mode,
+        None => false,
+    }
+}
+
+// ============================================================================
+// STORAGE
+// ============================================================================
+
+fn recordings_dir(app_dir: &Path) -> PathBuf {
+    app_dir.join(RECORDINGS_DIR)
+}
+
+fn load(app_dir: &Path, id: &str) -> Result<CommandRecording, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid recording id '{}'", id));
+    }
+
+    let path = recordings_dir(app_dir).join(format!("{}.json", id));
+    let contents = fs::read_to_string(&path).map_err(|e| format!("Failed to read recording {}: {}", id, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse recording {}: {}", id, e))
+}
+
+/// Saved recordings, most recent first. Unreadable files are skipped.
+pub fn list(app_dir: &Path) -> Result<Vec<RecordingSummary>, String> {
+    let dir = recordings_dir(app_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut recordings: Vec<RecordingSummary> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read command recordings: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let contents = fs::read_to_string(&path).ok()?;
+            let recording: CommandRecording = serde_json::from_str(&contents).ok()?;
+            Some(RecordingSummary {
+                id: recording.id,
+                started_at: recording.started_at,
+                duration_ms: recording.duration_ms,
+                command_count: recording.commands.len(),
+            })
+        })
+        .collect();
+
+    recordings.sort_by_key(|recording| std::cmp::Reverse(recording.started_at));
+    Ok(recordings)
+}
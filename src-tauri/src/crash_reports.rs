@@ -0,0 +1,202 @@
+// crash_reports.rs - Panic reports written to disk and surfaced on the next launch
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::fs;
+use std::panic::{AssertUnwindSafe, Location};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+
+const CRASHES_DIR: &str = "crashes";
+const REPORTED_SUFFIX: &str = ".reported.txt";
+/// Reported crashes kept around for reference; older ones are deleted.
+const KEPT_REPORTED: usize = 20;
+/// The hook gives up on a slow disk after this long rather than hang a
+/// thread that is already failing.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Set in `setup` once the app data dir is known; panics before that only
+/// reach the default hook.
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+static IN_HOOK: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Set by `catch_expected_panic` while running code whose panics are
+    /// caught and handled, so they aren't reported as crashes.
+    static EXPECTED_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReport {
+    /// File stem, used to mark individual reports as seen.
+    pub id: String,
+    pub path: String,
+    /// First line of the panic message.
+    pub summary: String,
+    pub contents: String,
+}
+
+// ============================================================================
+// HOOK
+// ============================================================================
+
+pub fn crash_dir(app_dir: &Path) -> PathBuf {
+    app_dir.join(CRASHES_DIR)
+}
+
+pub fn set_crash_dir(dir: PathBuf) {
+    let _ = CRASH_DIR.set(dir);
+}
+
+/// Writes a report for every panic, then runs the previous hook (which
+/// prints to stderr). Best-effort: nothing in here may panic or block for
+/// more than `WRITE_TIMEOUT`.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        // A panic while writing a report must not recurse into another one
+        if !EXPECTED_PANIC.with(Cell::get) && !IN_HOOK.swap(true, Ordering::SeqCst) {
+            let _ = std::panic::catch_unwind(AssertUnwindSafe(|| write_report(info.payload(), info.location())));
+            IN_HOOK.store(false, Ordering::SeqCst);
+        }
+        previous(info);
+    }));
+}
+
+/// `catch_unwind` for code known to panic on some systems (like a missing
+/// native library) where the caller recovers; no crash report is written.
+pub fn catch_expected_panic<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    let previous = EXPECTED_PANIC.with(|expected| expected.replace(true));
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    EXPECTED_PANIC.with(|expected| expected.set(previous));
+    result
+}
+
+fn write_report(payload: &(dyn Any + Send), location: Option<&Location>) {
+    let Some(dir) = CRASH_DIR.get() else { return };
+
+    let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "(non-string panic payload)".to_string(),
+    };
+    let location = location
+        .map(|location| format!("{}:{}:{}", location.file(), location.line(), location.column()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let thread = std::thread::current().name().unwrap_or("<unnamed>").to_string();
+    let now = chrono::Local::now();
+    // Captured here, symbolized on the writer thread (that's the slow part)
+    let backtrace = Backtrace::force_capture();
+    let path = dir.join(format!("{}.txt", now.format("%Y-%m-%d_%H-%M-%S%.3f")));
+    let header = format!(
+        "SegiTelep crash report\n\
+         Time: {}\n\
+         Version: {}\n\
+         Platform: {} {}\n\
+         Thread: {}\n\
+         Location: {}\n\
+         \n\
+         Message:\n{}\n",
+        now.to_rfc3339(),
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread,
+        location,
+        message,
+    );
+
+    // On a separate thread so a hung disk can't hold this one forever
+    let (tx, rx) = mpsc::channel();
+    let dir = dir.clone();
+    let spawned = std::thread::Builder::new().name("crash-report".to_string()).spawn(move || {
+        let contents = format!("{}\nBacktrace:\n{}\n", header, backtrace);
+        let result = fs::create_dir_all(&dir).and_then(|_| fs::write(&path, contents));
+        let _ = tx.send(result);
+    });
+    if spawned.is_ok() {
+        let _ = rx.recv_timeout(WRITE_TIMEOUT);
+    }
+}
+
+// ============================================================================
+// NEXT LAUNCH
+// ============================================================================
+
+/// Reports not yet marked as reported, oldest first.
+pub fn unreported(app_dir: &Path) -> Result<Vec<CrashReport>, String> {
+    let dir = crash_dir(app_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read crash reports: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_report(path) && !is_reported(path))
+        .collect();
+    paths.sort();
+
+    Ok(paths.into_iter().filter_map(|path| {
+        let contents = fs::read_to_string(&path).ok()?;
+        let summary = contents.split("Message:\n").nth(1)
+            .and_then(|message| message.lines().next())
+            .unwrap_or_default()
+            .to_string();
+        Some(CrashReport {
+            id: path.file_stem()?.to_string_lossy().to_string(),
+            path: path.to_string_lossy().to_string(),
+            summary,
+            contents,
+        })
+    }).collect())
+}
+
+/// Marks the given reports (all unreported ones when `ids` is `None`) as
+/// reported, and prunes old reported ones. Returns how many were marked.
+pub fn mark_reported(app_dir: &Path, ids: Option<&[String]>) -> Result<usize, String> {
+    let dir = crash_dir(app_dir);
+    let mut marked = 0;
+
+    for report in unreported(app_dir)? {
+        if ids.is_some_and(|ids| !ids.contains(&report.id)) {
+            continue;
+        }
+        let target = dir.join(format!("{}{}", report.id, REPORTED_SUFFIX));
+        fs::rename(&report.path, &target).map_err(|e| format!("Failed to mark crash report {}: {}", report.id, e))?;
+        marked += 1;
+    }
+
+    prune_reported(&dir);
+    Ok(marked)
+}
+
+fn prune_reported(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    let mut reported: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| is_reported(path)).collect();
+    reported.sort();
+
+    let excess = reported.len().saturating_sub(KEPT_REPORTED);
+    for path in &reported[..excess] {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn is_report(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "txt")
+}
+
+fn is_reported(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name.to_string_lossy().ends_with(REPORTED_SUFFIX))
+}
@@ -0,0 +1,166 @@
+// cursor.rs - Cursor hiding over the prompter, manual and idle-based
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::async_runtime::JoinHandle;
+use tauri::{Manager, Window};
+
+use crate::AppState;
+
+/// Tauri has no pointer-motion window event, so the auto-hide task samples
+/// the cursor position at this rate.
+const CURSOR_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CursorState {
+    /// What was asked for via `set_cursor_visible`.
+    pub hidden: bool,
+    /// Idle timeout in seconds when auto-hide is on.
+    pub autohide_seconds: Option<f64>,
+}
+
+#[derive(Default)]
+struct WindowCursor {
+    /// Explicitly hidden via `set_cursor_visible(false)`.
+    hidden: bool,
+    autohide: Option<(f64, JoinHandle<()>)>,
+}
+
+#[derive(Default)]
+pub struct CursorControl {
+    windows: Mutex<HashMap<String, WindowCursor>>,
+}
+
+// ============================================================================
+// CONTROL
+// ============================================================================
+
+impl CursorControl {
+    pub fn set_visible(&self, window: &Window, visible: bool) -> Result<CursorState, String> {
+        window.set_cursor_visible(visible)
+            .map_err(|e| format!("Failed to change cursor visibility: {}", e))?;
+
+        let mut windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entry = windows.entry(window.label().to_string()).or_default();
+        entry.hidden = !visible;
+
+        Ok(state_of(entry))
+    }
+
+    /// Hides the cursor after `seconds` without movement and shows it again on
+    /// motion. `0` turns auto-hide off (and shows the cursor).
+    pub fn set_autohide(&self, window: &Window, seconds: f64) -> Result<CursorState, String> {
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err("Auto-hide delay must be a non-negative number of seconds".to_string());
+        }
+
+        let mut windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entry = windows.entry(window.label().to_string()).or_default();
+
+        if let Some((_, task)) = entry.autohide.take() {
+            task.abort();
+            if !entry.hidden {
+                let _ = window.set_cursor_visible(true);
+            }
+        }
+
+        if seconds > 0.0 {
+            let task = spawn_autohide(window.clone(), Duration::from_secs_f64(seconds));
+            entry.autohide = Some((seconds, task));
+            log::info!("🖱️  Cursor auto-hide on '{}' after {}s", window.label(), seconds);
+        }
+
+        Ok(state_of(entry))
+    }
+
+    pub fn state(&self, label: &str) -> CursorState {
+        let windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        windows.get(label).map(state_of).unwrap_or(CursorState {
+            hidden: false,
+            autohide_seconds: None,
+        })
+    }
+
+    /// The cursor must never stay hidden once the user is working in another
+    /// window; it is hidden again on refocus if that was requested.
+    pub fn on_focus_changed(&self, window: &Window, focused: bool) {
+        let hidden = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(window.label())
+            .is_some_and(|entry| entry.hidden);
+
+        if !focused {
+            let _ = window.set_cursor_visible(true);
+        } else if hidden {
+            let _ = window.set_cursor_visible(false);
+        }
+    }
+
+    pub fn forget(&self, label: &str) {
+        if let Some(entry) = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(label) {
+            if let Some((_, task)) = entry.autohide {
+                task.abort();
+            }
+        }
+    }
+
+    /// Stops every auto-hide task and shows the cursor everywhere (app exit).
+    pub fn restore_all(&self, app_handle: &tauri::AppHandle) {
+        let windows: Vec<(String, WindowCursor)> = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).drain().collect();
+
+        for (label, entry) in windows {
+            if let Some((_, task)) = entry.autohide {
+                task.abort();
+            }
+            if let Some(window) = app_handle.get_webview_window(&label) {
+                let _ = window.set_cursor_visible(true);
+            }
+        }
+    }
+}
+
+fn state_of(entry: &WindowCursor) -> CursorState {
+    CursorState {
+        hidden: entry.hidden,
+        autohide_seconds: entry.autohide.as_ref().map(|(seconds, _)| *seconds),
+    }
+}
+
+fn spawn_autohide(window: Window, idle: Duration) -> JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(CURSOR_POLL_INTERVAL);
+        let mut last_position = None;
+        let mut last_motion = Instant::now();
+        let mut auto_hidden = false;
+
+        loop {
+            ticker.tick().await;
+
+            let focused = window.is_focused().unwrap_or(false);
+            let position = window.cursor_position().ok().map(|p| (p.x, p.y));
+            let moved = position != last_position;
+            last_position = position;
+
+            if moved || !focused {
+                last_motion = Instant::now();
+                if auto_hidden {
+                    auto_hidden = false;
+                    // Leave it hidden if it was hidden explicitly as well
+                    let explicit = window.state::<AppState>().cursor.state(window.label()).hidden;
+                    if !explicit || !focused {
+                        let _ = window.set_cursor_visible(true);
+                    }
+                }
+            } else if !auto_hidden && last_motion.elapsed() >= idle {
+                auto_hidden = true;
+                let _ = window.set_cursor_visible(false);
+            }
+        }
+    })
+}
@@ -0,0 +1,102 @@
+// deep_link.rs - segitelep:// links from emails, QR codes and the browser
+
+use tauri::{AppHandle, Manager, Url};
+
+use crate::{file_open, fs_sandbox, AppState};
+
+/// Scheme registered under `plugins.deep-link` in `tauri.conf.json`.
+pub const DEEP_LINK_SCHEME: &str = "segitelep";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Everything a link is allowed to do. Links come from outside the app, so
+/// anything not listed here is rejected rather than passed on.
+#[derive(Debug, Clone, PartialEq)]
+enum DeepLinkAction {
+    /// `segitelep://open?path=<percent-encoded absolute path>`
+    OpenProject { path: String },
+    /// `segitelep://remote/start`
+    StartRemote,
+}
+
+// ============================================================================
+// PARSING
+// ============================================================================
+
+fn parse(url: &Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("unexpected scheme '{}'", url.scheme()));
+    }
+    if !url.username().is_empty() || url.password().is_some() || url.port().is_some() || url.fragment().is_some() {
+        return Err("unexpected credentials, port or fragment".to_string());
+    }
+
+    let path = url.path().trim_end_matches('/');
+    // `query_pairs` percent-decodes keys and values
+    let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+
+    match (url.host_str(), path) {
+        (Some("open"), "") => match query.as_slice() {
+            [(key, value)] if key == "path" && !value.is_empty() => {
+                Ok(DeepLinkAction::OpenProject { path: value.clone() })
+            }
+            _ => Err("'open' takes exactly one non-empty 'path' parameter".to_string()),
+        },
+        (Some("remote"), "/start") if query.is_empty() => Ok(DeepLinkAction::StartRemote),
+        (host, path) => Err(format!("unknown action '{}{}'", host.unwrap_or_default(), path)),
+    }
+}
+
+// ============================================================================
+// DISPATCH
+// ============================================================================
+
+/// Carries out each recognised link. Malformed or unknown links are logged
+/// and dropped; events for the frontend are queued until it is ready.
+pub fn handle_urls(app_handle: &AppHandle, urls: Vec<Url>) {
+    for url in urls {
+        let action = match parse(&url) {
+            Ok(action) => action,
+            Err(reason) => {
+                log::warn!("🔗 Ignoring deep link {}: {}", url, reason);
+                continue;
+            }
+        };
+
+        log::info!("🔗 Deep link: {:?}", action);
+        match action {
+            DeepLinkAction::OpenProject { path } => open_project(app_handle, &path),
+            DeepLinkAction::StartRemote => start_remote(app_handle),
+        }
+    }
+}
+
+fn open_project(app_handle: &AppHandle, raw: &str) {
+    match fs_sandbox::resolve_path(app_handle, raw) {
+        Ok(path) => {
+            file_open::focus_main_window(app_handle);
+            file_open::request_open(app_handle, vec![path]);
+        }
+        Err(e) => log::warn!("🔗 Ignoring deep link to {}: {}", raw, e),
+    }
+}
+
+fn start_remote(app_handle: &AppHandle) {
+    file_open::focus_main_window(app_handle);
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let result = crate::start_remote_server(app_handle.clone(), state.clone()).await;
+
+        match result {
+            Ok(server) => state.frontend_events.emit(&app_handle, "remote-server-started", server),
+            Err(e) => {
+                log::error!("Failed to start remote server from deep link: {}", e);
+                state.frontend_events.emit(&app_handle, "remote-server-error", e);
+            }
+        }
+    });
+}
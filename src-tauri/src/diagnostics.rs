@@ -0,0 +1,279 @@
+// diagnostics.rs - Environment and server report for support requests
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::{app_data_dir, assets, disk_space, logging, AppState};
+
+const LOG_TAIL_LINES: usize = 200;
+/// Enough for `LOG_TAIL_LINES` of any sane length without reading the whole file.
+const LOG_TAIL_BYTES: u64 = 256 * 1024;
+const REPORT_PREFIX: &str = "diagnostics";
+
+// ============================================================================
+// REPORT
+// ============================================================================
+
+/// Collects every section, redacts the home directory to `~`, writes a
+/// timestamped `.txt` copy into the logs directory and returns the report.
+/// Never fails: a section that can't be gathered reads
+/// `"<unavailable: reason>"`, and so does `report_path` if the copy can't be
+/// written.
+pub async fn generate(app_handle: &AppHandle) -> Value {
+    let app_dir = app_data_dir(app_handle);
+
+    let mut report = Map::new();
+    report.insert("generated_at".to_string(), json!(chrono::Local::now().to_rfc3339()));
+    report.insert("app".to_string(), app_info(app_handle));
+    report.insert("system".to_string(), system_info());
+    report.insert("network_interfaces".to_string(), section(network_interfaces()));
+    report.insert("remote_server".to_string(), remote_server(app_handle).await);
+    report.insert("remote_clients".to_string(), section(remote_clients(app_handle).await));
+
+    let blocking_dir = app_dir.clone();
+    let (assets, disk, log_tail) = tauri::async_runtime::spawn_blocking(move || match blocking_dir {
+        Ok(dir) => (section(assets_usage(&dir)), section(disk_space::disk_space(&dir)), section(log_tail(&dir))),
+        Err(e) => (unavailable(&e), unavailable(&e), unavailable(&e)),
+    })
+    .await
+    .unwrap_or_else(|e| {
+        let reason = format!("task failed: {}", e);
+        (unavailable(&reason), unavailable(&reason), unavailable(&reason))
+    });
+    report.insert("assets".to_string(), assets);
+    report.insert("disk_space".to_string(), disk);
+    report.insert("log_tail".to_string(), log_tail);
+
+    let mut report = Value::Object(report);
+    if let Some(home) = dirs::home_dir() {
+        redact(&mut report, &home.to_string_lossy());
+    }
+
+    let report_path = match &app_dir {
+        Ok(dir) => write_text_copy(dir, &report),
+        Err(e) => Err(e.clone()),
+    };
+    if let Value::Object(map) = &mut report {
+        map.insert("report_path".to_string(), section(report_path.map(|path| redact_str(&path))));
+    }
+
+    log::info!("🩺 Generated diagnostic report");
+    report
+}
+
+fn section<T: Serialize>(result: Result<T, String>) -> Value {
+    match result.and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string())) {
+        Ok(value) => value,
+        Err(reason) => unavailable(&reason),
+    }
+}
+
+fn unavailable(reason: &str) -> Value {
+    Value::String(format!("<unavailable: {}>", reason))
+}
+
+// ============================================================================
+// SECTIONS
+// ============================================================================
+
+fn app_info(app_handle: &AppHandle) -> Value {
+    let package = app_handle.package_info();
+    json!({
+        "name": package.name,
+        "version": package.version.to_string(),
+        "identifier": app_handle.config().identifier,
+        "tauri_version": tauri::VERSION,
+        "debug_build": cfg!(debug_assertions),
+    })
+}
+
+fn system_info() -> Value {
+    json!({
+        "os": std::env::consts::OS,
+        "family": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "os_version": section(os_version()),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn os_version() -> Result<String, String> {
+    let release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+    let name = release.lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+        .map(|name| name.trim_matches('"').to_string())
+        .unwrap_or_else(|| "Linux".to_string());
+    let kernel = fs::read_to_string("/proc/sys/kernel/osrelease").map_err(|e| e.to_string())?;
+
+    Ok(format!("{} (kernel {})", name, kernel.trim()))
+}
+
+#[cfg(target_os = "macos")]
+fn os_version() -> Result<String, String> {
+    let output = std::process::Command::new("sw_vers").arg("-productVersion").output().map_err(|e| e.to_string())?;
+    Ok(format!("macOS {}", String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+#[cfg(target_os = "windows")]
+fn os_version() -> Result<String, String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("cmd")
+        .args(["/C", "ver"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn os_version() -> Result<String, String> {
+    Err("not detected on this platform".to_string())
+}
+
+fn network_interfaces() -> Result<Vec<Value>, String> {
+    let interfaces = local_ip_address::list_afinet_netifas().map_err(|e| e.to_string())?;
+    Ok(interfaces.into_iter()
+        .map(|(name, ip)| json!({ "name": name, "address": ip.to_string(), "loopback": ip.is_loopback() }))
+        .collect())
+}
+
+async fn remote_server(app_handle: &AppHandle) -> Value {
+    let state = app_handle.state::<AppState>();
+    let server = state.remote_server.lock().await.clone();
+    json!(server)
+}
+
+async fn remote_clients(app_handle: &AppHandle) -> Result<Value, String> {
+    let state = app_handle.state::<AppState>();
+    let shared = state.remote_state.lock().await.clone();
+    let Some(shared) = shared else {
+        return Err("remote server not running".to_string());
+    };
+
+    let server = shared.read().await;
+    Ok(json!({
+        "connected_clients": server.status.connected_clients,
+        "broadcast_subscribers": server.broadcast_tx.receiver_count(),
+        "is_live": server.status.is_live,
+        "is_playing": server.status.is_playing,
+        "total_segments": server.status.total_segments,
+    }))
+}
+
+fn assets_usage(app_dir: &Path) -> Result<Value, String> {
+    let dir = app_dir.join(assets::ASSETS_DIR);
+    if !dir.exists() {
+        return Ok(json!({ "file_count": 0, "total_bytes": 0 }));
+    }
+
+    let entries = fs::read_dir(&dir).map_err(|e| e.to_string())?;
+    let (mut file_count, mut total_bytes) = (0u64, 0u64);
+    for metadata in entries.flatten().filter_map(|entry| entry.metadata().ok()) {
+        if metadata.is_file() {
+            file_count += 1;
+            total_bytes += metadata.len();
+        }
+    }
+
+    Ok(json!({ "file_count": file_count, "total_bytes": total_bytes }))
+}
+
+/// Last `LOG_TAIL_LINES` lines of the active log file.
+fn log_tail(app_dir: &Path) -> Result<Vec<String>, String> {
+    let path = logging::log_file_path(app_dir);
+    let mut file = File::open(&path).map_err(|e| e.to_string())?;
+
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let start = len.saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).map_err(|e| e.to_string())?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut lines: Vec<&str> = text.lines().collect();
+    // Starting mid-file, the first line is probably cut off
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(LOG_TAIL_LINES);
+    Ok(lines[skip..].iter().map(|line| line.to_string()).collect())
+}
+
+// ============================================================================
+// REDACTION AND OUTPUT
+// ============================================================================
+
+/// Replaces the home directory in every string with `~`, so user names in
+/// paths (and in log lines quoting paths) don't end up in support threads.
+fn redact(value: &mut Value, home: &str) {
+    match value {
+        Value::String(text) => *text = replace_home(text, home),
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, home)),
+        Value::Object(map) => map.values_mut().for_each(|item| redact(item, home)),
+        _ => {}
+    }
+}
+
+fn redact_str(text: &str) -> String {
+    match dirs::home_dir() {
+        Some(home) => replace_home(text, &home.to_string_lossy()),
+        None => text.to_string(),
+    }
+}
+
+fn replace_home(text: &str, home: &str) -> String {
+    let home = home.trim_end_matches(['/', '\\']);
+    if home.len() < 2 {
+        return text.to_string();
+    }
+
+    // Windows paths may show up with either separator
+    let mut out = text.replace(home, "~");
+    let alternate = home.replace('\\', "/");
+    if alternate != home {
+        out = out.replace(&alternate, "~");
+    }
+    out
+}
+
+/// `logs/diagnostics-<timestamp>.txt`: the report sections, then the log
+/// tail as plain lines.
+fn write_text_copy(app_dir: &Path, report: &Value) -> Result<String, String> {
+    let dir = logging::log_dir(app_dir);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create log directory: {}", e))?;
+
+    let mut text = String::from("SegiTelep diagnostic report\n");
+    if let Value::Object(map) = report {
+        for (name, value) in map {
+            text.push_str(&format!("\n== {} ==\n", name));
+            match value {
+                Value::Array(lines) if name == "log_tail" => {
+                    for line in lines {
+                        text.push_str(line.as_str().unwrap_or_default());
+                        text.push('\n');
+                    }
+                }
+                Value::String(s) => {
+                    text.push_str(s);
+                    text.push('\n');
+                }
+                other => {
+                    text.push_str(&serde_json::to_string_pretty(other).unwrap_or_default());
+                    text.push('\n');
+                }
+            }
+        }
+    }
+
+    let path = dir.join(format!("{}-{}.txt", REPORT_PREFIX, chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")));
+    fs::write(&path, text).map_err(|e| format!("Failed to write report: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
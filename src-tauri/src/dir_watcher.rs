@@ -0,0 +1,189 @@
+// dir_watcher.rs - Watches user folders (e.g. the media bin) for file changes
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use notify_debouncer_full::notify::event::ModifyKind;
+use notify_debouncer_full::notify::{EventKind, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::AppState;
+
+/// Upper bound on concurrent directory watchers, so a frontend bug that
+/// re-registers on every render can't exhaust inotify/FSEvents handles.
+pub const MAX_DIR_WATCHERS: usize = 16;
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+/// How often watched roots are checked for removal/recreation.
+const ROOT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FsChangeEvent {
+    pub watcher_id: String,
+    /// One of "created", "modified", "removed", "renamed".
+    pub kind: &'static str,
+    pub path: String,
+    /// Previous path for renames, when the platform reports both ends.
+    pub from: Option<String>,
+}
+
+type DirDebouncer = Debouncer<RecommendedWatcher, RecommendedCache>;
+
+struct DirWatch {
+    root: PathBuf,
+    recursive: bool,
+    /// `None` while the watched directory doesn't exist.
+    debouncer: Option<DirDebouncer>,
+}
+
+#[derive(Default)]
+pub struct DirWatchers {
+    watches: Mutex<HashMap<String, DirWatch>>,
+}
+
+// ============================================================================
+// WATCHER REGISTRY
+// ============================================================================
+
+impl DirWatchers {
+    pub fn watch(&self, app_handle: &AppHandle, root: PathBuf, recursive: bool) -> Result<String, String> {
+        if !root.is_dir() {
+            return Err(format!("Not a directory: {}", root.display()));
+        }
+
+        let mut watches = self.watches.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if watches.len() >= MAX_DIR_WATCHERS {
+            return Err(format!("Too many directory watchers (limit {})", MAX_DIR_WATCHERS));
+        }
+
+        let watcher_id = uuid::Uuid::new_v4().to_string();
+        let debouncer = start_debouncer(app_handle.clone(), watcher_id.clone(), &root, recursive)?;
+
+        log::info!("👀 Watching directory {} ({})", root.display(), watcher_id);
+        watches.insert(watcher_id.clone(), DirWatch {
+            root,
+            recursive,
+            debouncer: Some(debouncer),
+        });
+
+        Ok(watcher_id)
+    }
+
+    pub fn unwatch(&self, watcher_id: &str) -> bool {
+        let removed = self.watches.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(watcher_id);
+
+        match removed {
+            Some(watch) => {
+                if let Some(debouncer) = watch.debouncer {
+                    debouncer.stop_nonblocking();
+                }
+                log::info!("🙈 Stopped watching directory {}", watch.root.display());
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn stop_all(&self) {
+        let watches: Vec<DirWatch> = self.watches.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).drain().map(|(_, w)| w).collect();
+        for debouncer in watches.into_iter().filter_map(|w| w.debouncer) {
+            debouncer.stop_nonblocking();
+        }
+    }
+
+    /// Drops watches whose root disappeared and re-registers them once the
+    /// directory is recreated (e.g. a footage folder deleted and restored).
+    fn check_roots(&self, app_handle: &AppHandle) {
+        let mut watches = self.watches.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        for (watcher_id, watch) in watches.iter_mut() {
+            let exists = watch.root.is_dir();
+
+            if watch.debouncer.is_some() && !exists {
+                if let Some(debouncer) = watch.debouncer.take() {
+                    debouncer.stop_nonblocking();
+                }
+                log::warn!("📁 Watched directory removed, waiting for it to return: {}", watch.root.display());
+                emit_change(app_handle, watcher_id, "removed", &watch.root, None);
+            } else if watch.debouncer.is_none() && exists {
+                match start_debouncer(app_handle.clone(), watcher_id.clone(), &watch.root, watch.recursive) {
+                    Ok(debouncer) => {
+                        watch.debouncer = Some(debouncer);
+                        log::info!("👀 Re-registered watcher for recreated directory {}", watch.root.display());
+                        emit_change(app_handle, watcher_id, "created", &watch.root, None);
+                    }
+                    Err(e) => log::warn!("Failed to re-register watcher for {}: {}", watch.root.display(), e),
+                }
+            }
+        }
+    }
+}
+
+fn start_debouncer(
+    app_handle: AppHandle,
+    watcher_id: String,
+    root: &Path,
+    recursive: bool,
+) -> Result<DirDebouncer, String> {
+    let mut debouncer = new_debouncer(DEBOUNCE_WINDOW, None, move |result: DebounceEventResult| {
+        match result {
+            Ok(events) => {
+                for event in events {
+                    let kind = match event.kind {
+                        EventKind::Create(_) => "created",
+                        EventKind::Modify(ModifyKind::Name(_)) => "renamed",
+                        EventKind::Modify(_) | EventKind::Any | EventKind::Other => "modified",
+                        EventKind::Remove(_) => "removed",
+                        EventKind::Access(_) => continue,
+                    };
+
+                    let Some(path) = event.paths.last() else { continue };
+                    let from = (kind == "renamed" && event.paths.len() > 1).then(|| event.paths[0].as_path());
+                    emit_change(&app_handle, &watcher_id, kind, path, from);
+                }
+            }
+            Err(errors) => {
+                for e in errors {
+                    log::warn!("Directory watcher {} error: {}", watcher_id, e);
+                }
+            }
+        }
+    }).map_err(|e| format!("Failed to create directory watcher: {}", e))?;
+
+    let mode = if recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    debouncer.watch(root, mode)
+        .map_err(|e| format!("Failed to watch '{}': {}", root.display(), e))?;
+
+    Ok(debouncer)
+}
+
+fn emit_change(app_handle: &AppHandle, watcher_id: &str, kind: &'static str, path: &Path, from: Option<&Path>) {
+    let payload = FsChangeEvent {
+        watcher_id: watcher_id.to_string(),
+        kind,
+        path: path.to_string_lossy().to_string(),
+        from: from.map(|p| p.to_string_lossy().to_string()),
+    };
+
+    if let Err(e) = app_handle.emit("fs-change", payload) {
+        log::error!("Failed to emit fs-change event: {}", e);
+    }
+}
+
+/// Background task keeping watchers alive across removal/recreation of their roots.
+pub fn spawn_supervisor(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(ROOT_CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            app_handle.state::<AppState>().dir_watchers.check_roots(&app_handle);
+        }
+    });
+}
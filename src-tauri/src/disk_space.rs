@@ -0,0 +1,59 @@
+// disk_space.rs - Free-space queries and pre-write space validation
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::CommandError;
+
+/// Headroom left untouched on every volume so a large write never fills the
+/// disk completely (the OS and other apps need room too).
+pub const SAFETY_MARGIN_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskSpace {
+    /// The existing directory actually queried (nearest ancestor of the input).
+    pub path: String,
+    pub available_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// Reports space on the volume containing `path`, which may not exist yet.
+pub fn disk_space(path: &Path) -> Result<DiskSpace, String> {
+    let existing = nearest_existing(path)
+        .ok_or_else(|| format!("No existing ancestor for path: {}", path.display()))?;
+
+    let stats = fs4::statvfs(&existing)
+        .map_err(|e| format!("Failed to query disk space for '{}': {}", existing.display(), e))?;
+
+    Ok(DiskSpace {
+        path: existing.to_string_lossy().to_string(),
+        available_bytes: stats.available_space(),
+        total_bytes: stats.total_space(),
+    })
+}
+
+/// Fails with `InsufficientSpace` when writing `required` more bytes at
+/// `path` would eat into the safety margin.
+pub fn ensure_space(path: &Path, required: u64) -> Result<(), CommandError> {
+    let space = disk_space(path)?;
+    let usable = space.available_bytes.saturating_sub(SAFETY_MARGIN_BYTES);
+
+    if required > usable {
+        log::warn!("💽 Refusing write of {} bytes to {}: only {} bytes available", required, path.display(), space.available_bytes);
+        return Err(CommandError::InsufficientSpace {
+            path: path.to_string_lossy().to_string(),
+            required_bytes: required,
+            available_bytes: space.available_bytes,
+            margin_bytes: SAFETY_MARGIN_BYTES,
+        });
+    }
+
+    Ok(())
+}
+
+fn nearest_existing(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|p| p.exists())
+        .map(Path::to_path_buf)
+}
@@ -0,0 +1,183 @@
+// drop_import.rs - Files dropped onto a window, imported natively
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::zip_archive::{self, OverwritePolicy};
+use crate::script_import::{self, ImportedScript};
+use crate::{app_data_dir, assets, file_open, AppState};
+
+/// Project bundle: a zip holding a `.segitelep` project and its media.
+pub const PACKAGE_EXTENSION: &str = "segipack";
+const IMPORTS_DIR: &str = "imports";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropKind {
+    Script,
+    Asset,
+    Project,
+    Package,
+    Unsupported,
+}
+
+/// Outcome for one dropped path. Exactly one of the payload fields is set on
+/// success, matching `kind`; `error` is set on failure.
+#[derive(Debug, Clone, Serialize)]
+pub struct DroppedFile {
+    pub path: String,
+    pub kind: DropKind,
+    pub success: bool,
+    /// Store-relative path from the asset store (`global_assets/...`).
+    pub asset_path: Option<String>,
+    /// Project to open, for `project` and `package` drops.
+    pub project_path: Option<String>,
+    /// Parsed script, for `script` drops.
+    pub script: Option<ImportedScript>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FilesImported {
+    pub window_label: String,
+    pub files: Vec<DroppedFile>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+// ============================================================================
+// IMPORT
+// ============================================================================
+
+/// Imports everything dropped on `window_label` off the main thread and
+/// reports the whole batch in one `files-imported` event to that window.
+pub fn handle_drop(app_handle: &AppHandle, window_label: &str, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    let window_label = window_label.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let files: Vec<DroppedFile> = paths.iter().map(|path| import_one(&app_handle, path)).collect();
+        let succeeded = files.iter().filter(|file| file.success).count();
+        let failed = files.len() - succeeded;
+
+        log::info!("📥 Imported {} dropped file(s), {} failed", succeeded, failed);
+
+        let _ = app_handle.emit_to(window_label.as_str(), "files-imported", FilesImported {
+            window_label: window_label.clone(),
+            files,
+            succeeded,
+            failed,
+        });
+    });
+}
+
+pub fn classify(path: &Path) -> DropKind {
+    if path.is_dir() {
+        return DropKind::Unsupported;
+    }
+
+    let extension = path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if extension == file_open::PROJECT_EXTENSION {
+        return DropKind::Project;
+    }
+    if extension == PACKAGE_EXTENSION {
+        return DropKind::Package;
+    }
+    if script_import::SCRIPT_EXTENSIONS.contains(&extension.as_str()) {
+        return DropKind::Script;
+    }
+
+    let is_media = mime_guess::from_ext(&extension).first().is_some_and(|mime| {
+        [mime_guess::mime::IMAGE, mime_guess::mime::VIDEO, mime_guess::mime::AUDIO].contains(&mime.type_())
+    });
+    if is_media { DropKind::Asset } else { DropKind::Unsupported }
+}
+
+fn import_one(app_handle: &AppHandle, path: &Path) -> DroppedFile {
+    let kind = classify(path);
+    let mut result = DroppedFile {
+        path: path.to_string_lossy().to_string(),
+        kind,
+        success: false,
+        asset_path: None,
+        project_path: None,
+        script: None,
+        error: None,
+    };
+
+    let outcome = match kind {
+        DropKind::Script => script_import::import_script(path)
+            .map(|script| result.script = Some(script))
+            .map_err(|e| e.to_string()),
+        DropKind::Asset => app_data_dir(app_handle)
+            .and_then(|app_dir| assets::store_from_path(&app_dir, path).map_err(|e| e.to_string()))
+            .map(|asset| result.asset_path = Some(asset)),
+        DropKind::Project => file_open::validate_project(path)
+            .map(|project| result.project_path = Some(project.to_string_lossy().to_string())),
+        DropKind::Package => import_package(app_handle, path)
+            .map(|project| result.project_path = Some(project.to_string_lossy().to_string())),
+        DropKind::Unsupported if path.is_dir() => Err("Folders can't be imported".to_string()),
+        DropKind::Unsupported => Err("Unsupported file type".to_string()),
+    };
+
+    match outcome {
+        Ok(()) => result.success = true,
+        Err(e) => {
+            log::warn!("⚠️  Dropped file {} not imported: {}", result.path, e);
+            result.error = Some(e);
+        }
+    }
+    result
+}
+
+/// Unpacks into `imports/<name>-<timestamp>` under the app data dir and
+/// returns the project file inside.
+fn import_package(app_handle: &AppHandle, path: &Path) -> Result<PathBuf, String> {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let destination = app_data_dir(app_handle)?
+        .join(IMPORTS_DIR)
+        .join(format!("{}-{}", stem, chrono::Utc::now().timestamp_millis()));
+
+    let state = app_handle.state::<AppState>();
+    let job_id = format!("drop-import-{}", uuid::Uuid::new_v4());
+    zip_archive::extract_zip(app_handle, &state.archive_jobs, &job_id, path, &destination, OverwritePolicy::Error)
+        .map_err(|e| format!("Failed to unpack package: {}", e))?;
+
+    let project = find_project_file(&destination)
+        .ok_or_else(|| format!("Package contains no .{} project", file_open::PROJECT_EXTENSION))?;
+    file_open::validate_project(&project)
+}
+
+/// Project file at the top of `dir`, else the first found in its
+/// subdirectories (alphabetical order).
+fn find_project_file(dir: &Path) -> Option<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir).ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    let is_project = |path: &PathBuf| {
+        path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(file_open::PROJECT_EXTENSION))
+    };
+    if let Some(project) = entries.iter().find(|path| is_project(path)) {
+        return Some(project.clone());
+    }
+
+    entries.iter()
+        .filter(|path| path.is_dir())
+        .find_map(|subdir| find_project_file(subdir))
+}
@@ -0,0 +1,102 @@
+// error.rs - Structured errors for commands whose failures the UI branches on
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Serialized as `{ "kind": "...", ...fields }` so the frontend can switch on
+/// `kind` instead of pattern-matching message strings.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandError {
+    /// The file was written by a newer SegiTelep than this build understands.
+    NewerSchemaVersion { found: u32, supported: u32 },
+    /// A ranged read started beyond the end of the file.
+    OffsetPastEof { offset: u64, file_size: u64 },
+    /// The path the command needed does not exist.
+    NotFound { path: String },
+    /// The destination exists and the caller did not allow overwriting it.
+    AlreadyExists { path: String },
+    /// Source and destination resolve to the same file.
+    SamePath { path: String },
+    /// A non-recursive delete hit a directory that still has entries.
+    NotEmpty { path: String, entry_count: usize },
+    /// The path is a system or user root that must never be deleted.
+    ProtectedPath { path: String },
+    /// The file looks like binary data rather than text.
+    NotText { path: String },
+    /// The file exceeds the size limit for the requested operation.
+    FileTooLarge { path: String, size: u64, limit: u64 },
+    /// The OS refused access to the path.
+    PermissionDenied { path: String },
+    /// The operation was cancelled by the caller before it finished.
+    Cancelled,
+    /// The volume doesn't have room for the write (after the safety margin).
+    InsufficientSpace { path: String, required_bytes: u64, available_bytes: u64, margin_bytes: u64 },
+    /// No application is registered for the file type; the UI can offer
+    /// "show in folder" instead.
+    NoAssociatedApp { path: String },
+    /// The kiosk exit PIN was missing or wrong.
+    InvalidPin,
+    /// The document is encrypted and can't be read without its password.
+    PasswordProtected { path: String },
+    /// The document is damaged or not what its extension claims.
+    CorruptDocument { path: String, reason: String },
+    /// The platform, compositor or window system can't do this.
+    Unsupported { feature: String, reason: String },
+    /// Anything the UI only needs to display.
+    Other { message: String },
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::NewerSchemaVersion { found, supported } => write!(
+                f,
+                "This file was created by a newer version of SegiTelep (schema v{}, this app supports up to v{})",
+                found, supported
+            ),
+            CommandError::OffsetPastEof { offset, file_size } => write!(
+                f,
+                "Offset {} is past the end of the file ({} bytes)",
+                offset, file_size
+            ),
+            CommandError::NotFound { path } => write!(f, "File not found: {}", path),
+            CommandError::AlreadyExists { path } => write!(f, "Destination already exists: {}", path),
+            CommandError::SamePath { path } => write!(f, "Source and destination are the same file: {}", path),
+            CommandError::NotEmpty { path, entry_count } => write!(
+                f,
+                "Directory is not empty ({} entries): {}",
+                entry_count, path
+            ),
+            CommandError::ProtectedPath { path } => write!(f, "Refusing to delete protected location: {}", path),
+            CommandError::NotText { path } => write!(f, "Not a text file: {}", path),
+            CommandError::FileTooLarge { path, size, limit } => write!(
+                f,
+                "File is too large ({} bytes, limit {} bytes): {}",
+                size, limit, path
+            ),
+            CommandError::PermissionDenied { path } => write!(f, "Permission denied: {}", path),
+            CommandError::Cancelled => write!(f, "Operation cancelled"),
+            CommandError::InsufficientSpace { path, required_bytes, available_bytes, .. } => write!(
+                f,
+                "Not enough disk space to write {} bytes to {} ({} bytes available)",
+                required_bytes, path, available_bytes
+            ),
+            CommandError::NoAssociatedApp { path } => write!(f, "No application is associated with this file type: {}", path),
+            CommandError::InvalidPin => write!(f, "Incorrect PIN"),
+            CommandError::PasswordProtected { path } => write!(f, "Document is password-protected: {}", path),
+            CommandError::CorruptDocument { path, reason } => write!(f, "Document could not be read ({}): {}", reason, path),
+            CommandError::Unsupported { feature, reason } => write!(f, "{} is not supported here: {}", feature, reason),
+            CommandError::Other { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Other { message }
+    }
+}
@@ -0,0 +1,148 @@
+// file_hash.rs - Streaming file hashing with progress and cancellation
+
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::CommandError;
+use crate::jobs::{JobGuard, JobRegistry};
+
+const HASH_CHUNK_SIZE: usize = 1024 * 1024;
+/// Files smaller than this hash in well under a second; no progress events.
+const HASH_PROGRESS_THRESHOLD: u64 = 32 * 1024 * 1024;
+const HASH_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha1,
+    Blake3,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileHash {
+    pub algorithm: HashAlgorithm,
+    pub hex_digest: String,
+    pub bytes_hashed: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HashProgress {
+    job_id: String,
+    bytes_hashed: u64,
+    total_bytes: u64,
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Sha1(sha1::Sha1),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(bytes),
+            Hasher::Sha1(h) => h.update(bytes),
+            Hasher::Blake3(h) => {
+                h.update(bytes);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Sha256(h) => format!("{:x}", h.finalize()),
+            Hasher::Sha1(h) => format!("{:x}", h.finalize()),
+            Hasher::Blake3(h) => h.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+// ============================================================================
+// HASHING
+// ============================================================================
+
+/// Hashes `path` in fixed-size chunks. Blocking; run it on a blocking thread.
+pub fn hash_file(
+    app_handle: &AppHandle,
+    jobs: &JobRegistry,
+    job_id: &str,
+    path: &Path,
+    algorithm: HashAlgorithm,
+) -> Result<FileHash, CommandError> {
+    let job = jobs.register(job_id);
+    hash_with_job(app_handle, &job, path, algorithm)
+}
+
+fn hash_with_job(
+    app_handle: &AppHandle,
+    job: &JobGuard<'_>,
+    path: &Path,
+    algorithm: HashAlgorithm,
+) -> Result<FileHash, CommandError> {
+    let display = path.to_string_lossy().to_string();
+    let mut file = File::open(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => CommandError::NotFound { path: display.clone() },
+        ErrorKind::PermissionDenied => CommandError::PermissionDenied { path: display.clone() },
+        _ => format!("Failed to open '{}': {}", display, e).into(),
+    })?;
+
+    let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let report_progress = total_bytes >= HASH_PROGRESS_THRESHOLD;
+    let mut last_report = Instant::now();
+
+    let mut hasher = Hasher::new(algorithm);
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+    let mut bytes_hashed = 0u64;
+
+    loop {
+        if job.is_cancelled() {
+            log::info!("⏹️  Hash job {} cancelled", job.id());
+            return Err(CommandError::Cancelled);
+        }
+
+        let read = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read '{}': {}", display, e))?;
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..read]);
+        bytes_hashed += read as u64;
+
+        if report_progress && last_report.elapsed() >= HASH_PROGRESS_INTERVAL {
+            last_report = Instant::now();
+            let _ = app_handle.emit("file-hash-progress", HashProgress {
+                job_id: job.id().to_string(),
+                bytes_hashed,
+                total_bytes,
+            });
+        }
+    }
+
+    Ok(FileHash {
+        algorithm,
+        hex_digest: hasher.finalize_hex(),
+        bytes_hashed,
+    })
+}
@@ -0,0 +1,994 @@
+// file_io.rs - Native file operations backing the fs commands
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::disk_space;
+use crate::error::CommandError;
+use crate::AppState;
+
+/// Writers that receive no chunk for this long are assumed abandoned (webview
+/// reloaded mid-export) and their partial files removed.
+const WRITER_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const WRITER_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+/// Copies smaller than this finish too quickly for progress to be useful.
+const COPY_PROGRESS_THRESHOLD: u64 = 16 * 1024 * 1024;
+const COPY_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Most `read_range` returns in one call, whatever `length` asks for.
+const MAX_READ_RANGE: u64 = 16 * 1024 * 1024;
+
+const DEFAULT_STREAM_CHUNK_SIZE: usize = 1024 * 1024;
+const MIN_STREAM_CHUNK_SIZE: usize = 16 * 1024;
+const MAX_STREAM_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+/// A stream whose chunks go unacknowledged this long is assumed abandoned
+/// (webview reloaded mid-read) and closed.
+const STREAM_ACK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Directories with fewer path components than this (e.g. `/home/ali`,
+/// `C:\Users\ali`) are never deleted, whatever else the checks say, unless
+/// `AppSettings::min_delete_depth` says otherwise.
+pub const DEFAULT_MIN_DELETE_DEPTH: usize = 3;
+
+// ============================================================================
+// RANGED READS
+// ============================================================================
+
+/// Reads up to `length` bytes starting at `offset` (to EOF when `length` is
+/// `None`), and never more than `MAX_READ_RANGE`. Returns fewer bytes when
+/// the file ends early or the window is capped, so callers read on from
+/// `offset` plus what they got; an offset exactly at EOF yields an empty
+/// buffer, anything beyond is an error.
+pub fn read_range(path: &Path, offset: u64, length: Option<u64>) -> Result<Vec<u8>, CommandError> {
+    let mut file = File::open(path)
+        .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+
+    let file_size = file.metadata()
+        .map_err(|e| format!("Failed to read metadata for '{}': {}", path.display(), e))?
+        .len();
+
+    if offset > file_size {
+        return Err(CommandError::OffsetPastEof { offset, file_size });
+    }
+
+    let available = file_size - offset;
+    let to_read = length.map_or(available, |len| len.min(available)).min(MAX_READ_RANGE);
+
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to seek to {} in '{}': {}", offset, path.display(), e))?;
+
+    let mut buffer = Vec::with_capacity(to_read as usize);
+    file.take(to_read).read_to_end(&mut buffer)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+
+    Ok(buffer)
+}
+
+// ============================================================================
+// CHUNKED WRITERS
+// ============================================================================
+
+struct OpenWriter {
+    file: File,
+    path: PathBuf,
+    bytes_written: u64,
+    last_activity: Instant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WriterSummary {
+    pub path: String,
+    pub bytes_written: u64,
+}
+
+#[derive(Default)]
+pub struct FileWriters {
+    writers: Mutex<HashMap<String, Arc<Mutex<OpenWriter>>>>,
+}
+
+impl FileWriters {
+    /// Creates (or truncates) `path`, creating parent directories as needed.
+    /// When the caller knows the final size, space is checked up front.
+    pub fn open(&self, path: PathBuf, expected_size: Option<u64>) -> Result<String, CommandError> {
+        if let Some(expected_size) = expected_size {
+            disk_space::ensure_space(&path, expected_size)?;
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+        }
+
+        let file = File::create(&path)
+            .map_err(|e| format!("Failed to create '{}': {}", path.display(), e))?;
+
+        let writer_id = uuid::Uuid::new_v4().to_string();
+        log::info!("✍️  Opened chunked writer {} for {}", writer_id, path.display());
+
+        self.writers.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(writer_id.clone(), Arc::new(Mutex::new(OpenWriter {
+            file,
+            path,
+            bytes_written: 0,
+            last_activity: Instant::now(),
+        })));
+
+        Ok(writer_id)
+    }
+
+    fn get(&self, writer_id: &str) -> Result<Arc<Mutex<OpenWriter>>, String> {
+        self.writers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(writer_id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown or expired file writer: {}", writer_id))
+    }
+
+    fn take(&self, writer_id: &str) -> Result<Arc<Mutex<OpenWriter>>, String> {
+        self.writers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(writer_id)
+            .ok_or_else(|| format!("Unknown or expired file writer: {}", writer_id))
+    }
+
+    pub fn write_chunk(&self, writer_id: &str, bytes: &[u8]) -> Result<u64, CommandError> {
+        let writer = self.get(writer_id)?;
+        let mut writer = writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        disk_space::ensure_space(&writer.path, bytes.len() as u64)?;
+
+        writer.file.write_all(bytes)
+            .map_err(|e| format!("Failed to write chunk to '{}': {}", writer.path.display(), e))?;
+        writer.bytes_written += bytes.len() as u64;
+        writer.last_activity = Instant::now();
+
+        Ok(writer.bytes_written)
+    }
+
+    /// Flushes and fsyncs the file so a successful close means the data is on disk.
+    pub fn close(&self, writer_id: &str) -> Result<WriterSummary, String> {
+        let writer = self.take(writer_id)?;
+        let mut writer = writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        writer.file.flush()
+            .and_then(|_| writer.file.sync_all())
+            .map_err(|e| format!("Failed to sync '{}': {}", writer.path.display(), e))?;
+
+        log::info!("✅ Closed chunked writer for {} ({} bytes)", writer.path.display(), writer.bytes_written);
+
+        Ok(WriterSummary {
+            path: writer.path.to_string_lossy().to_string(),
+            bytes_written: writer.bytes_written,
+        })
+    }
+
+    /// Drops the writer and deletes whatever was written so far.
+    pub fn abort(&self, writer_id: &str) -> Result<(), String> {
+        let writer = self.take(writer_id)?;
+        let path = writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).path.clone();
+        drop(writer);
+
+        discard_partial_file(&path);
+        Ok(())
+    }
+
+    fn reap_idle(&self, timeout: Duration) {
+        let expired: Vec<(String, Arc<Mutex<OpenWriter>>)> = {
+            let mut writers = self.writers.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let ids: Vec<String> = writers.iter()
+                .filter(|(_, w)| w.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).last_activity.elapsed() > timeout)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| writers.remove(&id).map(|w| (id, w)))
+                .collect()
+        };
+
+        for (writer_id, writer) in expired {
+            let path = writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).path.clone();
+            drop(writer);
+            log::warn!("⏱️  Abandoned file writer {} timed out", writer_id);
+            discard_partial_file(&path);
+        }
+    }
+}
+
+fn discard_partial_file(path: &Path) {
+    match fs::remove_file(path) {
+        Ok(_) => log::info!("🗑️  Removed partial file: {}", path.display()),
+        Err(e) => log::warn!("Failed to remove partial file {}: {}", path.display(), e),
+    }
+}
+
+/// Periodically closes and deletes writers the frontend forgot about.
+pub fn spawn_writer_reaper(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(WRITER_REAP_INTERVAL);
+        loop {
+            ticker.tick().await;
+            app_handle.state::<AppState>().file_writers.reap_idle(WRITER_IDLE_TIMEOUT);
+        }
+    });
+}
+
+// ============================================================================
+// STREAMED READS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+struct FileChunk {
+    stream_id: String,
+    index: u64,
+    offset: u64,
+    /// Base64, since a JSON array of byte values is several times larger.
+    data: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StreamOutcome {
+    Complete,
+    Cancelled,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FileStreamEnd {
+    stream_id: String,
+    status: StreamOutcome,
+    /// Bytes sent in `file-chunk` events before the stream ended.
+    total_bytes: u64,
+    error: Option<String>,
+}
+
+struct OpenStream {
+    acks: mpsc::Sender<()>,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+pub struct FileStreams {
+    streams: Mutex<HashMap<String, OpenStream>>,
+}
+
+impl FileStreams {
+    /// Opens `path` and starts sending it as `file-chunk` events, then one
+    /// `file-chunk-end`. Nothing more is read until the last chunk sent is
+    /// acknowledged with `ack`, so at most one chunk is in memory or in
+    /// flight at a time.
+    pub fn start(&self, app_handle: &AppHandle, path: PathBuf, chunk_size: Option<usize>) -> Result<String, CommandError> {
+        let file = File::open(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => CommandError::NotFound { path: path.to_string_lossy().to_string() },
+            std::io::ErrorKind::PermissionDenied => CommandError::PermissionDenied { path: path.to_string_lossy().to_string() },
+            _ => format!("Failed to open '{}': {}", path.display(), e).into(),
+        })?;
+        let chunk_size = chunk_size
+            .unwrap_or(DEFAULT_STREAM_CHUNK_SIZE)
+            .clamp(MIN_STREAM_CHUNK_SIZE, MAX_STREAM_CHUNK_SIZE);
+
+        let stream_id = uuid::Uuid::new_v4().to_string();
+        let (acks, ack_rx) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.streams.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(stream_id.clone(), OpenStream { acks, cancelled: cancelled.clone() });
+        log::info!("📤 Streaming {} as {} ({} byte chunks)", path.display(), stream_id, chunk_size);
+
+        let app_handle = app_handle.clone();
+        let id = stream_id.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let (status, total_bytes, error) = match send_chunks(&app_handle, &id, file, chunk_size, &ack_rx, &cancelled) {
+                Ok(total_bytes) => (StreamOutcome::Complete, total_bytes, None),
+                Err((total_bytes, None)) => (StreamOutcome::Cancelled, total_bytes, None),
+                Err((total_bytes, Some(e))) => {
+                    log::warn!("File stream {} for {} failed: {}", id, path.display(), e);
+                    (StreamOutcome::Error, total_bytes, Some(e))
+                }
+            };
+
+            app_handle.state::<AppState>().file_streams.streams.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(&id);
+            let _ = app_handle.emit("file-chunk-end", FileStreamEnd { stream_id: id, status, total_bytes, error });
+        });
+
+        Ok(stream_id)
+    }
+
+    /// Lets the stream send its next chunk.
+    pub fn ack(&self, stream_id: &str) -> Result<(), String> {
+        let streams = self.streams.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let stream = streams.get(stream_id)
+            .ok_or_else(|| format!("Unknown or finished file stream: {}", stream_id))?;
+        let _ = stream.acks.send(());
+        Ok(())
+    }
+
+    /// Stops the stream; it still ends with a `cancelled` `file-chunk-end`.
+    pub fn cancel(&self, stream_id: &str) -> bool {
+        match self.streams.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(stream_id) {
+            Some(stream) => {
+                stream.cancelled.store(true, Ordering::Relaxed);
+                // Wake the reader if it is waiting for an ack
+                let _ = stream.acks.send(());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Returns the bytes sent, or the bytes sent so far and an error (`None`
+/// when cancelled).
+fn send_chunks(
+    app_handle: &AppHandle,
+    stream_id: &str,
+    mut file: File,
+    chunk_size: usize,
+    acks: &mpsc::Receiver<()>,
+    cancelled: &AtomicBool,
+) -> Result<u64, (u64, Option<String>)> {
+    let mut buffer = vec![0u8; chunk_size];
+    let mut offset = 0u64;
+
+    for index in 0.. {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err((offset, None));
+        }
+
+        // Fill the whole chunk; `read` may return less before EOF
+        let mut filled = 0;
+        while filled < chunk_size {
+            match file.read(&mut buffer[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err((offset, Some(format!("Failed to read file: {}", e)))),
+            }
+        }
+        if filled == 0 {
+            break;
+        }
+
+        let chunk = FileChunk {
+            stream_id: stream_id.to_string(),
+            index,
+            offset,
+            data: base64::engine::general_purpose::STANDARD.encode(&buffer[..filled]),
+        };
+        app_handle.emit("file-chunk", chunk)
+            .map_err(|e| (offset, Some(format!("Failed to emit chunk: {}", e))))?;
+        offset += filled as u64;
+
+        if acks.recv_timeout(STREAM_ACK_TIMEOUT).is_err() {
+            return Err((offset, Some("Timed out waiting for the chunk to be acknowledged".to_string())));
+        }
+        if cancelled.load(Ordering::Relaxed) {
+            return Err((offset, None));
+        }
+        if filled < chunk_size {
+            break;
+        }
+    }
+
+    Ok(offset)
+}
+
+// ============================================================================
+// COPY / MOVE
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferSummary {
+    pub source: String,
+    pub destination: String,
+    pub bytes: u64,
+    /// "copy" or "rename", so the UI can tell a cheap move from a full copy.
+    pub method: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CopyProgress {
+    source: String,
+    destination: String,
+    bytes_copied: u64,
+    total_bytes: u64,
+}
+
+fn check_transfer(src: &Path, dst: &Path, overwrite: bool) -> Result<u64, CommandError> {
+    let metadata = fs::metadata(src).map_err(|_| CommandError::NotFound {
+        path: src.to_string_lossy().to_string(),
+    })?;
+
+    if !metadata.is_file() {
+        return Err(format!("Not a file: {}", src.display()).into());
+    }
+
+    let same_file = src == dst || matches!(
+        (dunce::canonicalize(src), dunce::canonicalize(dst)),
+        (Ok(a), Ok(b)) if a == b
+    );
+    if same_file {
+        return Err(CommandError::SamePath { path: src.to_string_lossy().to_string() });
+    }
+
+    if dst.exists() && !overwrite {
+        return Err(CommandError::AlreadyExists { path: dst.to_string_lossy().to_string() });
+    }
+
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+    }
+
+    Ok(metadata.len())
+}
+
+/// Streams `src` into `dst` chunk by chunk, emitting `file-copy-progress`
+/// events for large files. A failed copy never leaves a partial destination,
+/// and an existing destination is only replaced once the copy is complete.
+pub fn copy_file(app_handle: &AppHandle, src: &Path, dst: &Path, overwrite: bool) -> Result<TransferSummary, CommandError> {
+    copy_file_with(src, dst, overwrite, &mut progress_emitter(app_handle))
+}
+
+fn copy_file_with(src: &Path, dst: &Path, overwrite: bool, on_progress: &mut dyn FnMut(CopyProgress)) -> Result<TransferSummary, CommandError> {
+    let total_bytes = check_transfer(src, dst, overwrite)?;
+    disk_space::ensure_space(dst, total_bytes)?;
+
+    let bytes = stream_copy(src, dst, total_bytes, on_progress)?;
+
+    log::info!("📋 Copied {} → {} ({} bytes)", src.display(), dst.display(), bytes);
+
+    Ok(TransferSummary {
+        source: src.to_string_lossy().to_string(),
+        destination: dst.to_string_lossy().to_string(),
+        bytes,
+        method: "copy",
+    })
+}
+
+/// Renames when possible and falls back to copy + delete across volumes.
+pub fn move_file(app_handle: &AppHandle, src: &Path, dst: &Path, overwrite: bool) -> Result<TransferSummary, CommandError> {
+    move_file_with(src, dst, overwrite, &mut progress_emitter(app_handle))
+}
+
+fn move_file_with(src: &Path, dst: &Path, overwrite: bool, on_progress: &mut dyn FnMut(CopyProgress)) -> Result<TransferSummary, CommandError> {
+    let total_bytes = check_transfer(src, dst, overwrite)?;
+
+    let method = match fs::rename(src, dst) {
+        Ok(_) => "rename",
+        Err(e) if is_cross_device(&e) => {
+            disk_space::ensure_space(dst, total_bytes)?;
+            stream_copy(src, dst, total_bytes, on_progress)?;
+            fs::remove_file(src)
+                .map_err(|e| format!("Copied to '{}' but failed to remove source: {}", dst.display(), e))?;
+            "copy"
+        }
+        Err(e) => {
+            return Err(format!("Failed to move '{}' to '{}': {}", src.display(), dst.display(), e).into());
+        }
+    };
+
+    log::info!("📦 Moved {} → {} via {}", src.display(), dst.display(), method);
+
+    Ok(TransferSummary {
+        source: src.to_string_lossy().to_string(),
+        destination: dst.to_string_lossy().to_string(),
+        bytes: total_bytes,
+        method,
+    })
+}
+
+fn progress_emitter(app_handle: &AppHandle) -> impl FnMut(CopyProgress) + '_ {
+    move |progress| {
+        let _ = app_handle.emit("file-copy-progress", progress);
+    }
+}
+
+fn stream_copy(src: &Path, dst: &Path, total_bytes: u64, on_progress: &mut dyn FnMut(CopyProgress)) -> Result<u64, String> {
+    let source = File::open(src)
+        .map_err(|e| format!("Failed to open '{}': {}", src.display(), e))?;
+    let mut reader = BufReader::with_capacity(COPY_CHUNK_SIZE, source);
+
+    let report_progress = total_bytes >= COPY_PROGRESS_THRESHOLD;
+    let mut last_report = Instant::now();
+    let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+
+    let copied = write_replacing(dst, |writer| {
+        let mut copied = 0u64;
+        loop {
+            let read = reader.read(&mut buffer)
+                .map_err(|e| format!("Failed to read '{}': {}", src.display(), e))?;
+            if read == 0 {
+                return Ok(copied);
+            }
+
+            writer.write_all(&buffer[..read])
+                .map_err(|e| format!("Failed to write '{}': {}", dst.display(), e))?;
+            copied += read as u64;
+
+            if report_progress && (last_report.elapsed() >= COPY_PROGRESS_INTERVAL || copied == total_bytes) {
+                last_report = Instant::now();
+                on_progress(CopyProgress {
+                    source: src.to_string_lossy().to_string(),
+                    destination: dst.to_string_lossy().to_string(),
+                    bytes_copied: copied,
+                    total_bytes,
+                });
+            }
+        }
+    })?;
+
+    if let Ok(metadata) = fs::metadata(src) {
+        let _ = fs::set_permissions(dst, metadata.permissions());
+    }
+
+    Ok(copied)
+}
+
+/// Has `write` fill a temporary sibling of `dst` and renames it over `dst`
+/// only once it succeeded, so whatever was at `dst` survives a failure.
+fn write_replacing(dst: &Path, write: impl FnOnce(&mut File) -> Result<u64, String>) -> Result<u64, String> {
+    let name = dst.file_name()
+        .ok_or_else(|| format!("Invalid destination: {}", dst.display()))?;
+    let mut part_name = name.to_os_string();
+    part_name.push(format!(".{}.part", uuid::Uuid::new_v4().simple()));
+    let part = dst.with_file_name(part_name);
+
+    let result = File::create(&part)
+        .map_err(|e| format!("Failed to create '{}': {}", part.display(), e))
+        .and_then(|mut writer| {
+            let written = write(&mut writer)?;
+            writer.sync_all()
+                .map_err(|e| format!("Failed to sync '{}': {}", dst.display(), e))?;
+            Ok(written)
+        })
+        .and_then(|written| {
+            fs::rename(&part, dst)
+                .map_err(|e| format!("Failed to replace '{}': {}", dst.display(), e))?;
+            Ok(written)
+        });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&part);
+    }
+    result
+}
+
+fn is_cross_device(error: &std::io::Error) -> bool {
+    #[cfg(unix)]
+    const CROSS_DEVICE: i32 = 18; // EXDEV
+    #[cfg(windows)]
+    const CROSS_DEVICE: i32 = 17; // ERROR_NOT_SAME_DEVICE
+
+    error.raw_os_error() == Some(CROSS_DEVICE)
+}
+
+// ============================================================================
+// DIRECTORY DELETION
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteFailure {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteSummary {
+    /// Files, links and directories removed, including `path` itself.
+    pub removed: usize,
+    pub failed: Vec<DeleteFailure>,
+}
+
+/// Deletes `path`, refusing anything that is or contains a directory in
+/// `protected` (compared after canonicalization) or with fewer than
+/// `min_depth` components (never fewer than one, so a filesystem root is
+/// always refused). A symlink to a directory is removed as a link; its
+/// target is left alone.
+pub fn delete_directory(path: &Path, recursive: bool, protected: &[PathBuf], min_depth: usize) -> Result<DeleteSummary, CommandError> {
+    let display = path.to_string_lossy().to_string();
+
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|_| CommandError::NotFound { path: display.clone() })?;
+    let is_link = metadata.file_type().is_symlink();
+    let is_dir = metadata.is_dir() || (is_link && path.is_dir());
+    if !is_dir {
+        return Err(format!("Not a directory: {}", display).into());
+    }
+
+    // A link is judged by where it lives, not by what it points at
+    let canonical = match (is_link, path.parent(), path.file_name()) {
+        (true, Some(parent), Some(name)) => dunce::canonicalize(parent).map(|parent| parent.join(name)),
+        _ => dunce::canonicalize(path),
+    }
+    .unwrap_or_else(|_| path.to_path_buf());
+    let depth = canonical.components()
+        .filter(|c| matches!(c, std::path::Component::Normal(_)))
+        .count();
+
+    let is_protected = depth < min_depth.max(1) || protected.iter().any(|p| {
+        p.starts_with(&canonical) || dunce::canonicalize(p).map(|p| p.starts_with(&canonical)).unwrap_or(false)
+    });
+    if is_protected {
+        log::warn!("🚫 Refused to delete protected directory: {}", display);
+        return Err(CommandError::ProtectedPath { path: display });
+    }
+
+    if is_link {
+        let mut summary = DeleteSummary { removed: 0, failed: Vec::new() };
+        remove_tree(path, &mut summary);
+        log::info!("🗑️  Removed directory link {}", display);
+        return Ok(summary);
+    }
+
+    if !recursive {
+        let entry_count = fs::read_dir(path)
+            .map_err(|e| format!("Failed to read directory '{}': {}", display, e))?
+            .count();
+        if entry_count > 0 {
+            return Err(CommandError::NotEmpty { path: display, entry_count });
+        }
+    }
+
+    let mut summary = DeleteSummary { removed: 0, failed: Vec::new() };
+    remove_tree(path, &mut summary);
+
+    log::info!("🗑️  Deleted directory {} ({} entries removed, {} failed)", display, summary.removed, summary.failed.len());
+
+    Ok(summary)
+}
+
+/// Post-order removal that keeps going past failures and records them.
+/// Symlinks are removed as links, never followed.
+fn remove_tree(path: &Path, summary: &mut DeleteSummary) {
+    let is_real_dir = fs::symlink_metadata(path)
+        .map(|m| m.is_dir())
+        .unwrap_or(false);
+
+    let result = if is_real_dir {
+        match fs::read_dir(path) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    remove_tree(&entry.path(), summary);
+                }
+            }
+            Err(e) => {
+                summary.failed.push(DeleteFailure {
+                    path: path.to_string_lossy().to_string(),
+                    error: e.to_string(),
+                });
+                return;
+            }
+        }
+        fs::remove_dir(path)
+    } else {
+        // Windows directory symlinks/junctions need remove_dir
+        fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+    };
+
+    match result {
+        Ok(_) => summary.removed += 1,
+        Err(e) => summary.failed.push(DeleteFailure {
+            path: path.to_string_lossy().to_string(),
+            error: e.to_string(),
+        }),
+    }
+}
+
+// ============================================================================
+// METADATA
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FileMetadata {
+    pub path: String,
+    pub exists: bool,
+    pub is_dir: bool,
+    pub is_file: bool,
+    pub is_symlink: bool,
+    pub size: u64,
+    /// Epoch millis; `None` where the platform/filesystem doesn't record it.
+    pub created_ms: Option<i64>,
+    pub modified_ms: Option<i64>,
+    pub readonly: bool,
+    pub mime_type: Option<String>,
+}
+
+/// Never fails for missing paths: they come back with `exists: false` so one
+/// call covers both the existence check and the stat.
+pub fn file_metadata(path: &Path, follow_symlinks: bool) -> FileMetadata {
+    let display = path.to_string_lossy().to_string();
+
+    let link_metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return FileMetadata { path: display, ..Default::default() },
+    };
+
+    let is_symlink = link_metadata.file_type().is_symlink();
+    let metadata = if follow_symlinks && is_symlink {
+        // A dangling link still exists as a link; report the link itself
+        fs::metadata(path).unwrap_or(link_metadata)
+    } else {
+        link_metadata
+    };
+
+    let mime_type = if metadata.is_file() {
+        mime_guess::from_path(path).first().map(|m| m.essence_str().to_string())
+    } else {
+        None
+    };
+
+    FileMetadata {
+        path: display,
+        exists: true,
+        is_dir: metadata.is_dir(),
+        is_file: metadata.is_file(),
+        is_symlink,
+        size: metadata.len(),
+        created_ms: metadata.created().ok().and_then(epoch_millis),
+        modified_ms: metadata.modified().ok().and_then(epoch_millis),
+        readonly: metadata.permissions().readonly(),
+        mime_type,
+    }
+}
+
+fn epoch_millis(time: SystemTime) -> Option<i64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ten-byte file under the temp dir, removed when dropped.
+    struct Fixture(PathBuf);
+
+    impl Fixture {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("segitelep-read-range-{}", uuid::Uuid::new_v4()));
+            fs::write(&path, b"0123456789").unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    /// An empty directory under the temp dir, removed with its contents
+    /// when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("segitelep-file-io-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn read_range_from_offset_zero() {
+        let file = Fixture::new();
+        assert_eq!(read_range(&file.0, 0, None).unwrap(), b"0123456789");
+        assert_eq!(read_range(&file.0, 0, Some(4)).unwrap(), b"0123");
+        assert_eq!(read_range(&file.0, 0, Some(0)).unwrap(), b"");
+    }
+
+    #[test]
+    fn read_range_clamps_length_to_the_end() {
+        let file = Fixture::new();
+        assert_eq!(read_range(&file.0, 6, Some(100)).unwrap(), b"6789");
+    }
+
+    #[test]
+    fn read_range_at_eof_is_empty() {
+        let file = Fixture::new();
+        assert_eq!(read_range(&file.0, 10, None).unwrap(), b"");
+        assert_eq!(read_range(&file.0, 10, Some(5)).unwrap(), b"");
+    }
+
+    #[test]
+    fn read_range_is_capped_to_the_maximum_window() {
+        let file = Fixture::new();
+        File::options().write(true).open(&file.0).unwrap().set_len(MAX_READ_RANGE + 10).unwrap();
+
+        assert_eq!(read_range(&file.0, 0, None).unwrap().len() as u64, MAX_READ_RANGE);
+        assert_eq!(read_range(&file.0, 0, Some(MAX_READ_RANGE + 1)).unwrap().len() as u64, MAX_READ_RANGE);
+        assert_eq!(read_range(&file.0, MAX_READ_RANGE, None).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn read_range_past_eof_is_an_error() {
+        let file = Fixture::new();
+        match read_range(&file.0, 11, None) {
+            Err(CommandError::OffsetPastEof { offset, file_size }) => {
+                assert_eq!(offset, 11);
+                assert_eq!(file_size, 10);
+            }
+            other => panic!("expected OffsetPastEof, got {:?}", other),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn delete_directory_removes_a_link_but_not_its_target() {
+        let dir = TempDir::new();
+        let target = dir.0.join("target");
+        let link = dir.0.join("link");
+        fs::create_dir_all(target.join("nested")).unwrap();
+        fs::write(target.join("nested").join("script.txt"), b"keep me").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let summary = delete_directory(&link, true, &[], 1).unwrap();
+
+        assert_eq!(summary.removed, 1);
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert_eq!(fs::read(target.join("nested").join("script.txt")).unwrap(), b"keep me");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn delete_directory_refuses_a_link_to_a_file() {
+        let dir = TempDir::new();
+        let file = dir.0.join("script.txt");
+        let link = dir.0.join("link");
+        fs::write(&file, b"keep me").unwrap();
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+
+        assert!(delete_directory(&link, true, &[], 1).is_err());
+        assert!(file.exists());
+    }
+
+    #[test]
+    fn delete_directory_refuses_ancestors_of_protected_directories() {
+        let dir = TempDir::new();
+        let protected = dir.0.join("share").join("app-data");
+        fs::create_dir_all(&protected).unwrap();
+
+        for path in [dir.0.join("share"), protected.clone()] {
+            match delete_directory(&path, true, std::slice::from_ref(&protected), 1) {
+                Err(CommandError::ProtectedPath { .. }) => {}
+                other => panic!("expected ProtectedPath for {}, got {:?}", path.display(), other),
+            }
+        }
+        assert!(protected.exists());
+    }
+
+    #[test]
+    fn delete_directory_allows_siblings_of_protected_directories() {
+        let dir = TempDir::new();
+        let protected = dir.0.join("app-data");
+        let sibling = dir.0.join("app-data-old");
+        fs::create_dir_all(&protected).unwrap();
+        fs::create_dir_all(sibling.join("nested")).unwrap();
+
+        let summary = delete_directory(&sibling, true, std::slice::from_ref(&protected), 1).unwrap();
+
+        assert_eq!(summary.removed, 2);
+        assert!(!sibling.exists());
+        assert!(protected.exists());
+    }
+
+    fn no_progress(_: CopyProgress) {}
+
+    #[test]
+    fn copy_file_copies_contents() {
+        let dir = TempDir::new();
+        let src = dir.0.join("script.txt");
+        let dst = dir.0.join("copies").join("script.txt");
+        fs::write(&src, b"Welcome everyone").unwrap();
+
+        let summary = copy_file_with(&src, &dst, false, &mut no_progress).unwrap();
+
+        assert_eq!(summary.bytes, 16);
+        assert_eq!(summary.method, "copy");
+        assert_eq!(fs::read(&dst).unwrap(), b"Welcome everyone");
+        assert_eq!(fs::read(&src).unwrap(), b"Welcome everyone");
+    }
+
+    #[test]
+    fn copy_file_respects_overwrite() {
+        let dir = TempDir::new();
+        let src = dir.0.join("new.txt");
+        let dst = dir.0.join("old.txt");
+        fs::write(&src, b"new").unwrap();
+        fs::write(&dst, b"old contents").unwrap();
+
+        match copy_file_with(&src, &dst, false, &mut no_progress) {
+            Err(CommandError::AlreadyExists { .. }) => {}
+            other => panic!("expected AlreadyExists, got {:?}", other),
+        }
+        assert_eq!(fs::read(&dst).unwrap(), b"old contents");
+
+        copy_file_with(&src, &dst, true, &mut no_progress).unwrap();
+        assert_eq!(fs::read(&dst).unwrap(), b"new");
+    }
+
+    #[test]
+    fn copy_file_refuses_the_same_path() {
+        let dir = TempDir::new();
+        let src = dir.0.join("script.txt");
+        fs::write(&src, b"keep me").unwrap();
+
+        assert!(matches!(copy_file_with(&src, &src, true, &mut no_progress), Err(CommandError::SamePath { .. })));
+        assert_eq!(fs::read(&src).unwrap(), b"keep me");
+    }
+
+    #[test]
+    fn failed_overwrite_keeps_the_original_destination() {
+        let dir = TempDir::new();
+        let dst = dir.0.join("script.txt");
+        fs::write(&dst, b"original").unwrap();
+
+        let result = write_replacing(&dst, |writer| {
+            writer.write_all(b"half of the new").unwrap();
+            Err("read failed".to_string())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read(&dst).unwrap(), b"original");
+        assert_eq!(fs::read_dir(&dir.0).unwrap().count(), 1, "temporary file left behind");
+    }
+
+    #[test]
+    fn move_file_renames_and_respects_overwrite() {
+        let dir = TempDir::new();
+        let src = dir.0.join("script.txt");
+        let dst = dir.0.join("archive").join("script.txt");
+        fs::write(&src, b"moved").unwrap();
+
+        let summary = move_file_with(&src, &dst, false, &mut no_progress).unwrap();
+        assert_eq!(summary.method, "rename");
+        assert!(!src.exists());
+        assert_eq!(fs::read(&dst).unwrap(), b"moved");
+
+        fs::write(&src, b"newer").unwrap();
+        assert!(matches!(move_file_with(&src, &dst, false, &mut no_progress), Err(CommandError::AlreadyExists { .. })));
+        move_file_with(&src, &dst, true, &mut no_progress).unwrap();
+        assert_eq!(fs::read(&dst).unwrap(), b"newer");
+    }
+
+    #[test]
+    fn large_copy_reports_progress_up_to_the_total() {
+        let dir = TempDir::new();
+        let src = dir.0.join("video.bin");
+        let dst = dir.0.join("video-copy.bin");
+        let total = COPY_PROGRESS_THRESHOLD + COPY_CHUNK_SIZE as u64 / 2;
+        fs::write(&src, vec![7u8; total as usize]).unwrap();
+
+        let mut reports = Vec::new();
+        copy_file_with(&src, &dst, false, &mut |progress: CopyProgress| reports.push(progress)).unwrap();
+
+        let last = reports.last().expect("no progress reported");
+        assert_eq!(last.bytes_copied, total);
+        assert_eq!(last.total_bytes, total);
+        assert!(reports.windows(2).all(|pair| pair[0].bytes_copied < pair[1].bytes_copied));
+        assert_eq!(fs::metadata(&dst).unwrap().len(), total);
+    }
+
+    #[test]
+    fn small_copy_reports_no_progress() {
+        let dir = TempDir::new();
+        let src = dir.0.join("script.txt");
+        fs::write(&src, b"short").unwrap();
+
+        let mut reports = 0;
+        copy_file_with(&src, &dir.0.join("copy.txt"), false, &mut |_| reports += 1).unwrap();
+        assert_eq!(reports, 0);
+    }
+}
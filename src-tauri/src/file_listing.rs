@@ -0,0 +1,148 @@
+// file_listing.rs - Directory listing with recursion, glob filtering and metadata
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+/// Hard ceiling on returned entries; callers can ask for less but never more.
+pub const MAX_LIST_ENTRIES: usize = 50_000;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ListFilesOptions {
+    pub recursive: bool,
+    /// Depth below the root to descend to when recursive (1 = direct children only).
+    pub max_depth: Option<usize>,
+    /// Glob such as `*.json`. Matched against the file name, or against the
+    /// path relative to the root when the pattern contains a `/`.
+    pub pattern: Option<String>,
+    pub include_hidden: bool,
+    pub max_entries: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileListing {
+    pub entries: Vec<FileEntry>,
+    /// True when the entry cap was hit and the listing is incomplete.
+    pub truncated: bool,
+    /// Subdirectories that could not be read and were skipped.
+    pub warnings: Vec<String>,
+}
+
+// ============================================================================
+// LISTING
+// ============================================================================
+
+pub fn list_files(root: &Path, options: &ListFilesOptions) -> Result<FileListing, String> {
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", root.display()));
+    }
+
+    let pattern = options.pattern.as_deref()
+        .map(|p| glob::Pattern::new(p).map_err(|e| format!("Invalid glob pattern '{}': {}", p, e)))
+        .transpose()?;
+    let match_relative = options.pattern.as_deref().is_some_and(|p| p.contains('/'));
+
+    let max_entries = options.max_entries.unwrap_or(MAX_LIST_ENTRIES).min(MAX_LIST_ENTRIES);
+    let max_depth = if options.recursive { options.max_depth.unwrap_or(usize::MAX) } else { 1 };
+
+    let mut listing = FileListing {
+        entries: Vec::new(),
+        truncated: false,
+        warnings: Vec::new(),
+    };
+
+    let mut pending: Vec<(PathBuf, usize)> = vec![(root.to_path_buf(), 1)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        let read_dir = match fs::read_dir(&dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if dir == root => {
+                return Err(format!("Failed to read directory '{}': {}", dir.display(), e));
+            }
+            Err(e) => {
+                log::warn!("Skipping unreadable directory {}: {}", dir.display(), e);
+                listing.warnings.push(format!("{}: {}", dir.display(), e));
+                continue;
+            }
+        };
+
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+
+            if !options.include_hidden && is_hidden(&name, &entry) {
+                continue;
+            }
+
+            // file_type() does not follow symlinks, so linked directories are
+            // listed but never descended into (no cycles).
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+
+            if is_dir && depth < max_depth {
+                pending.push((path.clone(), depth + 1));
+            }
+
+            let matches = match &pattern {
+                None => true,
+                Some(pattern) if match_relative => path.strip_prefix(root)
+                    .map(|rel| pattern.matches(&rel.to_string_lossy().replace('\\', "/")))
+                    .unwrap_or(false),
+                Some(pattern) => pattern.matches(&name),
+            };
+
+            if !matches {
+                continue;
+            }
+
+            if listing.entries.len() >= max_entries {
+                listing.truncated = true;
+                return Ok(listing);
+            }
+
+            let metadata = entry.metadata().ok();
+            listing.entries.push(FileEntry {
+                path: path.to_string_lossy().to_string(),
+                name,
+                is_dir,
+                size: metadata.as_ref().filter(|m| m.is_file()).map(|m| m.len()).unwrap_or(0),
+                modified_ms: metadata.as_ref()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as i64),
+            });
+        }
+    }
+
+    Ok(listing)
+}
+
+#[cfg(windows)]
+fn is_hidden(name: &str, entry: &fs::DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+    name.starts_with('.') || entry.metadata()
+        .map(|m| m.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+fn is_hidden(name: &str, _entry: &fs::DirEntry) -> bool {
+    name.starts_with('.')
+}
@@ -0,0 +1,110 @@
+// file_open.rs - Project files opened from the OS (double-click, "Open with")
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{project_schema, AppState};
+
+/// Extension registered for project files in `tauri.conf.json`.
+pub const PROJECT_EXTENSION: &str = "segitelep";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+struct OpenProjectRequest {
+    path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OpenProjectFailed {
+    path: String,
+    error: String,
+}
+
+// ============================================================================
+// ARGUMENTS
+// ============================================================================
+
+/// Project files among process arguments. `args` includes the executable
+/// name; relative paths are resolved against `cwd`, which for a second
+/// instance is that process's working directory, not ours.
+pub fn project_paths_from_args(args: &[String], cwd: &Path) -> Vec<PathBuf> {
+    args.iter()
+        .skip(1)
+        // Flags, and deep links (handled by `deep_link`)
+        .filter(|arg| !arg.starts_with('-') && !arg.contains("://"))
+        .map(|arg| cwd.join(arg))
+        .filter(|path| is_project_file(path))
+        .collect()
+}
+
+fn is_project_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(PROJECT_EXTENSION))
+}
+
+// ============================================================================
+// OPEN REQUESTS
+// ============================================================================
+
+/// Validates each file off the main thread and asks the frontend to open it
+/// via `open-project-request` (or reports `open-project-failed`), buffered
+/// until the frontend is ready.
+pub fn request_open(app_handle: &AppHandle, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = &app_handle.state::<AppState>().frontend_events;
+
+        for path in paths {
+            let display = path.to_string_lossy().to_string();
+            match validate_project(&path) {
+                Ok(path) => {
+                    log::info!("📂 Open requested by the OS: {}", path.display());
+                    events.emit(&app_handle, "open-project-request", OpenProjectRequest {
+                        path: path.to_string_lossy().to_string(),
+                    });
+                }
+                Err(error) => {
+                    log::warn!("⚠️  Not opening {}: {}", display, error);
+                    events.emit(&app_handle, "open-project-failed", OpenProjectFailed { path: display, error });
+                }
+            }
+        }
+    });
+}
+
+/// Canonical path of `path` if it exists and parses as a supported project.
+pub fn validate_project(path: &Path) -> Result<PathBuf, String> {
+    let path = dunce::canonicalize(path)
+        .map_err(|_| "File does not exist".to_string())?;
+    if !path.is_file() {
+        return Err("Not a file".to_string());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let document: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Not a valid project file: {}", e))?;
+    if !document.is_object() {
+        return Err("Not a valid project file".to_string());
+    }
+    project_schema::migrate_to_current(document).map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// A second launch means the user is looking for the app.
+pub fn focus_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
@@ -0,0 +1,75 @@
+// frontend_events.rs - Events held back until the main window's frontend is listening
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Window that receives app-level requests (open a project, ...).
+const MAIN_WINDOW_LABEL: &str = "main";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Default)]
+struct Inner {
+    ready: bool,
+    pending: Vec<(&'static str, serde_json::Value)>,
+}
+
+/// Events raised during launch (e.g. a file passed on the command line) would
+/// be lost if emitted before the webview has registered its listeners, so
+/// they queue here until the frontend calls `frontend_ready`.
+#[derive(Default)]
+pub struct FrontendEvents {
+    inner: Mutex<Inner>,
+}
+
+// ============================================================================
+// DELIVERY
+// ============================================================================
+
+impl FrontendEvents {
+    /// Sends to the main window now if its frontend is ready, otherwise queues.
+    pub fn emit<T: Serialize>(&self, app_handle: &AppHandle, event: &'static str, payload: T) {
+        let payload = match serde_json::to_value(payload) {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::error!("Failed to serialize '{}' event: {}", event, e);
+                return;
+            }
+        };
+
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if inner.ready {
+            drop(inner);
+            send(app_handle, event, payload);
+        } else {
+            log::info!("📨 Holding '{}' until the frontend is ready", event);
+            inner.pending.push((event, payload));
+        }
+    }
+
+    /// Marks the frontend ready and delivers everything queued, in order.
+    /// Returns how many events were delivered.
+    pub fn mark_ready(&self, app_handle: &AppHandle) -> usize {
+        let pending = {
+            let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            inner.ready = true;
+            std::mem::take(&mut inner.pending)
+        };
+
+        let delivered = pending.len();
+        for (event, payload) in pending {
+            send(app_handle, event, payload);
+        }
+        delivered
+    }
+}
+
+fn send(app_handle: &AppHandle, event: &str, payload: serde_json::Value) {
+    if let Err(e) = app_handle.emit_to(MAIN_WINDOW_LABEL, event, payload) {
+        log::error!("Failed to emit '{}': {}", event, e);
+    }
+}
@@ -0,0 +1,109 @@
+// fs_sandbox.rs - Restricts native filesystem commands to user-facing locations
+
+use std::path::{Component, Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+
+// ============================================================================
+// ALLOWED ROOTS
+// ============================================================================
+
+/// Locations the webview may touch through the native fs commands: our own
+/// data, the user's home tree (documents, synced folders, exports), the temp
+/// dir and removable volumes.
+pub fn allowed_roots(app_handle: &AppHandle) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if let Ok(app_dir) = app_handle.path().app_data_dir() {
+        roots.push(app_dir);
+    }
+    if let Some(home) = dirs::home_dir() {
+        roots.push(home);
+    }
+    roots.push(std::env::temp_dir());
+
+    #[cfg(target_os = "macos")]
+    roots.push(PathBuf::from("/Volumes"));
+
+    #[cfg(target_os = "linux")]
+    roots.extend(["/media", "/mnt", "/run/media"].iter().map(PathBuf::from));
+
+    roots.into_iter()
+        .map(|root| dunce::canonicalize(&root).unwrap_or(root))
+        .collect()
+}
+
+// ============================================================================
+// PATH RESOLUTION
+// ============================================================================
+
+/// Resolves `raw` (following symlinks and `..`) and verifies it lies inside one
+/// of the allowed roots. The path itself does not need to exist yet.
+pub fn resolve_path(app_handle: &AppHandle, raw: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(raw);
+
+    if !path.is_absolute() {
+        return Err(format!("Path must be absolute: {}", raw));
+    }
+
+    let resolved = canonicalize_lenient(&path);
+
+    if allowed_roots(app_handle).iter().any(|root| resolved.starts_with(root)) {
+        Ok(resolved)
+    } else {
+        log::warn!("🚫 Blocked filesystem access outside sandbox: {}", raw);
+        Err(format!("Access denied: '{}' is outside the allowed directories", raw))
+    }
+}
+
+/// Like `resolve_path`, but leaves the final component unresolved so commands
+/// that must not follow symlinks (metadata, deletion of links) see the link itself.
+pub fn resolve_path_nofollow(app_handle: &AppHandle, raw: &str) -> Result<PathBuf, String> {
+    let path = normalize_lexically(Path::new(raw));
+
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => {
+            let parent = resolve_path(app_handle, &parent.to_string_lossy())?;
+            Ok(parent.join(name))
+        }
+        _ => resolve_path(app_handle, raw),
+    }
+}
+
+/// Canonicalizes the deepest existing ancestor and re-appends the remainder, so
+/// write targets that don't exist yet are still checked after symlink resolution.
+fn canonicalize_lenient(path: &Path) -> PathBuf {
+    let normalized = normalize_lexically(path);
+    let mut existing = normalized.as_path();
+    let mut remainder = Vec::new();
+
+    loop {
+        if let Ok(canonical) = dunce::canonicalize(existing) {
+            return remainder.iter().rev().fold(canonical, |acc, part| acc.join(part));
+        }
+
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                remainder.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return normalized,
+        }
+    }
+}
+
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+
+    normalized
+}
@@ -0,0 +1,58 @@
+// jobs.rs - Cancellation flags for long-running blocking commands
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// In-flight jobs keyed by a caller-chosen id, so the frontend can cancel a
+/// command whose promise it is still awaiting.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl JobRegistry {
+    /// Registers `job_id`; the job is unregistered when the guard drops.
+    pub fn register(&self, job_id: &str) -> JobGuard<'_> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(job_id.to_string(), flag.clone());
+
+        JobGuard {
+            registry: self,
+            job_id: job_id.to_string(),
+            flag,
+        }
+    }
+
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(job_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub struct JobGuard<'a> {
+    registry: &'a JobRegistry,
+    job_id: String,
+    flag: Arc<AtomicBool>,
+}
+
+impl JobGuard<'_> {
+    pub fn id(&self) -> &str {
+        &self.job_id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for JobGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(&self.job_id);
+    }
+}
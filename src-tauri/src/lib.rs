@@ -1,11 +1,58 @@
 // lib.rs - Complete Fixed Version
 
+mod error;
 mod remote_server;
+mod project_watcher;
+mod recent_projects;
+mod autosave;
+mod project_schema;
+mod project_templates;
+mod fs_sandbox;
+mod file_listing;
+mod file_io;
+mod dir_watcher;
+mod text_decoding;
+mod jobs;
+mod file_hash;
+mod disk_space;
+mod zip_archive;
+mod shell;
+mod standard_dirs;
+mod window_control;
+mod app_settings;
+mod monitors;
+mod output_window;
+mod window_overlay;
+mod window_state;
+mod sleep_guard;
+mod cursor;
+mod window_bounds;
+mod screenshot;
+mod frontend_events;
+mod file_open;
+mod deep_link;
+mod tray;
+mod script_text;
+mod assets;
+mod drop_import;
+mod update_check;
+mod script_import;
+mod reading_time;
+mod run_sheet;
+mod logging;
+mod diagnostics;
+mod log_buffer;
+mod crash_reports;
+mod remote_sessions;
+mod raw_ipc;
+mod remote_qr;
+mod remote_clients;
+mod rate_limit;
+mod command_recordings;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashSet;
 use std::fs;
-use sha2::{Sha256, Digest};
 use tauri::{Manager, Emitter};  // ✅ Added Emitter trait
 
 #[derive(Clone, serde::Serialize)]
@@ -15,9 +62,97 @@ struct RemoteServerState {
     connection_url: String,
 }
 
+/// WebSocket and HTTP server tasks of the running remote server. Behind an
+/// async lock, so a command that panicked mid-start can't poison it for the
+/// next start or stop.
+#[derive(Default)]
+struct RemoteTasks(tokio::sync::Mutex<Vec<tauri::async_runtime::JoinHandle<()>>>);
+
+impl RemoteTasks {
+    /// Stores the tasks of a fresh start, aborting any left from before.
+    async fn replace(&self, tasks: Vec<tauri::async_runtime::JoinHandle<()>>) {
+        let previous = std::mem::replace(&mut *self.0.lock().await, tasks);
+        for task in previous {
+            task.abort();
+        }
+    }
+
+    /// Aborting the accept loops drops the listeners and every open remote
+    /// connection with them.
+    async fn abort_all(&self) {
+        for task in self.0.lock().await.drain(..) {
+            task.abort();
+        }
+    }
+}
+
 struct AppState {
-    remote_server: std::sync::Mutex<RemoteServerState>,
-    remote_state: std::sync::Arc<std::sync::Mutex<Option<remote_server::SharedState>>>,
+    /// Async locks: commands await while holding them, and a panicking
+    /// command can't poison them for every later one. Lock `remote_server`
+    /// before `remote_state` when both are needed.
+    remote_server: tokio::sync::Mutex<RemoteServerState>,
+    remote_state: std::sync::Arc<tokio::sync::Mutex<Option<remote_server::SharedState>>>,
+    remote_tasks: RemoteTasks,
+    remote_sessions: remote_sessions::SessionHistory,
+    command_recorder: command_recordings::CommandRecorder,
+    project_watcher: project_watcher::ProjectWatcher,
+    recent_projects: recent_projects::RecentProjects,
+    autosave: autosave::AutosaveQueue,
+    file_writers: file_io::FileWriters,
+    file_streams: file_io::FileStreams,
+    asset_data_urls: assets::DataUrlCache,
+    dir_watchers: dir_watcher::DirWatchers,
+    hash_jobs: jobs::JobRegistry,
+    archive_jobs: jobs::JobRegistry,
+    cleanup_jobs: jobs::JobRegistry,
+    window_control: window_control::WindowControl,
+    settings: app_settings::SettingsStore,
+    window_state: window_state::WindowStateStore,
+    sleep_guard: sleep_guard::SleepGuard,
+    cursor: cursor::CursorControl,
+    frontend_events: frontend_events::FrontendEvents,
+    tray: tray::TrayMenu,
+    update_checker: update_check::UpdateChecker,
+    log_buffer: std::sync::Arc<log_buffer::LogBuffer>,
+}
+
+// ============================================================================
+// SHARED HELPERS
+// ============================================================================
+
+/// Writes `data` to a sibling `.tmp` file and renames it over `path`, so a crash
+/// mid-write never leaves a truncated file behind.
+pub(crate) fn write_json_atomic<T: serde::Serialize>(path: &Path, data: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+    }
+    
+    let mut temp_name = path.as_os_str().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+    
+    let json_data = serde_json::to_string_pretty(data)
+        .map_err(|e| format!("JSON serialization failed: {}", e))?;
+    
+    fs::write(&temp_path, json_data)
+        .map_err(|e| format!("Failed to write temp file '{}': {}", temp_path.display(), e))?;
+    
+    fs::rename(&temp_path, path)
+        .map_err(|e| format!("Atomic rename failed from '{}' to '{}': {}", temp_path.display(), path.display(), e))?;
+    
+    Ok(())
+}
+
+pub(crate) fn app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// The running server's shared state, or the error commands report while
+/// it's stopped.
+async fn running_remote_state<T: Clone>(remote_state: &tokio::sync::Mutex<Option<T>>) -> Result<T, String> {
+    remote_state.lock().await.clone().ok_or_else(|| "Remote server is not running".to_string())
 }
 
 // ============================================================================
@@ -29,7 +164,9 @@ async fn start_remote_server(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
 ) -> Result<RemoteServerState, String> {
-    let mut server_state = state.remote_server.lock().unwrap();
+    // Held for the whole start so a second caller (tray and UI at once)
+    // waits and then sees the running server
+    let mut server_state = state.remote_server.lock().await;
 
     if server_state.is_running {
         log::info!("⚡ Remote server already running at {}", server_state.connection_url);
@@ -76,14 +213,20 @@ async fn start_remote_server(
 
     let port = 8765;
     let connection_url = format!("http://{}:{}", local_ip, port);
+    let app_dir = app_data_dir(&app_handle)?;
+    let mut settings = state.settings.load(&app_dir).remote;
+    if settings.ensure_controller_token() {
+        let token = settings.controller_token.clone();
+        state.settings.update(&app_dir, |s| s.remote.controller_token = token)?;
+    }
 
     // Create WebSocket server (port + 1)
-    let ws_server = remote_server::RemoteServer::new(app_handle.clone(), port + 1);
+    let ws_server = remote_server::RemoteServer::new(app_handle.clone(), port + 1, &settings);
     let shared_state = ws_server.get_state();
     
     // Start WebSocket server
     let ws_handle = app_handle.clone();
-    tauri::async_runtime::spawn(async move {
+    let ws_task = tauri::async_runtime::spawn(async move {
         if let Err(e) = ws_server.start().await {
             log::error!("WebSocket server error: {}", e);
             let _ = ws_handle.emit("remote-server-error", format!("WebSocket error: {}", e));
@@ -93,7 +236,7 @@ async fn start_remote_server(
     // Start HTTP server with shared state
     let http_server = remote_server::MobileInterfaceServer::with_state(port, shared_state.clone());
     let http_handle = app_handle.clone();
-    tauri::async_runtime::spawn(async move {
+    let http_task = tauri::async_runtime::spawn(async move {
         if let Err(e) = http_server.start().await {
             log::error!("HTTP server error: {}", e);
             let _ = http_handle.emit("remote-server-error", format!("HTTP error: {}", e));
@@ -107,352 +250,1541 @@ async fn start_remote_server(
     server_state.connection_url = connection_url.clone();
 
     // Store the remote state for other commands to use
-    let mut app_remote_state = state.remote_state.lock().unwrap();
-    *app_remote_state = Some(shared_state);
+    *state.remote_state.lock().await = Some(shared_state);
+    state.remote_tasks.replace(vec![ws_task, http_task]).await;
+
+    let started = server_state.clone();
+    drop(server_state);
+    tray::refresh(&app_handle);
 
-    Ok(server_state.clone())
+    Ok(started)
 }
 
 #[tauri::command]
-async fn generate_remote_qr(connection_url: String) -> Result<String, String> {
-    use qrcode::{QrCode, render::svg};  // ✅ Single import, properly scoped
-    
-    let qr_code = QrCode::new(connection_url.as_bytes())
-        .map_err(|e| format!("Failed to generate QR code: {}", e))?;
+async fn stop_remote_server(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<RemoteServerState, String> {
+    let shared_state = {
+        let mut server_state = state.remote_server.lock().await;
+        if !server_state.is_running {
+            return Ok(server_state.clone());
+        }
+
+        state.remote_tasks.abort_all().await;
 
-    let svg = qr_code.render::<svg::Color>()  // ✅ Explicit type parameter
-        .min_dimensions(200, 200)
-        .build();
+        server_state.is_running = false;
+        server_state.port = 0;
+        server_state.connection_url.clear();
+        state.remote_state.lock().await.take()
+    };
+
+    log::info!("🛑 Remote control servers stopped");
+    if let Some(shared_state) = shared_state {
+        let result = match app_data_dir(&app_handle) {
+            Ok(app_dir) => state.remote_sessions.finish(&app_dir, &shared_state).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to save remote session stats: {}", e);
+        }
+    }
+    tray::refresh(&app_handle);
 
-    Ok(svg)
+    let stopped = state.remote_server.lock().await.clone();
+    let _ = app_handle.emit("remote-server-stopped", &stopped);
+    Ok(stopped)
 }
 
+/// SVG QR code for the connection URL. `options` adds colors, rounded or
+/// dot modules and a center logo; a logo too big for the error correction
+/// level is an error rather than an unscannable code.
 #[tauri::command]
-async fn toggle_window_fullscreen(window: tauri::Window) -> Result<(), String> {
-    let is_fullscreen = window.is_fullscreen().map_err(|e| e.to_string())?;
-    window.set_fullscreen(!is_fullscreen).map_err(|e| e.to_string())?;
-    Ok(())
+async fn generate_remote_qr(
+    app_handle: tauri::AppHandle,
+    connection_url: String,
+    options: Option<remote_qr::QrOptions>,
+) -> Result<String, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    remote_qr::generate_svg(&app_dir, &connection_url, &options.unwrap_or_default())
 }
 
+/// Open WebSocket remotes with their sessions and keepalive round-trip
+/// times, oldest connection first. Empty when the server isn't running.
 #[tauri::command]
-async fn set_window_fullscreen(window: tauri::Window, fullscreen: bool) -> Result<(), String> {
-    window.set_fullscreen(fullscreen).map_err(|e| e.to_string())?;
-    Ok(())
+async fn get_connected_clients(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<remote_clients::ConnectedClient>, String> {
+    let shared_state = state.remote_state.lock().await.clone();
+    let Some(shared_state) = shared_state else { return Ok(Vec::new()) };
+
+    let clients = shared_state.read().await.clients.list();
+    Ok(clients)
 }
 
 #[tauri::command]
-async fn sync_remote_status(
-    status: remote_server::RemoteStatus,
+async fn get_remote_settings(
+    app_handle: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let remote_state = {
-        let lock = state.remote_state.lock().unwrap();
-        lock.clone()
-    };
-    
-    if let Some(rs) = remote_state {
-        remote_server::update_status(rs, status).await;
-        Ok(())
-    } else {
-        Err("Remote server is not running".to_string())
-    }
+) -> Result<remote_server::RemoteSettings, String> {
+    Ok(state.settings.load(&app_data_dir(&app_handle)?).remote)
 }
 
-// ============================================================================
-// PROJECT STORAGE COMMANDS
-// ============================================================================
+/// Saves the settings and applies them to the running server, if any.
+#[tauri::command]
+async fn set_remote_settings(
+    app_handle: tauri::AppHandle,
+    settings: remote_server::RemoteSettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<remote_server::RemoteSettings, String> {
+    let saved = state.settings.update(&app_data_dir(&app_handle)?, |s| {
+        // Omitting the token keeps the existing one rather than rotating it
+        let controller_token = settings.controller_token.clone().or_else(|| s.remote.controller_token.take());
+        s.remote = remote_server::RemoteSettings { controller_token, ..settings };
+        s.remote.ensure_controller_token();
+    })?;
+
+    let shared_state = state.remote_state.lock().await.clone();
+    if let Some(shared_state) = shared_state {
+        let mut server = shared_state.write().await;
+        server.clients.apply_settings(&saved.remote);
+        server.controller_token = saved.remote.controller_token.clone().unwrap_or_default();
+    }
+    Ok(saved.remote)
+}
 
+/// Counters for the running remote server session, or `None` when it's stopped.
 #[tauri::command]
-async fn atomic_save_json(path: String, data: serde_json::Value) -> Result<String, String> {
-    let path_buf = PathBuf::from(&path);
-    
-    if let Some(parent) = path_buf.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+async fn get_current_session_stats(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<remote_sessions::SessionStats>, String> {
+    let shared_state = state.remote_state.lock().await.clone();
+    match shared_state {
+        Some(shared_state) => Ok(Some(shared_state.read().await.stats.snapshot())),
+        None => Ok(None),
     }
-    
-    let temp_path = format!("{}.tmp", path);
-    
-    let json_data = serde_json::to_string_pretty(&data)
-        .map_err(|e| format!("JSON serialization failed: {}", e))?;
-    
-    fs::write(&temp_path, json_data)
-        .map_err(|e| format!("Failed to write temp file '{}': {}", temp_path, e))?;
-    
-    fs::rename(&temp_path, &path)
-        .map_err(|e| format!("Atomic rename failed from '{}' to '{}': {}", temp_path, path, e))?;
-    
-    log::info!("✅ Successfully saved project to: {}", path);
-    
-    Ok(path)
 }
 
-// ============================================================================
-// ASSET STORAGE COMMANDS
-// ============================================================================
+/// Finished remote server sessions, most recent first.
+#[tauri::command]
+async fn get_remote_session_history(
+    app_handle: tauri::AppHandle,
+    limit: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<remote_sessions::SessionStats>, String> {
+    Ok(state.remote_sessions.list(&app_data_dir(&app_handle)?, limit))
+}
 
+/// While on, remote commands are validated and echoed back to the sender
+/// without reaching the prompter. Off again after every server start.
 #[tauri::command]
-async fn store_asset(
-    app_handle: tauri::AppHandle, 
-    bytes: Vec<u8>, 
-    extension: String
-) -> Result<String, String> {
-    let clean_extension = extension.trim_start_matches('.').to_lowercase();
-    if clean_extension.is_empty() {
-        return Err("Invalid file extension".to_string());
-    }
-    
-    if bytes.is_empty() {
-        return Err("Cannot store empty asset".to_string());
-    }
-    
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let hash = format!("{:x}", hasher.finalize());
-    
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    let assets_dir = app_dir.join("global_assets");
-    
-    if !assets_dir.exists() {
-        fs::create_dir_all(&assets_dir)
-            .map_err(|e| format!("Failed to create global_assets directory: {}", e))?;
-        log::info!("📁 Created global_assets directory at: {:?}", assets_dir);
-    }
-    
-    let filename = format!("{}.{}", hash, clean_extension);
-    let file_path = assets_dir.join(&filename);
-    
-    if !file_path.exists() {
-        fs::write(&file_path, &bytes)
-            .map_err(|e| format!("Failed to write asset to '{}': {}", file_path.display(), e))?;
-        log::info!("💾 Stored new asset: {} ({} bytes)", filename, bytes.len());
-    } else {
-        log::info!("♻️  Asset already exists (deduplicated): {}", filename);
+async fn set_remote_test_mode(
+    enabled: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<remote_server::RemoteStatus, String> {
+    let shared_state = running_remote_state(&state.remote_state).await?;
+    Ok(remote_server::set_test_mode(shared_state, enabled).await)
+}
+
+/// Takes the control lock away from whichever remote holds it. False if
+/// none did or the server isn't running.
+#[tauri::command]
+async fn force_release_remote_control(
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    let shared_state = state.remote_state.lock().await.clone();
+    match shared_state {
+        Some(shared_state) => Ok(remote_server::force_release_control(&shared_state).await),
+        None => Ok(false),
     }
-    
-    Ok(format!("global_assets/{}", filename))
 }
 
+/// Starts capturing accepted remote commands with their timing.
 #[tauri::command]
-async fn get_absolute_path(
-    app_handle: tauri::AppHandle, 
-    relative_path: String
+async fn start_command_recording(
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.command_recorder.start_recording()
+}
+
+/// Saves the recording in progress; returns its id.
+#[tauri::command]
+async fn stop_command_recording(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
 ) -> Result<String, String> {
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let normalized_path = relative_path.replace('\\', "/");
-    let full_path = app_dir.join(normalized_path);
-    
-    if !full_path.exists() {
-        return Err(format!("File not found: {}", full_path.display()));
-    }
-    
-    Ok(full_path.to_string_lossy().to_string())
+    state.command_recorder.stop_recording(&app_data_dir(&app_handle)?)
 }
 
 #[tauri::command]
-async fn cleanup_global_assets(
-    app_handle: tauri::AppHandle, 
-    active_assets: Vec<String>
-) -> Result<usize, String> {
-    let active_asset_set: HashSet<String> = active_assets.into_iter().collect();
-    
-    let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    let assets_dir = app_dir.join("global_assets");
-    
-    if !assets_dir.exists() {
-        log::info!("No global_assets directory found, nothing to clean up");
-        return Ok(0);
-    }
-    
-    let mut deleted_count = 0;
-    let mut failed_deletions = Vec::new();
-    
-    match fs::read_dir(&assets_dir) {
-        Ok(entries) => {
-            for entry in entries.flatten() {
-                if let Ok(file_name) = entry.file_name().into_string() {
-                    let path_key = format!("global_assets/{}", file_name);
-                    
-                    if !active_asset_set.contains(&path_key) {
-                        match fs::remove_file(entry.path()) {
-                            Ok(_) => {
-                                deleted_count += 1;
-                                log::info!("🗑️  Deleted orphaned asset: {}", file_name);
-                            }
-                            Err(e) => {
-                                let error_msg = format!("Failed to delete {}: {}", file_name, e);
-                                log::warn!("{}", error_msg);
-                                failed_deletions.push(error_msg);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            return Err(format!("Failed to read assets directory: {}", e));
-        }
-    }
-    
-    if !failed_deletions.is_empty() {
-        log::warn!("⚠️  Some assets could not be deleted: {:?}", failed_deletions);
-    }
-    
-    log::info!("✅ Cleanup complete: {} orphaned assets deleted", deleted_count);
-    
-    Ok(deleted_count)
+async fn list_command_recordings(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<command_recordings::RecordingSummary>, String> {
+    command_recordings::list(&app_data_dir(&app_handle)?)
 }
 
-// ============================================================================
-// FILE SYSTEM COMMANDS
-// ============================================================================
+/// Replays a recording in the background, `speed_factor` (default 1) times
+/// as fast; `command-replay-finished` fires when it's done.
+#[tauri::command]
+async fn replay_command_recording(
+    app_handle: tauri::AppHandle,
+    id: String,
+    speed_factor: Option<f64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    state.command_recorder.start_replay(&app_handle, &app_dir, &id, speed_factor.unwrap_or(1.0))
+}
 
 #[tauri::command]
-async fn get_download_dir() -> Result<String, String> {
-    let download_dir = dirs::download_dir()
-        .ok_or_else(|| "Could not determine download directory".to_string())?;
-    
-    Ok(download_dir.to_string_lossy().to_string())
+async fn stop_replay(
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.command_recorder.stop_replay())
 }
 
 #[tauri::command]
-async fn open_file(file_path: String) -> Result<(), String> {
-    let path = PathBuf::from(&file_path);
-    if !path.exists() {
-        return Err(format!("File not found: {}", file_path));
-    }
-    
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        Command::new("cmd")
-            .args(["/C", "start", "", &file_path])
-            .spawn()
-            .map_err(|e| format!("Failed to open file on Windows: {}", e))?;
-    }
-    
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        Command::new("open")
-            .arg(&file_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file on macOS: {}", e))?;
-    }
-    
-    #[cfg(target_os = "linux")]
-    {
-        use std::process::Command;
-        Command::new("xdg-open")
-            .arg(&file_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file on Linux: {}", e))?;
-    }
-    
-    log::info!("📂 Opened file: {}", file_path);
-    
-    Ok(())
+async fn toggle_window_fullscreen(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let is_fullscreen = window.is_fullscreen().map_err(|e| e.to_string())?;
+    state.window_control.set_fullscreen(&window, !is_fullscreen)
 }
 
 #[tauri::command]
-async fn show_in_folder(file_path: String) -> Result<(), String> {
-    let path = PathBuf::from(&file_path);
-    
-    if !path.exists() {
-        return Err(format!("File not found: {}", file_path));
-    }
-    
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        Command::new("explorer")
-            .args(["/select,", &file_path])
-            .spawn()
-            .map_err(|e| format!("Failed to show file in folder on Windows: {}", e))?;
-    }
-    
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        Command::new("open")
-            .args(["-R", &file_path])
-            .spawn()
-            .map_err(|e| format!("Failed to show file in folder on macOS: {}", e))?;
+async fn set_window_fullscreen(
+    window: tauri::Window,
+    fullscreen: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.window_control.set_fullscreen(&window, fullscreen)
+}
+
+#[tauri::command]
+async fn set_window_always_on_top(
+    window: tauri::Window,
+    on_top: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    state.window_control.set_always_on_top(&window, on_top)
+}
+
+#[tauri::command]
+async fn toggle_window_always_on_top(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    state.window_control.toggle_always_on_top(&window)
+}
+
+#[tauri::command]
+async fn get_window_flags(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+) -> Result<window_control::WindowFlags, String> {
+    state.window_control.flags(&window)
+}
+
+#[tauri::command]
+async fn set_window_decorations(
+    window: tauri::Window,
+    decorated: bool,
+) -> Result<window_bounds::WindowBounds, String> {
+    window_bounds::set_decorations(&window, decorated).await
+}
+
+#[tauri::command]
+async fn set_window_bounds(
+    window: tauri::Window,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<window_bounds::WindowBounds, String> {
+    window_bounds::set_bounds(&window, x, y, width, height).await
+}
+
+#[tauri::command]
+async fn apply_capture_preset(
+    window: tauri::Window,
+    width: u32,
+    height: u32,
+) -> Result<window_bounds::WindowBounds, String> {
+    window_bounds::apply_capture_preset(&window, width, height).await
+}
+
+#[tauri::command]
+async fn enter_kiosk_mode(
+    window: tauri::Window,
+    hide_cursor: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.window_control.enter_kiosk(&window, hide_cursor.unwrap_or(false))
+}
+
+#[tauri::command]
+async fn exit_kiosk_mode(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    pin: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), error::CommandError> {
+    let settings = state.settings.load(&app_data_dir(&app_handle)?);
+
+    if !app_settings::kiosk_pin_matches(&settings, pin.as_deref()) {
+        log::warn!("🔒 Rejected kiosk exit with wrong PIN for '{}'", window.label());
+        return Err(error::CommandError::InvalidPin);
     }
-    
-    #[cfg(target_os = "linux")]
-    {
-        use std::process::Command;
-        let parent_dir = path.parent()
-            .ok_or_else(|| "File has no parent directory".to_string())?;
-        
-        Command::new("xdg-open")
-            .arg(parent_dir)
-            .spawn()
-            .map_err(|e| format!("Failed to show file in folder on Linux: {}", e))?;
+
+    Ok(state.window_control.exit_kiosk(&window)?)
+}
+
+/// `None` or an empty string removes the PIN. Refused while any window is in
+/// kiosk mode, so the lock can't be loosened from inside it.
+#[tauri::command]
+async fn set_kiosk_pin(
+    app_handle: tauri::AppHandle,
+    pin: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if state.window_control.any_kiosk() {
+        return Err("Cannot change the kiosk PIN while kiosk mode is active".to_string());
     }
-    
-    log::info!("📁 Showed file in folder: {}", file_path);
-    
+
+    let pin_hash = pin.filter(|p| !p.is_empty()).map(|p| app_settings::hash_pin(&p));
+    state.settings.update(&app_data_dir(&app_handle)?, |s| s.kiosk_pin_hash = pin_hash)?;
     Ok(())
 }
 
-// ============================================================================
-// APPLICATION ENTRY POINT
-// ============================================================================
+#[tauri::command]
+async fn capture_window_screenshot(
+    app_handle: tauri::AppHandle,
+    webview_window: tauri::WebviewWindow,
+    destination: Option<String>,
+) -> Result<String, error::CommandError> {
+    let path = screenshot::save_png(&app_handle, &webview_window, destination.as_deref()).await?;
+    Ok(path.to_string_lossy().to_string())
+}
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_clipboard_manager::init())
-        .plugin(tauri_plugin_fs::init())
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_sql::Builder::default().build())
-        .manage(AppState {
-            remote_server: std::sync::Mutex::new(RemoteServerState {
-                is_running: false,
-                port: 0,
-                connection_url: String::new(),
-            }),
-            remote_state: std::sync::Arc::new(std::sync::Mutex::new(None)),
-        })
-        .invoke_handler(tauri::generate_handler![
-            start_remote_server,
-            generate_remote_qr,
-            atomic_save_json,
-            store_asset,
-            get_absolute_path,
-            cleanup_global_assets,
-            get_download_dir,
-            open_file,
-            show_in_folder,
-            toggle_window_fullscreen,
-            set_window_fullscreen,
-            sync_remote_status,
-        ])
-        .setup(|app| {
-            app.handle().plugin(
-                tauri_plugin_log::Builder::default()
-                    .level(if cfg!(debug_assertions) {
-                        log::LevelFilter::Debug
-                    } else {
-                        log::LevelFilter::Info
-                    })
-                    .build(),
-            )?;
+/// Same capture, returned as base64 PNG for the remote preview instead of a file.
+#[tauri::command]
+async fn capture_window_screenshot_base64(
+    webview_window: tauri::WebviewWindow,
+) -> Result<String, error::CommandError> {
+    use base64::Engine;
 
-            log::info!("═══════════════════════════════════════════");
-            log::info!("🚀 SegiTelep Pro Starting Up");
-            log::info!("═══════════════════════════════════════════");
-            log::info!("📱 Environment: {}", if cfg!(debug_assertions) { 
-                "Development" 
-            } else { 
-                "Production" 
+    let png = screenshot::capture_png(&webview_window).await?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(png))
+}
+
+#[tauri::command]
+async fn set_window_opacity(window: tauri::Window, alpha: f64) -> Result<f64, error::CommandError> {
+    window_overlay::set_opacity(&window, alpha).await
+}
+
+#[tauri::command]
+async fn set_window_click_through(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    enabled: bool,
+) -> Result<bool, error::CommandError> {
+    window_overlay::set_click_through(&app_handle, &window, enabled)
+}
+
+#[tauri::command]
+async fn get_overlay_state(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+) -> Result<window_overlay::OverlayState, String> {
+    Ok(window_overlay::overlay_state(&app_handle, &window))
+}
+
+#[tauri::command]
+async fn create_output_window(
+    app_handle: tauri::AppHandle,
+    options: Option<output_window::OutputWindowOptions>,
+) -> Result<output_window::OutputWindowState, String> {
+    output_window::create(&app_handle, &options.unwrap_or_default())
+}
+
+#[tauri::command]
+async fn close_output_window(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    output_window::close(&app_handle)
+}
+
+#[tauri::command]
+async fn get_output_window_state(app_handle: tauri::AppHandle) -> Result<output_window::OutputWindowState, String> {
+    output_window::state(&app_handle)
+}
+
+#[tauri::command]
+async fn set_sleep_prevention(
+    enabled: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<sleep_guard::SleepPreventionState, error::CommandError> {
+    state.sleep_guard.set(enabled).await
+}
+
+#[tauri::command]
+async fn get_sleep_prevention(
+    state: tauri::State<'_, AppState>,
+) -> Result<sleep_guard::SleepPreventionState, String> {
+    Ok(state.sleep_guard.state().await)
+}
+
+#[tauri::command]
+async fn set_cursor_visible(
+    window: tauri::Window,
+    visible: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<cursor::CursorState, String> {
+    state.cursor.set_visible(&window, visible)
+}
+
+#[tauri::command]
+async fn set_cursor_autohide(
+    window: tauri::Window,
+    seconds: Option<f64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<cursor::CursorState, String> {
+    state.cursor.set_autohide(&window, seconds.unwrap_or(0.0))
+}
+
+#[tauri::command]
+async fn get_cursor_state(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+) -> Result<cursor::CursorState, String> {
+    Ok(state.cursor.state(window.label()))
+}
+
+#[tauri::command]
+async fn reset_window_state(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.window_state.reset(&app_handle, &window)
+}
+
+#[tauri::command]
+async fn list_monitors(app_handle: tauri::AppHandle) -> Result<Vec<monitors::MonitorInfo>, String> {
+    monitors::list_monitors(&app_handle)
+}
+
+#[tauri::command]
+async fn set_fullscreen_on_monitor(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    monitor_id: String,
+) -> Result<String, String> {
+    monitors::fullscreen_on_monitor(&app_handle, &window, &monitor_id)
+}
+
+#[tauri::command]
+async fn sync_remote_status(
+    status: remote_server::RemoteStatus,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let remote_state = running_remote_state(&state.remote_state).await?;
+    remote_server::update_status(remote_state, status).await;
+    Ok(())
+}
+
+/// Scroll position (0 to 1) for remotes subscribed to scroll frames. Called
+/// at display rate, so it's a quiet no-op while the server is stopped.
+#[tauri::command]
+async fn sync_scroll_position(
+    position: f64,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if !position.is_finite() {
+        return Err(format!("Invalid scroll position: {}", position));
+    }
+
+    let remote_state = state.remote_state.lock().await.clone();
+    if let Some(rs) = remote_state {
+        remote_server::set_scroll_position(&rs, position.clamp(0.0, 1.0)).await;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// PROJECT STORAGE COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn atomic_save_json(
+    path: String,
+    mut data: serde_json::Value,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let path_buf = PathBuf::from(&path);
+    // An older autosave for this path must not land after the explicit save
+    let _flush_guard = state.autosave.supersede(&path_buf).await;
+    
+    project_schema::stamp_current_version(&mut data);
+    write_json_atomic(&path_buf, &data)?;
+    
+    state.project_watcher.record_own_write(&path_buf);
+    
+    log::info!("✅ Successfully saved project to: {}", path);
+    
+    Ok(path)
+}
+
+#[tauri::command]
+async fn load_json(path: String) -> Result<serde_json::Value, error::CommandError> {
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    
+    let document: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse '{}': {}", path, e))?;
+    
+    project_schema::migrate_to_current(document)
+}
+
+/// Called once the frontend has registered its event listeners; delivers
+/// anything queued during launch (e.g. a project passed on the command line).
+#[tauri::command]
+async fn frontend_ready(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<usize, String> {
+    Ok(state.frontend_events.mark_ready(&app_handle))
+}
+
+#[tauri::command]
+async fn get_supported_schema_version() -> u32 {
+    project_schema::CURRENT_SCHEMA_VERSION
+}
+
+#[tauri::command]
+async fn list_project_templates(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<project_templates::TemplateInfo>, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    Ok(project_templates::list_templates(&app_dir))
+}
+
+#[tauri::command]
+async fn create_project_from_template(
+    app_handle: tauri::AppHandle,
+    template_id: String,
+    destination_path: String,
+    project_name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let destination = PathBuf::from(&destination_path);
+    
+    if destination.exists() {
+        return Err(format!("A file already exists at: {}", destination_path));
+    }
+    
+    let project = project_templates::create_from_template(&app_dir, &template_id, &destination, &project_name)?;
+    state.project_watcher.record_own_write(&destination);
+    
+    Ok(project)
+}
+
+#[tauri::command]
+async fn watch_project_file(
+    app_handle: tauri::AppHandle,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.project_watcher.watch(app_handle, &PathBuf::from(path))
+}
+
+#[tauri::command]
+async fn unwatch_project_file(
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.project_watcher.unwatch(&PathBuf::from(path)))
+}
+
+// ============================================================================
+// RECENT PROJECTS COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn touch_recent_project(
+    app_handle: tauri::AppHandle,
+    path: String,
+    title: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<recent_projects::RecentProject>, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    state.recent_projects.touch(&app_dir, &path, &title)
+}
+
+#[tauri::command]
+async fn get_recent_projects(
+    app_handle: tauri::AppHandle,
+    limit: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<recent_projects::RecentProjectEntry>, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    let limit = limit.unwrap_or(recent_projects::MAX_RECENT_PROJECTS);
+    Ok(state.recent_projects.list(&app_dir, limit))
+}
+
+#[tauri::command]
+async fn remove_recent_project(
+    app_handle: tauri::AppHandle,
+    path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    state.recent_projects.remove(&app_dir, &path)
+}
+
+#[tauri::command]
+async fn clear_recent_projects(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let app_dir = app_data_dir(&app_handle)?;
+    state.recent_projects.clear(&app_dir)
+}
+
+// ============================================================================
+// AUTOSAVE COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn queue_autosave(
+    path: String,
+    data: serde_json::Value,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.autosave.queue(PathBuf::from(path), data);
+    Ok(())
+}
+
+#[tauri::command]
+async fn flush_autosaves(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<autosave::AutosaveFlushed>, String> {
+    Ok(state.autosave.flush(&app_handle, false).await)
+}
+
+#[tauri::command]
+async fn set_autosave_interval(
+    seconds: f64,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if !seconds.is_finite() || seconds < 0.5 {
+        return Err(format!("Invalid autosave interval: {}", seconds));
+    }
+    
+    state.autosave.set_interval(std::time::Duration::from_secs_f64(seconds));
+    Ok(())
+}
+
+// ============================================================================
+// ASSET STORAGE COMMANDS
+// ============================================================================
+
+#[derive(serde::Deserialize)]
+struct StoreAssetArgs {
+    extension: String,
+}
+
+/// Send the bytes as the raw body with an `x-extension` header
+/// (`invoke("store_asset", bytes, { headers: { "x-extension": "png" } })`),
+/// or as before, `{ bytes, extension }`. See `raw_ipc::binary_payload`.
+#[tauri::command]
+async fn store_asset(
+    app_handle: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<String, error::CommandError> {
+    let (bytes, args) = raw_ipc::binary_payload::<StoreAssetArgs>(&request)?;
+    let app_dir = app_data_dir(&app_handle)?;
+    assets::store_bytes(&app_dir, &bytes, &args.extension)
+}
+
+/// Copies a file from disk into the asset store without passing its bytes
+/// through the webview.
+#[tauri::command]
+async fn store_asset_from_path(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<String, error::CommandError> {
+    let source = fs_sandbox::resolve_path(&app_handle, &path)?;
+    let app_dir = app_data_dir(&app_handle)?;
+
+    tauri::async_runtime::spawn_blocking(move || assets::store_from_path(&app_dir, &source))
+        .await
+        .map_err(|e| format!("Asset import task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn get_absolute_path(
+    app_handle: tauri::AppHandle, 
+    relative_path: String
+) -> Result<String, String> {
+    let app_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    
+    let normalized_path = relative_path.replace('\\', "/");
+    let full_path = app_dir.join(normalized_path);
+    
+    if !full_path.exists() {
+        return Err(format!("File not found: {}", full_path.display()));
+    }
+    
+    Ok(full_path.to_string_lossy().to_string())
+}
+
+/// The asset as a `data:` URL, for webviews where `convertFileSrc` URLs
+/// don't load. Cached in memory.
+#[tauri::command]
+async fn get_asset_data_url(
+    app_handle: tauri::AppHandle,
+    relative_path: String,
+) -> Result<String, error::CommandError> {
+    let app_dir = app_data_dir(&app_handle)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        app_handle.state::<AppState>().asset_data_urls.data_url(&app_dir, &relative_path)
+    })
+    .await
+    .map_err(|e| format!("Asset read task failed: {}", e))?
+}
+
+/// Deletes assets no project references any more. Runs on worker threads
+/// with `asset-cleanup-progress` events; cancel with `cancel_asset_cleanup`.
+#[tauri::command]
+async fn cleanup_global_assets(
+    app_handle: tauri::AppHandle, 
+    active_assets: Vec<String>,
+    job_id: Option<String>,
+) -> Result<assets::CleanupSummary, String> {
+    let active_asset_set: HashSet<String> = active_assets.into_iter().collect();
+    let app_dir = app_data_dir(&app_handle)?;
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        let job = state.cleanup_jobs.register(&job_id);
+        assets::cleanup(&app_handle, &job, &app_dir, &active_asset_set, &state.asset_data_urls)
+    })
+    .await
+    .map_err(|e| format!("Asset cleanup task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn cancel_asset_cleanup(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.cleanup_jobs.cancel(&job_id))
+}
+
+// ============================================================================
+// FILE SYSTEM COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn get_standard_dirs(
+    app_handle: tauri::AppHandle,
+    ensure: Option<bool>,
+) -> Result<standard_dirs::StandardDirs, String> {
+    Ok(standard_dirs::standard_dirs(&app_handle, ensure.unwrap_or(false)))
+}
+
+/// Kept for older frontends; prefer `get_standard_dirs`.
+#[tauri::command]
+async fn get_download_dir(app_handle: tauri::AppHandle) -> Result<String, String> {
+    standard_dirs::standard_dirs(&app_handle, false).download
+        .ok_or_else(|| "Could not determine download directory".to_string())
+}
+
+#[tauri::command]
+async fn get_app_data_path(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let app_dir = app_data_dir(&app_handle)?;
+
+    fs::create_dir_all(&app_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_dir.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn ensure_directory(app_handle: tauri::AppHandle, path: String) -> Result<(), String> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+
+    fs::create_dir_all(&path)
+        .map_err(|e| format!("Failed to create directory '{}': {}", path.display(), e))
+}
+
+#[tauri::command]
+async fn read_file_bytes(app_handle: tauri::AppHandle, path: String) -> Result<Vec<u8>, error::CommandError> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+
+    fs::read(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => error::CommandError::NotFound { path: path.to_string_lossy().to_string() },
+        std::io::ErrorKind::PermissionDenied => error::CommandError::PermissionDenied { path: path.to_string_lossy().to_string() },
+        _ => format!("Failed to read '{}': {}", path.display(), e).into(),
+    })
+}
+
+/// Sends the file as base64 `file-chunk` events followed by a
+/// `file-chunk-end`, for files too large to return from `read_file_bytes`.
+/// Acknowledge each chunk with `ack_file_chunk` to get the next one.
+#[tauri::command]
+async fn read_file_streamed(
+    app_handle: tauri::AppHandle,
+    path: String,
+    chunk_size: Option<usize>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, error::CommandError> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+    state.file_streams.start(&app_handle, path, chunk_size)
+}
+
+#[tauri::command]
+async fn ack_file_chunk(
+    stream_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.file_streams.ack(&stream_id)
+}
+
+#[tauri::command]
+async fn cancel_file_stream(
+    stream_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.file_streams.cancel(&stream_id))
+}
+
+#[derive(serde::Deserialize)]
+struct WriteFileBytesArgs {
+    path: String,
+}
+
+/// Send the bytes as the raw body with the destination in an `x-path`
+/// header (`encodeURIComponent`-ed), or as before, `{ path, bytes }`. See
+/// `raw_ipc::binary_payload`.
+#[tauri::command]
+async fn write_file_bytes(
+    app_handle: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<(), error::CommandError> {
+    let (bytes, args) = raw_ipc::binary_payload::<WriteFileBytesArgs>(&request)?;
+    let path = fs_sandbox::resolve_path(&app_handle, &args.path)?;
+    disk_space::ensure_space(&path, bytes.len() as u64)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+    }
+
+    fs::write(&path, &bytes)
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e).into())
+}
+
+#[tauri::command]
+async fn delete_file(app_handle: tauri::AppHandle, path: String) -> Result<(), error::CommandError> {
+    let path = fs_sandbox::resolve_path_nofollow(&app_handle, &path)?;
+
+    let metadata = fs::symlink_metadata(&path)
+        .map_err(|_| error::CommandError::NotFound { path: path.to_string_lossy().to_string() })?;
+    if metadata.is_dir() {
+        return Err(format!("'{}' is a directory; use delete_directory", path.display()).into());
+    }
+
+    fs::remove_file(&path)
+        .map_err(|e| format!("Failed to delete '{}': {}", path.display(), e))?;
+
+    log::info!("🗑️  Deleted file: {}", path.display());
+    Ok(())
+}
+
+#[tauri::command]
+async fn list_files(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<Vec<String>, String> {
+    let root = fs_sandbox::resolve_path(&app_handle, &path)?;
+
+    // Plain paths, as the legacy command returned; metadata lives in list_files_ex
+    tauri::async_runtime::spawn_blocking(move || {
+        file_listing::list_files(&root, &file_listing::ListFilesOptions::default())
+            .map(|listing| listing.entries.into_iter().map(|entry| entry.path).collect())
+    })
+    .await
+    .map_err(|e| format!("Listing task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn file_exists(app_handle: tauri::AppHandle, path: String) -> Result<bool, String> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+    Ok(path.exists())
+}
+
+#[tauri::command]
+async fn list_files_ex(
+    app_handle: tauri::AppHandle,
+    path: String,
+    options: Option<file_listing::ListFilesOptions>,
+) -> Result<file_listing::FileListing, String> {
+    let root = fs_sandbox::resolve_path(&app_handle, &path)?;
+    let options = options.unwrap_or_default();
+    
+    tauri::async_runtime::spawn_blocking(move || file_listing::list_files(&root, &options))
+        .await
+        .map_err(|e| format!("Listing task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn read_file_range(
+    app_handle: tauri::AppHandle,
+    path: String,
+    offset: u64,
+    length: Option<u64>,
+) -> Result<Vec<u8>, error::CommandError> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+    
+    tauri::async_runtime::spawn_blocking(move || file_io::read_range(&path, offset, length))
+        .await
+        .map_err(|e| format!("Read task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn open_file_writer(
+    app_handle: tauri::AppHandle,
+    path: String,
+    expected_size: Option<u64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, error::CommandError> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+    state.file_writers.open(path, expected_size)
+}
+
+#[tauri::command]
+async fn write_file_chunk(
+    writer_id: String,
+    bytes: Vec<u8>,
+    state: tauri::State<'_, AppState>,
+) -> Result<u64, error::CommandError> {
+    state.file_writers.write_chunk(&writer_id, &bytes)
+}
+
+#[tauri::command]
+async fn close_file_writer(
+    writer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<file_io::WriterSummary, String> {
+    state.file_writers.close(&writer_id)
+}
+
+#[tauri::command]
+async fn abort_file_writer(
+    writer_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.file_writers.abort(&writer_id)
+}
+
+#[tauri::command]
+async fn copy_file(
+    app_handle: tauri::AppHandle,
+    src: String,
+    dst: String,
+    overwrite: bool,
+) -> Result<file_io::TransferSummary, error::CommandError> {
+    let src = fs_sandbox::resolve_path(&app_handle, &src)?;
+    let dst = fs_sandbox::resolve_path(&app_handle, &dst)?;
+    
+    tauri::async_runtime::spawn_blocking(move || file_io::copy_file(&app_handle, &src, &dst, overwrite))
+        .await
+        .map_err(|e| format!("Copy task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn move_file(
+    app_handle: tauri::AppHandle,
+    src: String,
+    dst: String,
+    overwrite: bool,
+) -> Result<file_io::TransferSummary, error::CommandError> {
+    let src = fs_sandbox::resolve_path(&app_handle, &src)?;
+    let dst = fs_sandbox::resolve_path(&app_handle, &dst)?;
+    
+    tauri::async_runtime::spawn_blocking(move || file_io::move_file(&app_handle, &src, &dst, overwrite))
+        .await
+        .map_err(|e| format!("Move task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn delete_directory(
+    app_handle: tauri::AppHandle,
+    path: String,
+    recursive: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<file_io::DeleteSummary, error::CommandError> {
+    // Not followed: deleting a link to a directory must not empty its target
+    let path = fs_sandbox::resolve_path_nofollow(&app_handle, &path)?;
+    let min_depth = state.settings.load(&app_data_dir(&app_handle)?).min_delete_depth
+        .unwrap_or(file_io::DEFAULT_MIN_DELETE_DEPTH);
+    
+    let mut protected: Vec<PathBuf> = fs_sandbox::allowed_roots(&app_handle);
+    protected.extend([
+        dirs::home_dir(),
+        dirs::document_dir(),
+        dirs::download_dir(),
+        dirs::desktop_dir(),
+        dirs::video_dir(),
+        dirs::picture_dir(),
+        dirs::audio_dir(),
+    ].into_iter().flatten());
+    
+    tauri::async_runtime::spawn_blocking(move || file_io::delete_directory(&path, recursive, &protected, min_depth))
+        .await
+        .map_err(|e| format!("Delete task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn file_metadata(
+    app_handle: tauri::AppHandle,
+    path: String,
+    follow_symlinks: Option<bool>,
+) -> Result<file_io::FileMetadata, String> {
+    let path = fs_sandbox::resolve_path_nofollow(&app_handle, &path)?;
+    Ok(file_io::file_metadata(&path, follow_symlinks.unwrap_or(false)))
+}
+
+#[tauri::command]
+async fn watch_directory(
+    app_handle: tauri::AppHandle,
+    path: String,
+    recursive: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let root = fs_sandbox::resolve_path(&app_handle, &path)?;
+    state.dir_watchers.watch(&app_handle, root, recursive)
+}
+
+#[tauri::command]
+async fn unwatch_directory(
+    watcher_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.dir_watchers.unwatch(&watcher_id))
+}
+
+#[tauri::command]
+async fn read_text_file(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<text_decoding::DecodedText, error::CommandError> {
+    const MAX_TEXT_FILE_SIZE: u64 = 64 * 1024 * 1024;
+    
+    let resolved = fs_sandbox::resolve_path(&app_handle, &path)?;
+    
+    let io_error = |e: std::io::Error| match e.kind() {
+        std::io::ErrorKind::NotFound => error::CommandError::NotFound { path: path.clone() },
+        std::io::ErrorKind::PermissionDenied => error::CommandError::PermissionDenied { path: path.clone() },
+        _ => format!("Failed to read '{}': {}", path, e).into(),
+    };
+    
+    let size = fs::metadata(&resolved).map_err(io_error)?.len();
+    if size > MAX_TEXT_FILE_SIZE {
+        return Err(error::CommandError::FileTooLarge { path, size, limit: MAX_TEXT_FILE_SIZE });
+    }
+    
+    let bytes = fs::read(&resolved).map_err(io_error)?;
+    
+    let decoded = text_decoding::decode_text(&bytes)
+        .ok_or(error::CommandError::NotText { path: path.clone() })?;
+    
+    log::info!("📄 Read text file {} as {}", path, decoded.encoding);
+    
+    Ok(decoded)
+}
+
+#[tauri::command]
+async fn compute_file_hash(
+    app_handle: tauri::AppHandle,
+    path: String,
+    algorithm: Option<file_hash::HashAlgorithm>,
+    job_id: Option<String>,
+) -> Result<file_hash::FileHash, error::CommandError> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+    let algorithm = algorithm.unwrap_or_default();
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        file_hash::hash_file(&app_handle, &state.hash_jobs, &job_id, &path, algorithm)
+    })
+    .await
+    .map_err(|e| format!("Hash task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn cancel_file_hash(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.hash_jobs.cancel(&job_id))
+}
+
+#[tauri::command]
+async fn check_disk_space(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<disk_space::DiskSpace, String> {
+    let path = fs_sandbox::resolve_path(&app_handle, &path)?;
+    disk_space::disk_space(&path)
+}
+
+#[tauri::command]
+async fn create_zip(
+    app_handle: tauri::AppHandle,
+    source_paths: Vec<String>,
+    destination: String,
+    overwrite: Option<zip_archive::OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<zip_archive::ArchiveSummary, error::CommandError> {
+    if source_paths.is_empty() {
+        return Err("No files to archive".to_string().into());
+    }
+
+    let sources = source_paths.iter()
+        .map(|p| fs_sandbox::resolve_path(&app_handle, p))
+        .collect::<Result<Vec<_>, _>>()?;
+    let destination = fs_sandbox::resolve_path(&app_handle, &destination)?;
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        zip_archive::create_zip(&app_handle, &state.archive_jobs, &job_id, &sources, &destination, overwrite.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("Zip task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn extract_zip(
+    app_handle: tauri::AppHandle,
+    zip_path: String,
+    destination_dir: String,
+    overwrite: Option<zip_archive::OverwritePolicy>,
+    job_id: Option<String>,
+) -> Result<zip_archive::ArchiveSummary, error::CommandError> {
+    let zip_path = fs_sandbox::resolve_path(&app_handle, &zip_path)?;
+    let destination_dir = fs_sandbox::resolve_path(&app_handle, &destination_dir)?;
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        zip_archive::extract_zip(&app_handle, &state.archive_jobs, &job_id, &zip_path, &destination_dir, overwrite.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("Unzip task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn cancel_zip_job(
+    job_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.archive_jobs.cancel(&job_id))
+}
+
+#[tauri::command]
+async fn open_file(file_path: String) -> Result<(), error::CommandError> {
+    let path = PathBuf::from(&file_path);
+    
+    tauri::async_runtime::spawn_blocking(move || shell::open_with_default_app(&path))
+        .await
+        .map_err(|e| format!("Open task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn show_in_folder(file_path: String) -> Result<shell::RevealOutcome, String> {
+    shell::reveal_in_file_manager(&PathBuf::from(&file_path)).await
+}
+
+/// Clipboard contents cleaned up for the prompter, with a report of what changed.
+#[tauri::command]
+async fn get_clipboard_script_text(
+    app_handle: tauri::AppHandle,
+    options: Option<script_text::SanitizeOptions>,
+) -> Result<script_text::SanitizedText, String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let text = app_handle.clipboard().read_text().ok();
+    // The clipboard plugin only reads plain text; the HTML flavor comes from
+    // arboard directly
+    let html = tauri::async_runtime::spawn_blocking(|| {
+        arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get().html()).ok()
+    }).await.unwrap_or(None);
+
+    let has_content = |flavor: &Option<String>| flavor.as_deref().is_some_and(|s| !s.is_empty());
+    if !has_content(&text) && !has_content(&html) {
+        return Err("Clipboard does not contain any text".to_string());
+    }
+
+    Ok(script_text::sanitize(text.as_deref(), html.as_deref(), &options.unwrap_or_default()))
+}
+
+/// Reads a .docx, Markdown or plain-text script and splits it into segments.
+#[tauri::command]
+async fn import_script(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<script_import::ImportedScript, error::CommandError> {
+    let resolved = fs_sandbox::resolve_path(&app_handle, &path)?;
+
+    tauri::async_runtime::spawn_blocking(move || script_import::import_script(&resolved))
+        .await
+        .map_err(|e| format!("Script import task failed: {}", e))?
+}
+
+/// Per-segment and total read-aloud time. `wpm` is the project's pace.
+#[tauri::command]
+async fn estimate_reading_time(
+    segments: Vec<reading_time::SegmentText>,
+    wpm: Option<f64>,
+) -> Result<reading_time::ReadingEstimate, String> {
+    reading_time::estimate(&segments, wpm)
+}
+
+/// Words per minute achieved in a timed rehearsal.
+#[tauri::command]
+async fn calibrate_wpm(word_count: usize, elapsed_seconds: f64) -> Result<f64, String> {
+    reading_time::calibrate(word_count, elapsed_seconds)
+}
+
+/// Writes a CSV or HTML rundown of the segments with their timings and
+/// returns the path. `open` shows it in the default app afterwards.
+#[tauri::command]
+async fn export_run_sheet(
+    app_handle: tauri::AppHandle,
+    segments: Vec<reading_time::SegmentText>,
+    destination: String,
+    format: run_sheet::RunSheetFormat,
+    wpm: Option<f64>,
+    title: Option<String>,
+    open: Option<bool>,
+) -> Result<String, error::CommandError> {
+    let destination = fs_sandbox::resolve_path(&app_handle, &destination)?;
+    let path = run_sheet::export(&segments, wpm, title.as_deref(), &destination, format)?;
+    let path = path.to_string_lossy().to_string();
+
+    if open.unwrap_or(false) {
+        open_file(path.clone()).await?;
+    }
+    Ok(path)
+}
+
+// ============================================================================
+// LOG COMMANDS
+// ============================================================================
+
+/// Path of the active log file (rotated files sit next to it).
+#[tauri::command]
+async fn get_log_path(app_handle: tauri::AppHandle) -> Result<String, String> {
+    Ok(logging::log_file_path(&app_data_dir(&app_handle)?).to_string_lossy().to_string())
+}
+
+/// Zips all log files to `destination` for attaching to a bug report and
+/// returns the path written.
+#[tauri::command]
+async fn export_logs(
+    app_handle: tauri::AppHandle,
+    destination: String,
+) -> Result<String, error::CommandError> {
+    let destination = fs_sandbox::resolve_path(&app_handle, &destination)?;
+    let app_dir = app_data_dir(&app_handle)?;
+
+    let path = tauri::async_runtime::spawn_blocking(move || logging::export(&app_dir, &destination))
+        .await
+        .map_err(|e| format!("Log export task failed: {}", e))??;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Buffered log records for the debug panel; poll with the previous
+/// `latest_sequence` as `since_sequence` to get only new ones.
+#[tauri::command]
+async fn get_recent_logs(
+    level_filter: Option<String>,
+    limit: Option<usize>,
+    since_sequence: Option<u64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<log_buffer::RecentLogs, String> {
+    state.log_buffer.recent(level_filter.as_deref(), limit, since_sequence)
+}
+
+/// Environment, network and server state for support requests. Never
+/// fails; sections that couldn't be gathered say why.
+#[tauri::command]
+async fn generate_diagnostic_report(app_handle: tauri::AppHandle) -> serde_json::Value {
+    diagnostics::generate(&app_handle).await
+}
+
+/// Crash reports from earlier sessions the user hasn't been told about.
+#[tauri::command]
+async fn get_unreported_crashes(app_handle: tauri::AppHandle) -> Result<Vec<crash_reports::CrashReport>, String> {
+    crash_reports::unreported(&app_data_dir(&app_handle)?)
+}
+
+/// Marks the given reports (all when `ids` is omitted) as seen.
+#[tauri::command]
+async fn mark_crashes_reported(app_handle: tauri::AppHandle, ids: Option<Vec<String>>) -> Result<usize, String> {
+    crash_reports::mark_reported(&app_data_dir(&app_handle)?, ids.as_deref())
+}
+
+// ============================================================================
+// UPDATE CHECK COMMANDS
+// ============================================================================
+
+/// Compares the running version with the latest release. Cached for a few
+/// hours unless `force`; offline and rate-limited checks report status
+/// `unknown` rather than failing.
+#[tauri::command]
+async fn check_for_updates(
+    app_handle: tauri::AppHandle,
+    force: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<update_check::UpdateCheckResult, String> {
+    state.update_checker.check(&app_handle, force.unwrap_or(false)).await
+}
+
+#[tauri::command]
+async fn get_update_check_settings(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<update_check::UpdateCheckSettings, String> {
+    Ok(state.settings.load(&app_data_dir(&app_handle)?).update_check)
+}
+
+#[tauri::command]
+async fn set_update_check_settings(
+    app_handle: tauri::AppHandle,
+    settings: update_check::UpdateCheckSettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<update_check::UpdateCheckSettings, String> {
+    let saved = state.settings.update(&app_data_dir(&app_handle)?, |s| s.update_check = settings)?;
+    state.update_checker.clear_cache();
+    Ok(saved.update_check)
+}
+
+// ============================================================================
+// APPLICATION ENTRY POINT
+// ============================================================================
+
+/// Declares the invoke handler and `COMMAND_NAMES` from one list, so the
+/// names callers may invoke can't drift from what's registered.
+macro_rules! app_commands {
+    ($($command:ident),* $(,)?) => {
+        /// Every command the frontend can invoke.
+        #[cfg_attr(not(test), allow(dead_code))]
+        const COMMAND_NAMES: &[&str] = &[$(stringify!($command)),*];
+
+        fn invoke_handler() -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
+            tauri::generate_handler![$($command),*]
+        }
+    };
+}
+
+app_commands![
+    start_remote_server,
+    stop_remote_server,
+    generate_remote_qr,
+    get_connected_clients,
+    get_remote_settings,
+    set_remote_settings,
+    set_remote_test_mode,
+    force_release_remote_control,
+    start_command_recording,
+    stop_command_recording,
+    list_command_recordings,
+    replay_command_recording,
+    stop_replay,
+    get_current_session_stats,
+    get_remote_session_history,
+    atomic_save_json,
+    load_json,
+    get_supported_schema_version,
+    list_project_templates,
+    create_project_from_template,
+    watch_project_file,
+    unwatch_project_file,
+    touch_recent_project,
+    get_recent_projects,
+    remove_recent_project,
+    clear_recent_projects,
+    queue_autosave,
+    flush_autosaves,
+    set_autosave_interval,
+    store_asset,
+    store_asset_from_path,
+    get_asset_data_url,
+    get_absolute_path,
+    cleanup_global_assets,
+    cancel_asset_cleanup,
+    get_standard_dirs,
+    get_download_dir,
+    get_app_data_path,
+    ensure_directory,
+    read_file_bytes,
+    read_file_streamed,
+    ack_file_chunk,
+    cancel_file_stream,
+    write_file_bytes,
+    delete_file,
+    list_files,
+    file_exists,
+    list_files_ex,
+    read_file_range,
+    open_file_writer,
+    write_file_chunk,
+    close_file_writer,
+    abort_file_writer,
+    copy_file,
+    move_file,
+    delete_directory,
+    file_metadata,
+    watch_directory,
+    unwatch_directory,
+    read_text_file,
+    compute_file_hash,
+    cancel_file_hash,
+    check_disk_space,
+    create_zip,
+    extract_zip,
+    cancel_zip_job,
+    open_file,
+    show_in_folder,
+    get_clipboard_script_text,
+    import_script,
+    estimate_reading_time,
+    calibrate_wpm,
+    export_run_sheet,
+    get_log_path,
+    export_logs,
+    get_recent_logs,
+    generate_diagnostic_report,
+    get_unreported_crashes,
+    mark_crashes_reported,
+    toggle_window_fullscreen,
+    set_window_fullscreen,
+    set_window_always_on_top,
+    toggle_window_always_on_top,
+    get_window_flags,
+    set_window_decorations,
+    set_window_bounds,
+    apply_capture_preset,
+    capture_window_screenshot,
+    capture_window_screenshot_base64,
+    enter_kiosk_mode,
+    exit_kiosk_mode,
+    set_kiosk_pin,
+    set_window_opacity,
+    set_window_click_through,
+    get_overlay_state,
+    create_output_window,
+    close_output_window,
+    get_output_window_state,
+    set_sleep_prevention,
+    get_sleep_prevention,
+    set_cursor_visible,
+    set_cursor_autohide,
+    get_cursor_state,
+    reset_window_state,
+    list_monitors,
+    set_fullscreen_on_monitor,
+    sync_remote_status,
+    sync_scroll_position,
+    frontend_ready,
+    check_for_updates,
+    get_update_check_settings,
+    set_update_check_settings,
+];
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    crash_reports::install_panic_hook();
+
+    tauri::Builder::default()
+        // Must be registered first: a second launch hands its arguments to
+        // this instance and exits before anything else is set up
+        .plugin(tauri_plugin_single_instance::init(|app_handle, argv, cwd| {
+            file_open::focus_main_window(app_handle);
+            file_open::request_open(app_handle, file_open::project_paths_from_args(&argv, Path::new(&cwd)));
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_sql::Builder::default().build())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app_handle, _shortcut, event| {
+                    // Only shortcut we register: the click-through escape hatch
+                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                        window_overlay::release_click_through(app_handle);
+                    }
+                })
+                .build(),
+        )
+        .manage(AppState {
+            remote_server: tokio::sync::Mutex::new(RemoteServerState {
+                is_running: false,
+                port: 0,
+                connection_url: String::new(),
+            }),
+            remote_state: std::sync::Arc::new(tokio::sync::Mutex::new(None)),
+            remote_tasks: RemoteTasks::default(),
+            remote_sessions: remote_sessions::SessionHistory::default(),
+            command_recorder: command_recordings::CommandRecorder::default(),
+            project_watcher: project_watcher::ProjectWatcher::default(),
+            recent_projects: recent_projects::RecentProjects::default(),
+            autosave: autosave::AutosaveQueue::default(),
+            file_writers: file_io::FileWriters::default(),
+            file_streams: file_io::FileStreams::default(),
+            asset_data_urls: assets::DataUrlCache::default(),
+            dir_watchers: dir_watcher::DirWatchers::default(),
+            hash_jobs: jobs::JobRegistry::default(),
+            archive_jobs: jobs::JobRegistry::default(),
+            cleanup_jobs: jobs::JobRegistry::default(),
+            window_control: window_control::WindowControl::default(),
+            settings: app_settings::SettingsStore::default(),
+            window_state: window_state::WindowStateStore::default(),
+            sleep_guard: sleep_guard::SleepGuard::default(),
+            cursor: cursor::CursorControl::default(),
+            frontend_events: frontend_events::FrontendEvents::default(),
+            tray: tray::TrayMenu::default(),
+            update_checker: update_check::UpdateChecker::default(),
+            log_buffer: std::sync::Arc::new(log_buffer::LogBuffer::default()),
+        })
+        .invoke_handler(invoke_handler())
+        .setup(|app| {
+            let app_dir = app.path().app_data_dir().ok();
+            if let Some(app_dir) = &app_dir {
+                crash_reports::set_crash_dir(crash_reports::crash_dir(app_dir));
+            }
+            let log_buffer = app.state::<AppState>().log_buffer.clone();
+            app.handle().plugin(logging::plugin(app.handle(), app_dir.as_deref(), &log_buffer))?;
+
+            log::info!("═══════════════════════════════════════════");
+            log::info!("🚀 SegiTelep Pro Starting Up");
+            log::info!("═══════════════════════════════════════════");
+            log::info!("📱 Environment: {}", if cfg!(debug_assertions) { 
+                "Development" 
+            } else { 
+                "Production" 
             });
             log::info!("💻 Platform: {}", std::env::consts::OS);
             log::info!("🏗️  Architecture: {}", std::env::consts::ARCH);
@@ -463,8 +1795,248 @@ pub fn run() {
             
             log::info!("═══════════════════════════════════════════");
 
+            autosave::spawn_worker(app.handle().clone());
+            file_io::spawn_writer_reaper(app.handle().clone());
+            dir_watcher::spawn_supervisor(app.handle().clone());
+            monitors::spawn_monitor_watch(app.handle().clone());
+            window_state::spawn_flusher(app.handle().clone());
+
+            // The main window starts hidden (tauri.conf.json) so it doesn't
+            // flash at the default position before its saved geometry applies
+            if let Some(main_window) = app.get_webview_window("main") {
+                app.state::<AppState>().window_state.restore(app.handle(), &main_window.as_ref().window());
+                let _ = main_window.show();
+            }
+
+            tray::build(app.handle());
+            update_check::spawn_startup_check(app.handle().clone());
+
+            // Project double-clicked in Explorer / a file manager (macOS
+            // delivers these as RunEvent::Opened instead)
+            let args: Vec<String> = std::env::args().collect();
+            let cwd = std::env::current_dir().unwrap_or_default();
+            file_open::request_open(app.handle(), file_open::project_paths_from_args(&args, &cwd));
+
+            // segitelep:// links: the one we were launched with, then any
+            // arriving later (forwarded by the single-instance plugin)
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                // Installers register the scheme; this covers portable and dev builds
+                #[cfg(any(target_os = "windows", target_os = "linux"))]
+                if let Err(e) = app.deep_link().register_all() {
+                    log::warn!("Failed to register {}:// handler: {}", deep_link::DEEP_LINK_SCHEME, e);
+                }
+
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    deep_link::handle_urls(app.handle(), urls);
+                }
+
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    deep_link::handle_urls(&handle, event.urls());
+                });
+            }
+
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("❌ Fatal error: Failed to run Tauri application");
-}
\ No newline at end of file
+        .on_window_event(|window, event| match event {
+            // Kiosk windows can only be closed by exiting kiosk mode first
+            tauri::WindowEvent::CloseRequested { api, .. }
+                if window.state::<AppState>().window_control.is_kiosk(window.label()) =>
+            {
+                api.prevent_close();
+                log::warn!("🔒 Blocked close of kiosk window '{}'", window.label());
+                let _ = window.emit("kiosk-close-blocked", window.label());
+            }
+            tauri::WindowEvent::CloseRequested { .. } => {
+                let window_state = &window.state::<AppState>().window_state;
+                window_state.capture(window.app_handle(), window);
+                window_state.flush(window.app_handle());
+            }
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                window.state::<AppState>().window_state.capture(window.app_handle(), window);
+            }
+            tauri::WindowEvent::Focused(focused) => {
+                window.state::<AppState>().cursor.on_focus_changed(window, *focused);
+            }
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                drop_import::handle_drop(window.app_handle(), window.label(), paths.clone());
+            }
+            tauri::WindowEvent::Destroyed => {
+                window.state::<AppState>().window_control.forget(window.label());
+                window.state::<AppState>().cursor.forget(window.label());
+                if window.label() == "main" {
+                    output_window::destroy_with_main(window.app_handle());
+                }
+            }
+            _ => {}
+        })
+        .build(tauri::generate_context!())
+        .expect("❌ Fatal error: Failed to build Tauri application")
+        .run(|app_handle, event| match event {
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Opened { urls } => {
+                let paths = urls.iter().filter_map(|url| url.to_file_path().ok()).collect();
+                file_open::request_open(app_handle, paths);
+            }
+            tauri::RunEvent::Exit => {
+                // Write out anything still waiting in the autosave queue
+                let state = app_handle.state::<AppState>();
+                let flushed = tauri::async_runtime::block_on(state.autosave.flush(app_handle, false));
+                if !flushed.is_empty() {
+                    log::info!("💾 Flushed {} pending autosave(s) on exit", flushed.len());
+                }
+                
+                // Record the remote session the app is closing on
+                let shared_state = tauri::async_runtime::block_on(state.remote_state.lock()).take();
+                if let (Some(shared_state), Ok(app_dir)) = (shared_state, app_data_dir(app_handle)) {
+                    if let Err(e) = tauri::async_runtime::block_on(state.remote_sessions.finish(&app_dir, &shared_state)) {
+                        log::warn!("Failed to save remote session stats on exit: {}", e);
+                    }
+                }
+
+                state.dir_watchers.stop_all();
+                state.window_state.flush(app_handle);
+                state.cursor.restore_all(app_handle);
+
+                if let Err(e) = tauri::async_runtime::block_on(state.sleep_guard.set(false)) {
+                    log::warn!("Failed to release sleep prevention on exit: {}", e);
+                }
+            }
+            _ => {}
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::{running_remote_state, RemoteTasks, COMMAND_NAMES};
+
+    /// The fs commands the old main.rs builder exposed to the frontend.
+    const LEGACY_FS_COMMANDS: &[&str] = &[
+        "get_app_data_path",
+        "ensure_directory",
+        "read_file_bytes",
+        "write_file_bytes",
+        "delete_file",
+        "list_files",
+        "file_exists",
+    ];
+
+    /// Command names passed to `invoke(...)` / `invoke<T>(...)` in `source`.
+    fn invoked_commands(source: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut rest = source;
+        while let Some(at) = rest.find("invoke") {
+            rest = &rest[at + "invoke".len()..];
+            let mut call = rest;
+            if let Some(generic) = call.strip_prefix('<') {
+                let Some(end) = generic.find('>') else { continue };
+                call = &generic[end + 1..];
+            }
+            let Some(args) = call.strip_prefix('(') else { continue };
+            let args = args.trim_start();
+            let Some(quote) = args.chars().next().filter(|c| matches!(c, '\'' | '"' | '`')) else { continue };
+            if let Some(end) = args[1..].find(quote) {
+                names.push(args[1..=end].to_string());
+            }
+        }
+        names
+    }
+
+    fn frontend_sources(dir: &std::path::Path, sources: &mut Vec<std::path::PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                frontend_sources(&path, sources);
+            } else if path.extension().is_some_and(|ext| ext == "ts" || ext == "tsx") {
+                sources.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn command_names_are_unique() {
+        let mut names = COMMAND_NAMES.to_vec();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), COMMAND_NAMES.len());
+    }
+
+    #[test]
+    fn legacy_fs_commands_are_registered() {
+        for command in LEGACY_FS_COMMANDS {
+            assert!(COMMAND_NAMES.contains(command), "{} is not registered", command);
+        }
+    }
+
+    #[test]
+    fn every_command_the_frontend_invokes_is_registered() {
+        let frontend = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../src");
+        let mut sources = Vec::new();
+        frontend_sources(&frontend, &mut sources);
+        assert!(!sources.is_empty(), "no frontend sources under {}", frontend.display());
+
+        for path in sources {
+            let source = std::fs::read_to_string(&path).unwrap();
+            for command in invoked_commands(&source) {
+                assert!(COMMAND_NAMES.contains(&command.as_str()), "{} invokes unregistered command {}", path.display(), command);
+            }
+        }
+    }
+
+    #[test]
+    fn invoked_commands_reads_plain_and_generic_calls() {
+        let source = "invoke('a_cmd', {}); await invoke<string>(\"b_cmd\"); invoke(name); invoke<T = unknown>";
+        assert_eq!(invoked_commands(source), ["a_cmd", "b_cmd"]);
+    }
+
+    /// A server task that reports through `stopped` once it's aborted.
+    fn server_task(stopped: tokio::sync::oneshot::Sender<()>) -> tauri::async_runtime::JoinHandle<()> {
+        tauri::async_runtime::spawn(async move {
+            let _stopped = stopped;
+            std::future::pending::<()>().await;
+        })
+    }
+
+    #[test]
+    fn remote_tasks_start_and_stop_after_a_panic_mid_start() {
+        tauri::async_runtime::block_on(async {
+            let tasks = Arc::new(RemoteTasks::default());
+
+            // A start that panics while holding the task list
+            let panicking = tasks.clone();
+            let started = tauri::async_runtime::spawn(async move {
+                let _guard = panicking.0.lock().await;
+                panic!("start_remote_server panicked mid-start");
+            });
+            assert!(started.await.is_err());
+
+            let (first_tx, first_rx) = tokio::sync::oneshot::channel();
+            tasks.replace(vec![server_task(first_tx)]).await;
+
+            // Restarting aborts the previous server's tasks
+            let (second_tx, second_rx) = tokio::sync::oneshot::channel();
+            tasks.replace(vec![server_task(second_tx)]).await;
+            assert!(tokio::time::timeout(Duration::from_secs(5), first_rx).await.unwrap().is_err());
+
+            tasks.abort_all().await;
+            assert!(tokio::time::timeout(Duration::from_secs(5), second_rx).await.unwrap().is_err());
+            assert!(tasks.0.lock().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn running_remote_state_reports_a_stopped_server() {
+        tauri::async_runtime::block_on(async {
+            let remote_state = tokio::sync::Mutex::new(Some(7u32));
+            assert_eq!(running_remote_state(&remote_state).await, Ok(7));
+
+            *remote_state.lock().await = None;
+            assert_eq!(running_remote_state(&remote_state).await, Err("Remote server is not running".to_string()));
+        });
+    }
+}
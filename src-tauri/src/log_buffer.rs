@@ -0,0 +1,128 @@
+// log_buffer.rs - Recent log records kept in memory for the in-app debug panel
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+const CAPACITY: usize = 2000;
+const DEFAULT_LIMIT: usize = 500;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    /// Increases by one per record, so pollers can ask for what's new.
+    pub sequence: u64,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub level: String,
+    #[serde(skip)]
+    severity: log::Level,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentLogs {
+    pub entries: Vec<LogEntry>,
+    /// Sequence of the newest record buffered (matching or not); pass it as
+    /// `since_sequence` on the next poll.
+    pub latest_sequence: u64,
+}
+
+struct Inner {
+    entries: VecDeque<LogEntry>,
+    next_sequence: u64,
+}
+
+/// Ring buffer of the last `CAPACITY` records. A push is one short lock and
+/// no I/O, so logging from hot paths stays cheap.
+pub struct LogBuffer {
+    inner: Mutex<Inner>,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner { entries: VecDeque::with_capacity(CAPACITY), next_sequence: 1 }),
+        }
+    }
+}
+
+// ============================================================================
+// RECORDING
+// ============================================================================
+
+impl LogBuffer {
+    /// Log target feeding this buffer. Warnings and errors are also sent to
+    /// the frontend as `log-entry` events.
+    pub fn dispatch(self: &Arc<Self>, app_handle: AppHandle) -> tauri_plugin_log::fern::Dispatch {
+        let buffer = self.clone();
+
+        tauri_plugin_log::fern::Dispatch::new().chain(tauri_plugin_log::fern::Output::call(move |record| {
+            let entry = buffer.push(record);
+
+            if record.level() <= log::Level::Warn {
+                // Emitting from inside the logger could re-enter it
+                let app_handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = app_handle.emit("log-entry", entry);
+                });
+            }
+        }))
+    }
+
+    fn push(&self, record: &log::Record) -> LogEntry {
+        let message = record.args().to_string();
+        let timestamp = chrono::Utc::now().timestamp_millis();
+
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entry = LogEntry {
+            sequence: inner.next_sequence,
+            timestamp,
+            level: record.level().as_str().to_lowercase(),
+            severity: record.level(),
+            target: record.target().to_string(),
+            message,
+        };
+        inner.next_sequence += 1;
+
+        if inner.entries.len() == CAPACITY {
+            inner.entries.pop_front();
+        }
+        inner.entries.push_back(entry.clone());
+        entry
+    }
+
+    // ========================================================================
+    // QUERYING
+    // ========================================================================
+
+    /// The newest `limit` records after `since_sequence` at `level_filter`
+    /// ("warn" means warnings and errors) or more severe, oldest first.
+    pub fn recent(&self, level_filter: Option<&str>, limit: Option<usize>, since_sequence: Option<u64>) -> Result<RecentLogs, String> {
+        let max_level = match level_filter {
+            Some(level) => log::LevelFilter::from_str(level).map_err(|_| format!("Unknown log level: {}", level))?,
+            None => log::LevelFilter::Trace,
+        };
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(CAPACITY);
+        let since = since_sequence.unwrap_or(0);
+
+        let inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut entries: Vec<LogEntry> = inner.entries.iter()
+            .rev()
+            .take_while(|entry| entry.sequence > since)
+            .filter(|entry| entry.severity <= max_level)
+            .take(limit)
+            .cloned()
+            .collect();
+        entries.reverse();
+
+        Ok(RecentLogs { entries, latest_sequence: inner.next_sequence - 1 })
+    }
+}
@@ -0,0 +1,157 @@
+// logging.rs - Log plugin setup, the rotating log file and log export for bug reports
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::fmt::Arguments;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tauri::plugin::TauriPlugin;
+use tauri::AppHandle;
+use tauri_plugin_log::fern::FormatCallback;
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::log_buffer::LogBuffer;
+
+const LOG_DIR: &str = "logs";
+/// The active file is `segitelep.log`; rotated ones get a timestamp suffix.
+const LOG_FILE_NAME: &str = "segitelep";
+const MAX_LOG_FILE_BYTES: u128 = 5 * 1024 * 1024;
+/// Rotated files kept besides the active one.
+const KEPT_LOG_FILES: usize = 5;
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+pub fn log_dir(app_dir: &Path) -> PathBuf {
+    app_dir.join(LOG_DIR)
+}
+
+pub fn log_file_path(app_dir: &Path) -> PathBuf {
+    log_dir(app_dir).join(format!("{}.log", LOG_FILE_NAME))
+}
+
+/// Stdout and webview as before, the rotating file under `app_dir`, and the
+/// in-memory buffer behind `get_recent_logs`. The plugin rotates inside its
+/// writer lock before appending the pending record, so nothing written across
+/// a rollover is lost.
+pub fn plugin(app_handle: &AppHandle, app_dir: Option<&Path>, log_buffer: &Arc<LogBuffer>) -> TauriPlugin<tauri::Wry> {
+    let mut builder = tauri_plugin_log::Builder::default()
+        .level(if cfg!(debug_assertions) {
+            log::LevelFilter::Debug
+        } else {
+            log::LevelFilter::Info
+        })
+        // Formatting is per target so the buffer gets the bare message
+        .format(|out, message, _record| out.finish(format_args!("{}", message)))
+        .clear_targets()
+        .target(Target::new(TargetKind::Stdout).format(format_line))
+        .target(Target::new(TargetKind::Webview).format(format_line))
+        .target(Target::new(TargetKind::Dispatch(log_buffer.dispatch(app_handle.clone()))))
+        .max_file_size(MAX_LOG_FILE_BYTES)
+        .rotation_strategy(RotationStrategy::KeepSome(KEPT_LOG_FILES));
+
+    if let Some(app_dir) = app_dir {
+        builder = builder.target(Target::new(TargetKind::Folder {
+            path: log_dir(app_dir),
+            file_name: Some(LOG_FILE_NAME.to_string()),
+        }).format(format_line));
+    }
+
+    builder.build()
+}
+
+/// The plugin's default line format: `[date][time][target][LEVEL] message`, UTC.
+fn format_line(out: FormatCallback, message: &Arguments, record: &log::Record) {
+    out.finish(format_args!(
+        "{}[{}][{}] {}",
+        chrono::Utc::now().format("[%Y-%m-%d][%H:%M:%S]"),
+        record.target(),
+        record.level(),
+        message
+    ))
+}
+
+// ============================================================================
+// EXPORT
+// ============================================================================
+
+/// Zips every log file (active and rotated) into `destination`, adding a
+/// `.zip` extension when it has none. Works while the app keeps logging: see
+/// `snapshot`.
+pub fn export(app_dir: &Path, destination: &Path) -> Result<PathBuf, String> {
+    let files = snapshot(&log_dir(app_dir))?;
+    if files.is_empty() {
+        return Err("No log files to export".to_string());
+    }
+
+    let mut destination = destination.to_path_buf();
+    if destination.extension().is_none() {
+        destination.set_extension("zip");
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let mut part_name = destination.as_os_str().to_os_string();
+    part_name.push(".part");
+    let part_path = PathBuf::from(part_name);
+
+    if let Err(e) = write_archive(&files, &part_path) {
+        let _ = fs::remove_file(&part_path);
+        return Err(format!("Failed to write log archive: {}", e));
+    }
+    fs::rename(&part_path, &destination).map_err(|e| {
+        let _ = fs::remove_file(&part_path);
+        format!("Failed to move log archive into place: {}", e)
+    })?;
+
+    log::info!("🪵 Exported {} log file(s) to {}", files.len(), destination.display());
+    Ok(destination)
+}
+
+/// Contents of every `.log` file in `dir`, each read in one go. A rotation
+/// while reading renames the active file, so the directory is listed again
+/// afterwards and anything new is picked up too.
+fn snapshot(dir: &Path) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    for _ in 0..2 {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read log directory '{}': {}", dir.display(), e))?;
+
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            if files.contains_key(&name) || !path.extension().is_some_and(|ext| ext == "log") {
+                continue;
+            }
+            match fs::read(&path) {
+                Ok(bytes) => {
+                    files.insert(name, bytes);
+                }
+                // Rotated away or pruned between listing and reading
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to read log file '{}': {}", path.display(), e)),
+            }
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+fn write_archive(files: &[(String, Vec<u8>)], part_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(part_path)?));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, bytes) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(bytes)?;
+    }
+
+    zip.finish()?.flush()?;
+    Ok(())
+}
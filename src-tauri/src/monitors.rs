@@ -0,0 +1,182 @@
+// monitors.rs - Display enumeration and fullscreen placement on a chosen monitor
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Monitor, PhysicalPosition, Window};
+
+use crate::{app_data_dir, AppState};
+
+/// How often the monitor a fullscreen window is pinned to is checked for removal.
+const MONITOR_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+    /// True for the monitor remembered from the last `set_fullscreen_on_monitor`.
+    pub is_last_used: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MonitorFallbackEvent {
+    window_label: String,
+    requested_id: String,
+    fallback_id: String,
+}
+
+// ============================================================================
+// ENUMERATION
+// ============================================================================
+
+/// Stable-enough identifier: the OS display name (`\\.\DISPLAY2`, `HDMI-1`)
+/// when there is one, otherwise the monitor's origin on the virtual desktop.
+pub fn monitor_id(monitor: &Monitor) -> String {
+    match monitor.name() {
+        Some(name) if !name.is_empty() => name.clone(),
+        _ => format!("monitor@{},{}", monitor.position().x, monitor.position().y),
+    }
+}
+
+pub fn list_monitors(app_handle: &AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    let monitors = app_handle.available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?;
+    let primary_id = app_handle.primary_monitor().ok().flatten().map(|m| monitor_id(&m));
+    let last_used_id = app_data_dir(app_handle).ok()
+        .and_then(|dir| app_handle.state::<AppState>().settings.load(&dir).last_monitor_id);
+
+    Ok(monitors.iter().map(|monitor| {
+        let id = monitor_id(monitor);
+        MonitorInfo {
+            name: monitor.name().cloned(),
+            width: monitor.size().width,
+            height: monitor.size().height,
+            x: monitor.position().x,
+            y: monitor.position().y,
+            scale_factor: monitor.scale_factor(),
+            is_primary: primary_id.as_deref() == Some(id.as_str()),
+            is_last_used: last_used_id.as_deref() == Some(id.as_str()),
+            id,
+        }
+    }).collect())
+}
+
+// ============================================================================
+// PLACEMENT
+// ============================================================================
+
+/// Moves `window` onto the monitor's bounds and enters fullscreen there. An
+/// unknown id (display unplugged since it was listed) falls back to the
+/// primary monitor and emits `monitor-fallback`. Returns the id actually used.
+pub fn fullscreen_on_monitor(app_handle: &AppHandle, window: &Window, requested_id: &str) -> Result<String, String> {
+    let monitors = window.available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?;
+
+    let monitor = match monitors.into_iter().find(|m| monitor_id(m) == requested_id) {
+        Some(monitor) => {
+            // Only remember explicit choices, so a projector that is briefly
+            // unplugged is still the default next time.
+            if let Ok(dir) = app_data_dir(app_handle) {
+                let state = app_handle.state::<AppState>();
+                if let Err(e) = state.settings.update(&dir, |s| s.last_monitor_id = Some(requested_id.to_string())) {
+                    log::warn!("Failed to remember monitor {}: {}", requested_id, e);
+                }
+            }
+            monitor
+        }
+        None => fallback_to_primary(app_handle, window, requested_id)?,
+    };
+
+    place_fullscreen(app_handle, window, &monitor)
+}
+
+fn fallback_to_primary(app_handle: &AppHandle, window: &Window, requested_id: &str) -> Result<Monitor, String> {
+    let primary = window.primary_monitor()
+        .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+        .ok_or_else(|| "No monitors available".to_string())?;
+
+    let fallback_id = monitor_id(&primary);
+    log::warn!("🖥️  Monitor {} not found, falling back to primary {}", requested_id, fallback_id);
+
+    let _ = app_handle.emit("monitor-fallback", MonitorFallbackEvent {
+        window_label: window.label().to_string(),
+        requested_id: requested_id.to_string(),
+        fallback_id,
+    });
+
+    Ok(primary)
+}
+
+fn place_fullscreen(app_handle: &AppHandle, window: &Window, monitor: &Monitor) -> Result<String, String> {
+    let control = &app_handle.state::<AppState>().window_control;
+    let id = monitor_id(monitor);
+
+    // Most platforms ignore moves while fullscreen, so leave it first.
+    if window.is_fullscreen().unwrap_or(false) {
+        control.set_fullscreen(window, false)?;
+    }
+
+    let origin = monitor.position();
+    window.set_position(PhysicalPosition::new(origin.x, origin.y))
+        .map_err(|e| format!("Failed to move window to monitor {}: {}", id, e))?;
+    control.set_fullscreen(window, true)?;
+    control.pin_to_monitor(window.label(), Some(id.clone()));
+
+    log::info!("🖥️  Window '{}' fullscreen on monitor {}", window.label(), id);
+    Ok(id)
+}
+
+// ============================================================================
+// DISCONNECT HANDLING
+// ============================================================================
+
+/// Moves windows whose pinned monitor disappeared (projector unplugged) to
+/// the primary display so the prompter doesn't end up off-screen.
+fn check_pinned_monitors(app_handle: &AppHandle) {
+    let control = &app_handle.state::<AppState>().window_control;
+    let pinned = control.pinned_monitors();
+    if pinned.is_empty() {
+        return;
+    }
+
+    let Ok(monitors) = app_handle.available_monitors() else { return };
+
+    for (label, pinned_id) in pinned {
+        if monitors.iter().any(|m| monitor_id(m) == pinned_id) {
+            continue;
+        }
+
+        let Some(webview_window) = app_handle.get_webview_window(&label) else {
+            control.pin_to_monitor(&label, None);
+            continue;
+        };
+        let window = webview_window.as_ref().window();
+
+        let result = fallback_to_primary(app_handle, &window, &pinned_id)
+            .and_then(|primary| place_fullscreen(app_handle, &window, &primary));
+        if let Err(e) = result {
+            log::error!("Failed to recover window '{}' from lost monitor {}: {}", label, pinned_id, e);
+            control.pin_to_monitor(&label, None);
+        }
+    }
+}
+
+pub fn spawn_monitor_watch(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(MONITOR_CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            check_pinned_monitors(&app_handle);
+        }
+    });
+}
@@ -0,0 +1,121 @@
+// output_window.rs - Secondary talent-facing output window
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, WebviewUrl, WebviewWindowBuilder, Window};
+
+use crate::{monitors, AppState};
+
+pub const OUTPUT_WINDOW_LABEL: &str = "output";
+/// The frontend's external player route.
+const OUTPUT_WINDOW_ROUTE: &str = "index.html#/player";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OutputWindowOptions {
+    /// Monitor id from `list_monitors`; the current monitor when omitted.
+    pub monitor_id: Option<String>,
+    pub fullscreen: bool,
+    pub always_on_top: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputWindowState {
+    pub open: bool,
+    pub fullscreen: bool,
+    pub always_on_top: bool,
+    pub monitor_id: Option<String>,
+}
+
+// ============================================================================
+// LIFECYCLE
+// ============================================================================
+
+/// Opens the output window (or reuses the open one) and applies `options`.
+pub fn create(app_handle: &AppHandle, options: &OutputWindowOptions) -> Result<OutputWindowState, String> {
+    let webview_window = match app_handle.get_webview_window(OUTPUT_WINDOW_LABEL) {
+        Some(existing) => existing,
+        None => {
+            let created = WebviewWindowBuilder::new(app_handle, OUTPUT_WINDOW_LABEL, WebviewUrl::App(OUTPUT_WINDOW_ROUTE.into()))
+                .title("SegiTelep - Output")
+                .inner_size(1280.0, 720.0)
+                .build()
+                .map_err(|e| format!("Failed to create output window: {}", e))?;
+            log::info!("🪟 Output window created");
+            let window = created.as_ref().window();
+            app_handle.state::<AppState>().window_state.restore(app_handle, &window);
+            created
+        }
+    };
+    let window = webview_window.as_ref().window();
+    let control = &app_handle.state::<AppState>().window_control;
+
+    control.set_always_on_top(&window, options.always_on_top)?;
+
+    match (&options.monitor_id, options.fullscreen) {
+        (Some(monitor_id), true) => {
+            monitors::fullscreen_on_monitor(app_handle, &window, monitor_id)?;
+        }
+        (Some(monitor_id), false) => {
+            control.set_fullscreen(&window, false)?;
+            move_to_monitor(&window, monitor_id)?;
+        }
+        (None, fullscreen) => control.set_fullscreen(&window, fullscreen)?,
+    }
+
+    state(app_handle)
+}
+
+/// Returns false when there was no output window to close.
+pub fn close(app_handle: &AppHandle) -> Result<bool, String> {
+    match app_handle.get_webview_window(OUTPUT_WINDOW_LABEL) {
+        Some(window) => {
+            window.close().map_err(|e| format!("Failed to close output window: {}", e))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+pub fn state(app_handle: &AppHandle) -> Result<OutputWindowState, String> {
+    let Some(webview_window) = app_handle.get_webview_window(OUTPUT_WINDOW_LABEL) else {
+        return Ok(OutputWindowState {
+            open: false,
+            fullscreen: false,
+            always_on_top: false,
+            monitor_id: None,
+        });
+    };
+
+    Ok(OutputWindowState {
+        open: true,
+        fullscreen: webview_window.is_fullscreen().map_err(|e| e.to_string())?,
+        always_on_top: webview_window.is_always_on_top().map_err(|e| e.to_string())?,
+        monitor_id: webview_window.current_monitor().ok().flatten().map(|m| monitors::monitor_id(&m)),
+    })
+}
+
+/// Main window gone: take the output window with it instead of leaving an
+/// orphan (bypasses the kiosk close guard, since the app is shutting down).
+pub fn destroy_with_main(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window(OUTPUT_WINDOW_LABEL) {
+        if let Err(e) = window.destroy() {
+            log::warn!("Failed to close output window with main window: {}", e);
+        }
+    }
+}
+
+fn move_to_monitor(window: &Window, monitor_id: &str) -> Result<(), String> {
+    let monitor = window.available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?
+        .into_iter()
+        .find(|m| monitors::monitor_id(m) == monitor_id)
+        .ok_or_else(|| format!("Monitor not found: {}", monitor_id))?;
+
+    let origin = monitor.position();
+    window.set_position(PhysicalPosition::new(origin.x, origin.y))
+        .map_err(|e| format!("Failed to move output window: {}", e))
+}
@@ -0,0 +1,178 @@
+// project_schema.rs - Project file schema versioning and migrations
+
+use serde_json::Value;
+
+use crate::error::CommandError;
+
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Files saved before versioning was introduced carry no version field.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// `MIGRATIONS[i]` upgrades a document from version `i + 1` to `i + 2`.
+///
+/// Migrations must be pure functions of their input: they run on every load of
+/// an old file and the result is only persisted on the next save. To change the
+/// schema, append a function here; `CURRENT_SCHEMA_VERSION` follows automatically.
+const MIGRATIONS: &[Migration] = &[];
+
+pub const CURRENT_SCHEMA_VERSION: u32 = LEGACY_SCHEMA_VERSION + MIGRATIONS.len() as u32;
+
+// ============================================================================
+// VERSIONING
+// ============================================================================
+
+/// Reads the document's schema version, treating a missing field as legacy.
+pub fn schema_version_of(document: &Value) -> Result<u32, String> {
+    match document.get(SCHEMA_VERSION_KEY) {
+        None => Ok(LEGACY_SCHEMA_VERSION),
+        Some(version) => version.as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= LEGACY_SCHEMA_VERSION)
+            .ok_or_else(|| format!("Invalid {}: {}", SCHEMA_VERSION_KEY, version)),
+    }
+}
+
+/// Stamps the current schema version onto object documents before they are saved.
+/// Non-object payloads (e.g. metadata arrays) are left untouched.
+pub fn stamp_current_version(document: &mut Value) {
+    if let Value::Object(map) = document {
+        map.insert(SCHEMA_VERSION_KEY.to_string(), Value::from(CURRENT_SCHEMA_VERSION));
+    }
+}
+
+/// Brings a loaded document up to `CURRENT_SCHEMA_VERSION`.
+pub fn migrate_to_current(document: Value) -> Result<Value, CommandError> {
+    migrate(document, MIGRATIONS)
+}
+
+/// Runs the `migrations` a document at its version still needs, stamping
+/// the version they lead to.
+fn migrate(document: Value, migrations: &[Migration]) -> Result<Value, CommandError> {
+    if !document.is_object() {
+        return Ok(document);
+    }
+
+    let found = schema_version_of(&document)?;
+    let current = LEGACY_SCHEMA_VERSION + migrations.len() as u32;
+
+    if found > current {
+        return Err(CommandError::NewerSchemaVersion {
+            found,
+            supported: current,
+        });
+    }
+
+    let mut migrated = document;
+    for (index, migration) in migrations.iter().enumerate().skip((found - LEGACY_SCHEMA_VERSION) as usize) {
+        let from = LEGACY_SCHEMA_VERSION + index as u32;
+        migrated = migration(migrated)
+            .map_err(|e| format!("Migration from schema v{} to v{} failed: {}", from, from + 1, e))?;
+        log::info!("🔄 Migrated project schema v{} → v{}", from, from + 1);
+    }
+
+    if let Value::Object(map) = &mut migrated {
+        map.insert(SCHEMA_VERSION_KEY.to_string(), Value::from(current));
+    }
+    Ok(migrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// A project as saved before versioning: no `schema_version` field.
+    fn legacy_project() -> Value {
+        json!({
+            "id": "p1",
+            "name": "Sunday service",
+            "segments": [{ "id": "s1", "text": "Welcome", "duration": 12 }],
+            "settings": { "fontSize": 48, "mirror": false },
+        })
+    }
+
+    fn rename_segments(mut document: Value) -> Result<Value, String> {
+        let segments = document.as_object_mut().and_then(|map| map.remove("segments")).ok_or("no segments")?;
+        document["pages"] = segments;
+        Ok(document)
+    }
+
+    fn add_speed(mut document: Value) -> Result<Value, String> {
+        document["settings"]["speed"] = json!(1.0);
+        Ok(document)
+    }
+
+    fn fail(_: Value) -> Result<Value, String> {
+        Err("bad page".to_string())
+    }
+
+    #[test]
+    fn legacy_document_is_stamped_and_otherwise_unchanged() {
+        let migrated = migrate_to_current(legacy_project()).unwrap();
+
+        let mut expected = legacy_project();
+        expected[SCHEMA_VERSION_KEY] = json!(CURRENT_SCHEMA_VERSION);
+        assert_eq!(migrated, expected);
+    }
+
+    #[test]
+    fn legacy_document_runs_every_migration_in_order() {
+        let migrated = migrate(legacy_project(), &[rename_segments, add_speed]).unwrap();
+
+        assert_eq!(migrated[SCHEMA_VERSION_KEY], json!(3));
+        assert!(migrated.get("segments").is_none());
+        assert_eq!(migrated["pages"][0]["text"], json!("Welcome"));
+        assert_eq!(migrated["settings"]["speed"], json!(1.0));
+        assert_eq!(migrated["settings"]["fontSize"], json!(48));
+    }
+
+    #[test]
+    fn partly_migrated_document_skips_migrations_it_has_had() {
+        let mut document = legacy_project();
+        document[SCHEMA_VERSION_KEY] = json!(2);
+
+        // rename_segments would fail on a document without `segments` left
+        let migrated = migrate(document, &[fail, add_speed]).unwrap();
+        assert_eq!(migrated[SCHEMA_VERSION_KEY], json!(3));
+        assert_eq!(migrated["settings"]["speed"], json!(1.0));
+    }
+
+    #[test]
+    fn failing_migration_names_its_versions() {
+        let err = migrate(legacy_project(), &[add_speed, fail]).unwrap_err();
+        assert!(err.to_string().contains("schema v2 to v3 failed: bad page"), "{}", err);
+    }
+
+    #[test]
+    fn newer_document_is_refused() {
+        let mut document = legacy_project();
+        document[SCHEMA_VERSION_KEY] = json!(CURRENT_SCHEMA_VERSION + 1);
+
+        match migrate_to_current(document) {
+            Err(CommandError::NewerSchemaVersion { found, supported }) => {
+                assert_eq!(found, CURRENT_SCHEMA_VERSION + 1);
+                assert_eq!(supported, CURRENT_SCHEMA_VERSION);
+            }
+            other => panic!("expected NewerSchemaVersion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_version_is_an_error() {
+        for version in [json!(0), json!(-1), json!("2"), json!(1.5)] {
+            let mut document = legacy_project();
+            document[SCHEMA_VERSION_KEY] = version;
+            assert!(migrate_to_current(document).is_err());
+        }
+    }
+
+    #[test]
+    fn non_object_documents_pass_through() {
+        let metadata = json!([{ "id": "p1" }, { "id": "p2" }]);
+        assert_eq!(migrate_to_current(metadata.clone()).unwrap(), metadata);
+    }
+}
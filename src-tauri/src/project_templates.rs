@@ -0,0 +1,165 @@
+// project_templates.rs - Built-in and user-supplied project templates
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{project_schema, write_json_atomic};
+
+const BUILTIN_TEMPLATES: &[&str] = &[
+    include_str!("assets/templates/news-bulletin.json"),
+    include_str!("assets/templates/wedding-speech.json"),
+    include_str!("assets/templates/two-column-interview.json"),
+];
+
+pub const USER_TEMPLATES_DIR: &str = "templates";
+const USER_TEMPLATE_PREFIX: &str = "user:";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+struct TemplateFile {
+    id: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    project: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateInfo {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub builtin: bool,
+}
+
+// ============================================================================
+// TEMPLATE LOOKUP
+// ============================================================================
+
+fn builtin_templates() -> Vec<TemplateFile> {
+    BUILTIN_TEMPLATES.iter()
+        .filter_map(|raw| match serde_json::from_str::<TemplateFile>(raw) {
+            Ok(template) => Some(template),
+            Err(e) => {
+                log::error!("Built-in template failed to parse: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// User templates live in `app_data_dir()/templates/*.json`. Their ids are
+/// namespaced so a user file can never shadow a built-in.
+fn user_templates(app_dir: &Path) -> Vec<TemplateFile> {
+    let templates_dir = app_dir.join(USER_TEMPLATES_DIR);
+    let entries = match std::fs::read_dir(&templates_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut templates: Vec<TemplateFile> = entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|path| {
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|raw| serde_json::from_str::<TemplateFile>(&raw).map_err(|e| e.to_string()));
+
+            match parsed {
+                Ok(mut template) => {
+                    template.id = format!("{}{}", USER_TEMPLATE_PREFIX, template.id);
+                    Some(template)
+                }
+                Err(e) => {
+                    log::warn!("⚠️  Skipping invalid user template {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    templates.sort_by_key(|t| t.title.to_lowercase());
+    templates
+}
+
+pub fn list_templates(app_dir: &Path) -> Vec<TemplateInfo> {
+    let builtin = builtin_templates().into_iter().map(|t| (t, true));
+    let user = user_templates(app_dir).into_iter().map(|t| (t, false));
+
+    builtin.chain(user)
+        .map(|(template, builtin)| TemplateInfo {
+            id: template.id,
+            title: template.title,
+            description: template.description,
+            builtin,
+        })
+        .collect()
+}
+
+fn find_template(app_dir: &Path, template_id: &str) -> Option<TemplateFile> {
+    if template_id.starts_with(USER_TEMPLATE_PREFIX) {
+        user_templates(app_dir).into_iter().find(|t| t.id == template_id)
+    } else {
+        builtin_templates().into_iter().find(|t| t.id == template_id)
+    }
+}
+
+// ============================================================================
+// INSTANTIATION
+// ============================================================================
+
+/// Writes a fresh project built from `template_id` to `destination`.
+pub fn create_from_template(
+    app_dir: &Path,
+    template_id: &str,
+    destination: &Path,
+    project_name: &str,
+) -> Result<Value, String> {
+    let template = find_template(app_dir, template_id)
+        .ok_or_else(|| format!("Unknown project template: {}", template_id))?;
+
+    let mut project = template.project;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    {
+        let map = project.as_object_mut()
+            .ok_or_else(|| format!("Template '{}' does not contain a project object", template_id))?;
+        map.insert("id".to_string(), Value::from(new_id()));
+        map.insert("name".to_string(), Value::from(project_name));
+        map.insert("createdAt".to_string(), Value::from(now));
+        map.insert("modifiedAt".to_string(), Value::from(now));
+    }
+
+    // Pages and segments get new ids so two projects from the same template
+    // never share identifiers.
+    if let Some(pages) = project.get_mut("pages").and_then(Value::as_array_mut) {
+        for page in pages {
+            reassign_id(page);
+            if let Some(segments) = page.get_mut("segments").and_then(Value::as_array_mut) {
+                segments.iter_mut().for_each(reassign_id);
+            }
+        }
+    }
+
+    project_schema::stamp_current_version(&mut project);
+    write_json_atomic(destination, &project)?;
+
+    log::info!("📄 Created project '{}' from template '{}' at {}", project_name, template_id, destination.display());
+
+    Ok(project)
+}
+
+fn reassign_id(item: &mut Value) {
+    if let Some(map) = item.as_object_mut() {
+        map.insert("id".to_string(), Value::from(new_id()));
+    }
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
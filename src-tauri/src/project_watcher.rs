@@ -0,0 +1,167 @@
+// project_watcher.rs - Detects external changes to open project files
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+use notify_debouncer_full::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Sync clients (Dropbox, OneDrive) write in bursts, so events are collapsed
+/// over this window before the frontend is notified.
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(750);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified_ms: i64,
+    size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectFileChanged {
+    pub path: String,
+    pub exists: bool,
+    pub modified_ms: i64,
+    pub size: u64,
+}
+
+type ProjectDebouncer = Debouncer<RecommendedWatcher, RecommendedCache>;
+
+#[derive(Default)]
+pub struct ProjectWatcher {
+    watchers: Mutex<HashMap<PathBuf, ProjectDebouncer>>,
+    /// Stamp of the last write made by `atomic_save_json` per file, used to
+    /// tell our own saves apart from external modifications.
+    own_writes: Arc<Mutex<HashMap<PathBuf, FileStamp>>>,
+}
+
+// ============================================================================
+// WATCHER
+// ============================================================================
+
+impl ProjectWatcher {
+    pub fn watch(&self, app_handle: AppHandle, path: &Path) -> Result<(), String> {
+        let target = normalize_path(path);
+        let mut watchers = self.watchers.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if watchers.contains_key(&target) {
+            return Ok(());
+        }
+
+        // Watch the parent directory: atomic saves (ours and most sync tools')
+        // replace the file via rename, which would drop a watch on the file itself.
+        let parent = target.parent()
+            .ok_or_else(|| format!("Project file has no parent directory: {}", target.display()))?
+            .to_path_buf();
+
+        let own_writes = self.own_writes.clone();
+        let event_target = target.clone();
+
+        let mut debouncer = new_debouncer(DEBOUNCE_WINDOW, None, move |result: DebounceEventResult| {
+            match result {
+                Ok(events) => {
+                    let touched = events.iter()
+                        .any(|event| event.paths.iter().any(|p| p == &event_target));
+                    if touched {
+                        notify_if_external(&app_handle, &event_target, &own_writes);
+                    }
+                }
+                Err(errors) => {
+                    for e in errors {
+                        log::warn!("Project watcher error for {}: {}", event_target.display(), e);
+                    }
+                }
+            }
+        }).map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+        debouncer.watch(&parent, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch '{}': {}", parent.display(), e))?;
+
+        log::info!("👀 Watching project file: {}", target.display());
+        watchers.insert(target, debouncer);
+
+        Ok(())
+    }
+
+    pub fn unwatch(&self, path: &Path) -> bool {
+        let target = normalize_path(path);
+        let removed = self.watchers.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(&target);
+        self.own_writes.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(&target);
+
+        match removed {
+            Some(debouncer) => {
+                debouncer.stop_nonblocking();
+                log::info!("🙈 Stopped watching project file: {}", target.display());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remembers the on-disk stamp of a file we just wrote so the resulting
+    /// change notification is suppressed.
+    pub fn record_own_write(&self, path: &Path) {
+        let target = normalize_path(path);
+        if let Some(stamp) = read_stamp(&target) {
+            self.own_writes.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(target, stamp);
+        }
+    }
+}
+
+fn notify_if_external(
+    app_handle: &AppHandle,
+    path: &Path,
+    own_writes: &Mutex<HashMap<PathBuf, FileStamp>>,
+) {
+    let stamp = read_stamp(path);
+
+    if let Some(current) = stamp {
+        if own_writes.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(path) == Some(&current) {
+            log::debug!("Ignoring change from our own save: {}", path.display());
+            return;
+        }
+    }
+
+    let payload = ProjectFileChanged {
+        path: path.to_string_lossy().to_string(),
+        exists: stamp.is_some(),
+        modified_ms: stamp.map(|s| s.modified_ms).unwrap_or(0),
+        size: stamp.map(|s| s.size).unwrap_or(0),
+    };
+
+    log::info!("📝 Project file changed on disk: {}", payload.path);
+
+    if let Err(e) = app_handle.emit("project-file-changed", payload) {
+        log::error!("Failed to emit project-file-changed event: {}", e);
+    }
+}
+
+fn read_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified_ms = metadata.modified().ok()?
+        .duration_since(UNIX_EPOCH).ok()?
+        .as_millis() as i64;
+
+    Some(FileStamp {
+        modified_ms,
+        size: metadata.len(),
+    })
+}
+
+/// Resolves the parent directory so event paths (which are always absolute and
+/// canonical) compare equal to the path the frontend handed us.
+fn normalize_path(path: &Path) -> PathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => parent.canonicalize()
+            .map(|p| p.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
@@ -0,0 +1,92 @@
+// rate_limit.rs - Per-address token buckets for the remote HTTP endpoints
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Addresses idle long enough to have refilled are forgotten once the map
+/// grows past this.
+const PRUNE_THRESHOLD: usize = 256;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Allows `burst` requests at once per address, refilling at `per_second`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_second: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_second: f64, burst: f64) -> Self {
+        Self {
+            per_second,
+            burst,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token for `ip`; false when its bucket is empty.
+    pub fn check(&self, ip: IpAddr) -> bool {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            let refill_secs = self.burst / self.per_second;
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated).as_secs_f64() < refill_secs);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket { tokens: self.burst, updated: now });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.burst);
+        bucket.updated = now;
+
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::Arc;
+
+    use super::*;
+
+    const PHONE: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+
+    #[test]
+    fn empty_bucket_refuses_until_refilled() {
+        let limiter = RateLimiter::new(0.001, 2.0);
+        assert!(limiter.check(PHONE));
+        assert!(limiter.check(PHONE));
+        assert!(!limiter.check(PHONE));
+        assert!(limiter.check(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 21))));
+    }
+
+    #[test]
+    fn keeps_working_after_a_panic_while_locked() {
+        let limiter = Arc::new(RateLimiter::new(0.001, 2.0));
+        assert!(limiter.check(PHONE));
+
+        let panicking = limiter.clone();
+        let result = std::thread::spawn(move || {
+            let _buckets = panicking.buckets.lock().unwrap();
+            panic!("request handler panicked while holding the buckets");
+        }).join();
+        assert!(result.is_err());
+        assert!(limiter.buckets.is_poisoned());
+
+        assert!(limiter.check(PHONE));
+        assert!(!limiter.check(PHONE));
+    }
+}
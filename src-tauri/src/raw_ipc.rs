@@ -0,0 +1,133 @@
+// raw_ipc.rs - Binary command payloads sent as raw IPC bodies instead of JSON arrays
+
+use std::borrow::Cow;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tauri::http::HeaderMap;
+use tauri::ipc::{InvokeBody, Request};
+
+/// Only headers with this prefix become arguments.
+const ARG_HEADER_PREFIX: &str = "x-";
+
+#[derive(Deserialize)]
+struct JsonPayload<A> {
+    bytes: Vec<u8>,
+    #[serde(flatten)]
+    args: A,
+}
+
+/// Bytes and remaining arguments of a command invoked either way:
+///
+/// - raw, the fast path: `invoke(cmd, uint8Array, { headers: { "x-<arg>": encodeURIComponent(value) } })`;
+///   the body arrives untouched and each `x-` header becomes an argument
+///   (`x-file-path` is `file_path`).
+/// - JSON, as before: `invoke(cmd, { bytes: [...], <arg>: value })`, where every
+///   byte is a number in a JSON array (several times the payload size).
+pub fn binary_payload<'r, A: DeserializeOwned>(request: &'r Request<'_>) -> Result<(Cow<'r, [u8]>, A), String> {
+    split_payload(request.body(), request.headers())
+}
+
+fn split_payload<'b, A: DeserializeOwned>(body: &'b InvokeBody, headers: &HeaderMap) -> Result<(Cow<'b, [u8]>, A), String> {
+    match body {
+        InvokeBody::Raw(bytes) => {
+            let mut args = Map::new();
+            for (name, value) in headers {
+                let Some(arg) = name.as_str().strip_prefix(ARG_HEADER_PREFIX) else { continue };
+                let value = value.to_str().map_err(|_| format!("Header '{}' is not ASCII; percent-encode it", name))?;
+                args.insert(arg.replace('-', "_"), Value::String(percent_decode(value)?));
+            }
+            let args = serde_json::from_value(Value::Object(args))
+                .map_err(|e| format!("Invalid command headers: {}", e))?;
+            Ok((Cow::Borrowed(bytes.as_slice()), args))
+        }
+        InvokeBody::Json(value) => {
+            let payload = JsonPayload::<A>::deserialize(value)
+                .map_err(|e| format!("Invalid command arguments: {}", e))?;
+            Ok((Cow::Owned(payload.bytes), payload.args))
+        }
+    }
+}
+
+/// Undoes `encodeURIComponent`, which headers need for non-ASCII paths.
+fn percent_decode(value: &str) -> Result<String, String> {
+    let input = value.as_bytes();
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] == b'%' {
+            let hex = input.get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("Invalid percent-encoding in '{}'", value))?;
+            bytes.push(hex);
+            i += 3;
+        } else {
+            bytes.push(input[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| format!("Percent-encoded header is not UTF-8: '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::http::HeaderValue;
+
+    use super::*;
+
+    const PAYLOAD_SIZE: usize = 8 * 1024 * 1024;
+
+    #[derive(Debug, Deserialize)]
+    struct WriteArgs {
+        file_path: String,
+    }
+
+    fn payload() -> Vec<u8> {
+        (0..PAYLOAD_SIZE).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn raw_body_is_borrowed_not_copied() {
+        let body = InvokeBody::Raw(payload());
+        let mut headers = HeaderMap::new();
+        headers.insert("x-file-path", HeaderValue::from_static("%2Ftmp%2FGr%C3%BC%C3%9Fe.bin"));
+        headers.insert("content-type", HeaderValue::from_static("application/octet-stream"));
+
+        let (bytes, args) = split_payload::<WriteArgs>(&body, &headers).unwrap();
+
+        let InvokeBody::Raw(original) = &body else { unreachable!() };
+        assert!(matches!(bytes, Cow::Borrowed(_)));
+        assert_eq!(bytes.as_ptr(), original.as_ptr());
+        assert_eq!(bytes.len(), PAYLOAD_SIZE);
+        assert_eq!(args.file_path, "/tmp/Grüße.bin");
+    }
+
+    #[test]
+    fn json_array_yields_the_same_bytes_as_an_owned_copy() {
+        let raw_body = InvokeBody::Raw(payload());
+        let mut headers = HeaderMap::new();
+        headers.insert("x-file-path", HeaderValue::from_static("script.bin"));
+        let json_body = InvokeBody::Json(serde_json::json!({ "bytes": payload(), "file_path": "script.bin" }));
+
+        let (raw_bytes, _) = split_payload::<WriteArgs>(&raw_body, &headers).unwrap();
+        let (json_bytes, args) = split_payload::<WriteArgs>(&json_body, &HeaderMap::new()).unwrap();
+
+        assert!(matches!(raw_bytes, Cow::Borrowed(_)));
+        assert!(matches!(json_bytes, Cow::Owned(_)));
+        assert_eq!(raw_bytes.len(), PAYLOAD_SIZE);
+        assert_eq!(raw_bytes, json_bytes);
+        assert_eq!(args.file_path, "script.bin");
+    }
+
+    #[test]
+    fn invalid_header_encoding_is_an_error() {
+        let body = InvokeBody::Raw(vec![1, 2, 3]);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-file-path", HeaderValue::from_static("%E2%28"));
+        assert!(split_payload::<WriteArgs>(&body, &headers).is_err());
+    }
+}
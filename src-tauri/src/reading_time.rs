@@ -0,0 +1,164 @@
+// reading_time.rs - How long segments take to read aloud
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A typical broadcast reading pace.
+pub const DEFAULT_WPM: f64 = 150.0;
+/// Chinese and Japanese are read by character, at roughly this many per
+/// minute for someone reading other text at `DEFAULT_WPM`.
+const DEFAULT_CJK_CPM: f64 = 300.0;
+const MIN_WPM: f64 = 30.0;
+const MAX_WPM: f64 = 600.0;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// The fields of a project segment that timing needs; other fields the
+/// frontend sends along are ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SegmentText {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    /// "text", "image", ... Only text segments are read.
+    #[serde(rename = "type")]
+    pub segment_type: Option<String>,
+    #[serde(default)]
+    pub content: String,
+    /// Fixed on-screen time in seconds, for non-text segments.
+    pub duration: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentEstimate {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub word_count: usize,
+    /// Chinese/Japanese characters, timed per character instead of per word.
+    pub cjk_char_count: usize,
+    pub seconds: f64,
+    /// `m:ss`, or `h:mm:ss` from an hour up.
+    pub formatted: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingEstimate {
+    pub wpm: f64,
+    pub segments: Vec<SegmentEstimate>,
+    pub total_word_count: usize,
+    pub total_cjk_char_count: usize,
+    pub total_seconds: f64,
+    pub total_formatted: String,
+}
+
+// ============================================================================
+// ESTIMATION
+// ============================================================================
+
+/// Times every segment at `wpm` (the project's pace; `DEFAULT_WPM` when
+/// unset). Empty and punctuation-only segments take no time.
+pub fn estimate(segments: &[SegmentText], wpm: Option<f64>) -> Result<ReadingEstimate, String> {
+    let wpm = match wpm {
+        Some(wpm) => validate_wpm(wpm)?,
+        None => DEFAULT_WPM,
+    };
+    let cjk_cpm = wpm * DEFAULT_CJK_CPM / DEFAULT_WPM;
+
+    let segments: Vec<SegmentEstimate> = segments.iter().map(|segment| {
+        let is_text = segment.segment_type.as_deref().map_or(true, |kind| kind == "text");
+        let (word_count, cjk_char_count) = if is_text { count_words(&segment.content) } else { (0, 0) };
+
+        let seconds = if is_text {
+            word_count as f64 / wpm * 60.0 + cjk_char_count as f64 / cjk_cpm * 60.0
+        } else {
+            segment.duration.filter(|d| d.is_finite() && *d > 0.0).unwrap_or(0.0)
+        };
+
+        SegmentEstimate {
+            id: segment.id.clone(),
+            name: segment.name.clone(),
+            word_count,
+            cjk_char_count,
+            seconds,
+            formatted: format_duration(seconds),
+        }
+    }).collect();
+
+    let total_seconds = segments.iter().map(|segment| segment.seconds).sum();
+    Ok(ReadingEstimate {
+        wpm,
+        total_word_count: segments.iter().map(|segment| segment.word_count).sum(),
+        total_cjk_char_count: segments.iter().map(|segment| segment.cjk_char_count).sum(),
+        total_seconds,
+        total_formatted: format_duration(total_seconds),
+        segments,
+    })
+}
+
+/// Pace actually achieved in a rehearsal, for storing as the project's WPM.
+pub fn calibrate(word_count: usize, elapsed_seconds: f64) -> Result<f64, String> {
+    if !elapsed_seconds.is_finite() || elapsed_seconds <= 0.0 {
+        return Err("Elapsed time must be a positive number of seconds".to_string());
+    }
+    if word_count == 0 {
+        return Err("Cannot calibrate from a rehearsal with no words".to_string());
+    }
+
+    let wpm = word_count as f64 / (elapsed_seconds / 60.0);
+    validate_wpm(wpm).map_err(|_| {
+        format!("Measured pace of {:.0} words per minute is implausible; check the timing", wpm)
+    })?;
+
+    // One decimal is plenty and keeps the stored value readable
+    Ok((wpm * 10.0).round() / 10.0)
+}
+
+fn validate_wpm(wpm: f64) -> Result<f64, String> {
+    if !wpm.is_finite() || !(MIN_WPM..=MAX_WPM).contains(&wpm) {
+        return Err(format!("Words per minute must be between {} and {}", MIN_WPM, MAX_WPM));
+    }
+    Ok(wpm)
+}
+
+/// (words, CJK characters). Words follow Unicode word boundaries, so
+/// punctuation and symbols don't count; Han, Hiragana and Katakana are
+/// counted per character since those scripts don't separate words.
+fn count_words(text: &str) -> (usize, usize) {
+    let mut words = 0;
+    let mut cjk_chars = 0;
+
+    for word in text.unicode_words() {
+        let cjk = word.chars().filter(|c| is_cjk(*c)).count();
+        if cjk > 0 {
+            cjk_chars += cjk;
+        } else {
+            words += 1;
+        }
+    }
+
+    (words, cjk_chars)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'     // Hiragana, Katakana
+        | '\u{31F0}'..='\u{31FF}'   // Katakana phonetic extensions
+        | '\u{3400}'..='\u{4DBF}'   // CJK extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK unified ideographs
+        | '\u{F900}'..='\u{FAFF}'   // CJK compatibility ideographs
+        | '\u{FF66}'..='\u{FF9F}'   // Half-width Katakana
+        | '\u{20000}'..='\u{2FA1F}' // CJK extensions B-F, supplement
+    )
+}
+
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (hours, minutes, secs) = (total / 3600, total % 3600 / 60, total % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{}:{:02}", minutes, secs)
+    }
+}
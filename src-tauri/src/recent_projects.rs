@@ -0,0 +1,120 @@
+// recent_projects.rs - Recently opened projects, persisted in the app data dir
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::write_json_atomic;
+
+const RECENT_PROJECTS_FILE: &str = "recent_projects.json";
+pub const MAX_RECENT_PROJECTS: usize = 20;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub path: String,
+    pub title: String,
+    /// Epoch milliseconds of the last time the project was opened.
+    pub last_opened: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentProjectEntry {
+    #[serde(flatten)]
+    pub project: RecentProject,
+    /// False when the file has been moved or deleted since it was last opened.
+    pub exists: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecentProjectsFile {
+    projects: Vec<RecentProject>,
+}
+
+/// Serializes read-modify-write cycles so rapid successive opens can't
+/// interleave and drop each other's entries.
+#[derive(Default)]
+pub struct RecentProjects {
+    lock: Mutex<()>,
+}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+impl RecentProjects {
+    pub fn touch(&self, app_dir: &Path, path: &str, title: &str) -> Result<Vec<RecentProject>, String> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut file = load(app_dir);
+
+        file.projects.retain(|p| p.path != path);
+        file.projects.insert(0, RecentProject {
+            path: path.to_string(),
+            title: title.to_string(),
+            last_opened: chrono::Utc::now().timestamp_millis(),
+        });
+        // Entries are kept most-recent-first, so truncating evicts the least recently used.
+        file.projects.truncate(MAX_RECENT_PROJECTS);
+
+        save(app_dir, &file)?;
+        Ok(file.projects)
+    }
+
+    pub fn list(&self, app_dir: &Path, limit: usize) -> Vec<RecentProjectEntry> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        load(app_dir).projects
+            .into_iter()
+            .take(limit)
+            .map(|project| {
+                let exists = Path::new(&project.path).is_file();
+                RecentProjectEntry { project, exists }
+            })
+            .collect()
+    }
+
+    pub fn remove(&self, app_dir: &Path, path: &str) -> Result<bool, String> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut file = load(app_dir);
+
+        let before = file.projects.len();
+        file.projects.retain(|p| p.path != path);
+        if file.projects.len() == before {
+            return Ok(false);
+        }
+
+        save(app_dir, &file)?;
+        Ok(true)
+    }
+
+    pub fn clear(&self, app_dir: &Path) -> Result<(), String> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        save(app_dir, &RecentProjectsFile::default())
+    }
+}
+
+fn file_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(RECENT_PROJECTS_FILE)
+}
+
+fn load(app_dir: &Path) -> RecentProjectsFile {
+    let path = file_path(app_dir);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return RecentProjectsFile::default(),
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("⚠️  Ignoring corrupt recent projects file {}: {}", path.display(), e);
+        RecentProjectsFile::default()
+    })
+}
+
+fn save(app_dir: &Path, file: &RecentProjectsFile) -> Result<(), String> {
+    write_json_atomic(&file_path(app_dir), file)
+}
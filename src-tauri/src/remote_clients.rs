@@ -0,0 +1,646 @@
+// remote_clients.rs - Registry of connected remotes and their resumable sessions
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize, Serializer};
+use tokio::sync::Notify;
+
+use crate::remote_server::RemoteSettings;
+
+const MAX_NAME_LEN: usize = 64;
+const MAX_SUBSCRIPTIONS: usize = 16;
+/// Topic for the high-frequency scroll position frames.
+pub const SCROLL_TOPIC: &str = "scroll";
+const DEFAULT_SCROLL_RATE_HZ: u32 = 30;
+const MAX_SCROLL_RATE_HZ: u32 = 60;
+/// Telemetry arriving sooner than this after the last accepted update is
+/// dropped, unless it changes visibility or charging.
+const TELEMETRY_MIN_INTERVAL: Duration = Duration::from_secs(3);
+/// Floor for visibility or charging changes, so a remote flipping them on
+/// every report can't bypass the throttle.
+const TELEMETRY_STATE_CHANGE_MIN_INTERVAL: Duration = Duration::from_millis(500);
+/// Battery level (0 to 1) below which an unplugged remote raises a warning.
+const LOW_BATTERY_LEVEL: f64 = 0.2;
+const MAX_TELEMETRY_RTT_MS: f64 = 60_000.0;
+/// How long a holder whose connection dropped keeps the control lock, so a
+/// phone blipping off Wi-Fi doesn't lose it to another remote.
+const CONTROL_RECONNECT_GRACE: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// One logical remote. `id` belongs to the current WebSocket connection;
+/// `session_id` survives reconnects, along with name, role and subscriptions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectedClient {
+    pub id: u64,
+    pub session_id: String,
+    pub address: String,
+    pub connected_at: i64,
+    /// Last message or pong received, epoch milliseconds.
+    pub last_seen: LastSeen,
+    /// Round trip of the latest keepalive ping; `None` until the first pong.
+    pub ping_ms: Option<f64>,
+    pub name: Option<String>,
+    pub role: Option<String>,
+    pub subscriptions: Vec<String>,
+    /// How often scroll frames are sampled for this client, when it's
+    /// subscribed to them.
+    pub scroll_rate_hz: u32,
+    /// Reconnects that picked this session back up.
+    pub resumed_count: u32,
+    /// Holds the control lock; filled in by `ClientRegistry::list`.
+    pub has_control: bool,
+    /// Latest `client-telemetry`; all `None` for remotes that never send it.
+    pub telemetry: ClientTelemetry,
+    /// Handed out in the welcome; a session only resumes for a hello that
+    /// presents it.
+    #[serde(skip)]
+    session_secret: String,
+}
+
+/// A connection's last-seen time, shared with its socket loop so every
+/// frame can bump it without locking the registry. Serializes as epoch
+/// milliseconds.
+#[derive(Debug, Clone, Default)]
+pub struct LastSeen(Arc<AtomicI64>);
+
+impl LastSeen {
+    fn new(at: i64) -> Self {
+        Self(Arc::new(AtomicI64::new(at)))
+    }
+
+    pub fn touch(&self, at: i64) {
+        self.0.store(at, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Serialize for LastSeen {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.get())
+    }
+}
+
+/// Sent by a remote after the welcome, to name itself or to pick up the
+/// session it had before its connection dropped. Fields left out keep the
+/// session's current (or restored) values.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ClientHello {
+    pub session_id: Option<String>,
+    /// The `session_secret` from the welcome that issued `session_id`.
+    pub session_secret: Option<String>,
+    pub name: Option<String>,
+    pub role: Option<String>,
+    pub subscriptions: Option<Vec<String>>,
+    /// 1 to 60; 30 unless set.
+    pub scroll_rate_hz: Option<u32>,
+}
+
+/// Periodic report from a remote about its own health. Every field is
+/// optional; what a browser can't tell stays `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientTelemetry {
+    /// 0 to 1, as the Battery Status API reports it.
+    pub battery_level: Option<f64>,
+    pub charging: Option<bool>,
+    /// `document.visibilityState == "visible"`.
+    pub visible: Option<bool>,
+    /// Round trip the remote measured itself, in milliseconds.
+    pub rtt_ms: Option<f64>,
+    /// When the server accepted this report, epoch milliseconds.
+    pub received_at: Option<i64>,
+    #[serde(skip)]
+    accepted: Option<Instant>,
+}
+
+/// Sent as `remote-client-warning` when a remote looks about to drop out.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientWarning {
+    pub client_id: u64,
+    pub name: String,
+    /// `low_battery` or `hidden_with_control`.
+    pub kind: &'static str,
+    pub battery_level: Option<f64>,
+}
+
+#[derive(Debug)]
+struct DetachedSession {
+    client: ConnectedClient,
+    detached_at: Instant,
+}
+
+/// Exclusive command rights, held by a session rather than a connection so
+/// a resumed session keeps them.
+#[derive(Debug)]
+struct ControlLock {
+    session_id: String,
+    last_active: Instant,
+}
+
+/// The remote holding the control lock, as shown to everyone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlHolder {
+    pub client_id: u64,
+    pub name: String,
+    /// The holder's connection dropped; it keeps the lock for a short grace
+    /// period in case it reconnects.
+    #[serde(default)]
+    pub detached: bool,
+}
+
+#[derive(Debug)]
+pub struct ClientRegistry {
+    clients: HashMap<u64, ConnectedClient>,
+    /// Closes a connection whose session was resumed elsewhere.
+    kickers: HashMap<u64, Arc<Notify>>,
+    /// Sessions whose connection dropped, by session id, until they resume
+    /// or `idle_window` runs out.
+    detached: HashMap<String, DetachedSession>,
+    next_id: u64,
+    idle_window: Duration,
+    control: Option<ControlLock>,
+    /// The lock frees itself after this long without a command from the
+    /// holder; `None` never.
+    control_idle: Option<Duration>,
+    /// How long a detached holder keeps the lock.
+    control_grace: Duration,
+}
+
+impl ConnectedClient {
+    /// Scroll frame rate if the client subscribed to scroll frames.
+    pub fn scroll_rate(&self) -> Option<u32> {
+        self.subscriptions.iter().any(|topic| topic == SCROLL_TOPIC).then_some(self.scroll_rate_hz)
+    }
+}
+
+// ============================================================================
+// REGISTRY
+// ============================================================================
+
+impl ClientRegistry {
+    pub fn new(settings: &RemoteSettings) -> Self {
+        Self {
+            clients: HashMap::new(),
+            kickers: HashMap::new(),
+            detached: HashMap::new(),
+            next_id: 1,
+            idle_window: settings.session_idle_window(),
+            control: None,
+            control_idle: settings.control_idle_timeout(),
+            control_grace: CONTROL_RECONNECT_GRACE,
+        }
+    }
+
+    pub fn apply_settings(&mut self, settings: &RemoteSettings) {
+        self.idle_window = settings.session_idle_window();
+        self.control_idle = settings.control_idle_timeout();
+        self.expire();
+    }
+
+    /// Registers a new connection under a fresh session. The `Notify` fires
+    /// if another connection takes the session over.
+    pub fn connect(&mut self, peer_addr: std::net::SocketAddr) -> (u64, Arc<Notify>) {
+        self.expire();
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let now = chrono::Utc::now().timestamp_millis();
+        self.clients.insert(id, ConnectedClient {
+            id,
+            session_id: uuid::Uuid::new_v4().to_string(),
+            address: peer_addr.to_string(),
+            connected_at: now,
+            last_seen: LastSeen::new(now),
+            ping_ms: None,
+            name: None,
+            role: None,
+            subscriptions: Vec::new(),
+            scroll_rate_hz: DEFAULT_SCROLL_RATE_HZ,
+            resumed_count: 0,
+            has_control: false,
+            telemetry: ClientTelemetry::default(),
+            session_secret: uuid::Uuid::new_v4().to_string(),
+        });
+
+        let kick = Arc::new(Notify::new());
+        self.kickers.insert(id, kick.clone());
+        (id, kick)
+    }
+
+    /// Drops the connection, keeping its session for `idle_window` and the
+    /// control lock, if it held it, for the reconnect grace period. False if
+    /// it was already gone (taken over by a resumed session).
+    pub fn disconnect(&mut self, id: u64) -> bool {
+        self.kickers.remove(&id);
+        let Some(client) = self.clients.remove(&id) else { return false };
+
+        self.detached.insert(client.session_id.clone(), DetachedSession {
+            client,
+            detached_at: Instant::now(),
+        });
+        true
+    }
+
+    /// Applies a hello to connection `id`. A known, unexpired `session_id`
+    /// presented with its secret moves that session onto this connection,
+    /// replacing the stale connection if it's still registered. Returns
+    /// whether a session was resumed.
+    pub fn hello(&mut self, id: u64, hello: ClientHello) -> bool {
+        self.expire();
+        if !self.clients.contains_key(&id) {
+            return false;
+        }
+
+        let previous = match (hello.session_id.as_deref(), hello.session_secret.as_deref()) {
+            (Some(session_id), Some(secret)) => self.take_session(session_id, secret, id),
+            _ => None,
+        };
+        let resumed = previous.is_some();
+
+        let Some(client) = self.clients.get_mut(&id) else { return false };
+        if let Some(previous) = previous {
+            client.session_id = previous.session_id;
+            client.session_secret = previous.session_secret;
+            client.name = previous.name;
+            client.role = previous.role;
+            client.subscriptions = previous.subscriptions;
+            client.scroll_rate_hz = previous.scroll_rate_hz;
+            client.resumed_count = previous.resumed_count + 1;
+        }
+        if let Some(name) = hello.name {
+            client.name = Some(name.chars().take(MAX_NAME_LEN).collect());
+        }
+        if let Some(role) = hello.role {
+            client.role = Some(role.chars().take(MAX_NAME_LEN).collect());
+        }
+        if let Some(mut subscriptions) = hello.subscriptions {
+            subscriptions.sort();
+            subscriptions.dedup();
+            subscriptions.truncate(MAX_SUBSCRIPTIONS);
+            client.subscriptions = subscriptions;
+        }
+        if let Some(rate) = hello.scroll_rate_hz {
+            client.scroll_rate_hz = rate.clamp(1, MAX_SCROLL_RATE_HZ);
+        }
+        resumed
+    }
+
+    /// Detaches `session_id` from wherever it lives so connection `id` can
+    /// take it over, kicking the old connection if there is one.
+    fn take_session(&mut self, session_id: &str, secret: &str, id: u64) -> Option<ConnectedClient> {
+        if let Some(detached) = self.detached.get(session_id) {
+            if detached.client.session_secret != secret {
+                log::warn!("🚫 Refusing to resume remote session {} without its secret", session_id);
+                return None;
+            }
+            return self.detached.remove(session_id).map(|detached| detached.client);
+        }
+
+        // The phone reconnected before its old socket was noticed dead
+        let stale_id = self.clients.values()
+            .find(|client| client.session_id == session_id && client.id != id)
+            .map(|client| client.id)?;
+        if self.clients[&stale_id].session_secret != secret {
+            log::warn!("🚫 Refusing to take over remote session of {} without its secret", self.clients[&stale_id].address);
+            return None;
+        }
+        if let Some(kick) = self.kickers.remove(&stale_id) {
+            kick.notify_one();
+        }
+        self.clients.remove(&stale_id)
+    }
+
+    /// Forgets sessions detached longer than `idle_window`. A lock whose
+    /// session went with them no longer has a holder and is cleared by
+    /// `expire_control`.
+    fn expire(&mut self) {
+        let idle_window = self.idle_window;
+        self.detached.retain(|_, detached| detached.detached_at.elapsed() < idle_window);
+    }
+
+    pub fn get(&self, id: u64) -> Option<&ConnectedClient> {
+        self.clients.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut ConnectedClient> {
+        self.clients.get_mut(&id)
+    }
+
+    /// Open connections, oldest first.
+    pub fn list(&self) -> Vec<ConnectedClient> {
+        let holder = self.control.as_ref().map(|lock| lock.session_id.as_str());
+        let mut clients: Vec<_> = self.clients.values()
+            .map(|client| ConnectedClient { has_control: Some(client.session_id.as_str()) == holder, ..client.clone() })
+            .collect();
+        clients.sort_by_key(|client| client.id);
+        clients
+    }
+
+    pub fn count(&self) -> usize {
+        self.clients.len()
+    }
+}
+
+// ============================================================================
+// TELEMETRY
+// ============================================================================
+
+impl ClientRegistry {
+    /// Stores a telemetry report for connection `id`, bounded to sane
+    /// values. `None` if it came too soon after the last one and was
+    /// dropped; otherwise the warnings it newly raises.
+    pub fn record_telemetry(&mut self, id: u64, mut telemetry: ClientTelemetry) -> Option<Vec<ClientWarning>> {
+        let has_control = self.holder().is_some_and(|holder| holder.id == id);
+        let client = self.clients.get_mut(&id)?;
+        // A phone going to the background or off the charger is reported
+        // almost straight away
+        let state_changed = telemetry.visible != client.telemetry.visible || telemetry.charging != client.telemetry.charging;
+        let min_interval = if state_changed { TELEMETRY_STATE_CHANGE_MIN_INTERVAL } else { TELEMETRY_MIN_INTERVAL };
+        if client.telemetry.accepted.is_some_and(|accepted| accepted.elapsed() < min_interval) {
+            return None;
+        }
+
+        telemetry.battery_level = telemetry.battery_level.filter(|level| level.is_finite()).map(|level| level.clamp(0.0, 1.0));
+        telemetry.rtt_ms = telemetry.rtt_ms.filter(|rtt| rtt.is_finite() && *rtt >= 0.0 && *rtt <= MAX_TELEMETRY_RTT_MS);
+        telemetry.received_at = Some(chrono::Utc::now().timestamp_millis());
+        telemetry.accepted = Some(Instant::now());
+
+        let previous = std::mem::replace(&mut client.telemetry, telemetry);
+        let current = &client.telemetry;
+        let mut warnings = Vec::new();
+
+        // Only on the way down, so a dying phone warns once rather than
+        // with every report
+        let low = |telemetry: &ClientTelemetry| {
+            telemetry.charging != Some(true) && telemetry.battery_level.is_some_and(|level| level < LOW_BATTERY_LEVEL)
+        };
+        if low(current) && !low(&previous) {
+            warnings.push(ClientWarning {
+                client_id: id,
+                name: label(client),
+                kind: "low_battery",
+                battery_level: current.battery_level,
+            });
+        }
+        if has_control && current.visible == Some(false) && previous.visible != Some(false) {
+            warnings.push(ClientWarning {
+                client_id: id,
+                name: label(client),
+                kind: "hidden_with_control",
+                battery_level: current.battery_level,
+            });
+        }
+        Some(warnings)
+    }
+}
+
+// ============================================================================
+// CONTROL LOCK
+// ============================================================================
+
+impl ClientRegistry {
+    /// The connected holder of the lock; `None` while its session is detached.
+    fn holder(&self) -> Option<&ConnectedClient> {
+        let lock = self.control.as_ref()?;
+        self.clients.values().find(|client| client.session_id == lock.session_id)
+    }
+
+    /// The lock's holder while its connection is down, until the grace
+    /// period runs out.
+    fn detached_holder(&self) -> Option<&ConnectedClient> {
+        let lock = self.control.as_ref()?;
+        self.detached.get(&lock.session_id)
+            .filter(|detached| detached.detached_at.elapsed() < self.control_grace)
+            .map(|detached| &detached.client)
+    }
+
+    pub fn control_holder(&self) -> Option<ControlHolder> {
+        if let Some(client) = self.holder() {
+            return Some(ControlHolder { client_id: client.id, name: label(client), detached: false });
+        }
+        self.detached_holder().map(|client| ControlHolder { client_id: client.id, name: label(client), detached: true })
+    }
+
+    /// Gives connection `id` exclusive command rights. Fails with the
+    /// holder's name if another remote has them.
+    pub fn take_control(&mut self, id: u64) -> Result<(), String> {
+        self.check_control(Some(id))?;
+        let client = self.clients.get(&id).ok_or("Not connected")?;
+
+        log::info!("🔒 Remote control taken by {}", label(client));
+        self.control = Some(ControlLock {
+            session_id: client.session_id.clone(),
+            last_active: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// False unless connection `id` held the lock.
+    pub fn release_control(&mut self, id: u64) -> bool {
+        if self.holder().map(|client| client.id) != Some(id) {
+            return false;
+        }
+        self.force_release_control()
+    }
+
+    pub fn force_release_control(&mut self) -> bool {
+        let released = self.control.take().is_some();
+        if released {
+            log::info!("🔓 Remote control released");
+        }
+        released
+    }
+
+    /// Whether a command from connection `id` (`None` for senders without a
+    /// session, like HTTP) may run; the error names the holder. Counts as
+    /// activity when it's the holder's.
+    pub fn check_control(&mut self, id: Option<u64>) -> Result<(), String> {
+        let Some(holder) = self.holder().or_else(|| self.detached_holder()) else { return Ok(()) };
+        if Some(holder.id) != id {
+            return Err(format!("Controlled by {}", label(holder)));
+        }
+
+        if let Some(lock) = self.control.as_mut() {
+            lock.last_active = Instant::now();
+        }
+        Ok(())
+    }
+
+    /// Frees the lock if its holder has been idle past the configured
+    /// timeout, or disconnected longer than the grace period. True if it did.
+    pub fn expire_control(&mut self) -> bool {
+        self.expire();
+        if self.control.is_some() && self.holder().is_none() && self.detached_holder().is_none() {
+            log::info!("🔓 Remote control released: holder disconnected");
+            self.control = None;
+            return true;
+        }
+
+        let Some(timeout) = self.control_idle else { return false };
+        let idle = self.control.as_ref().is_some_and(|lock| lock.last_active.elapsed() >= timeout);
+        if !idle {
+            return false;
+        }
+
+        log::info!("🔓 Remote control released after {} idle minute(s)", timeout.as_secs() / 60);
+        self.control = None;
+        true
+    }
+}
+
+/// Name a remote goes by in messages: its own, or its address.
+fn label(client: &ConnectedClient) -> String {
+    client.name.clone().unwrap_or_else(|| client.address.clone())
+}
+
+/// Sent on connect and in reply to every hello, only ever to the client
+/// itself; the remote keeps `session_id` and `session_secret` to present
+/// after a reconnect.
+pub fn welcome(client: &ConnectedClient, resumed: bool) -> serde_json::Value {
+    serde_json::json!({
+        "type": "welcome",
+        "client_id": client.id,
+        "session_id": client.session_id,
+        "session_secret": client.session_secret,
+        "resumed": resumed,
+        "name": client.name,
+        "role": client.role,
+        "subscriptions": client.subscriptions,
+        "scroll_rate_hz": client.scroll_rate_hz,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> ClientRegistry {
+        ClientRegistry::new(&RemoteSettings::default())
+    }
+
+    fn connect(registry: &mut ClientRegistry, name: &str) -> u64 {
+        let (id, _) = registry.connect("192.168.1.20:50000".parse().unwrap());
+        registry.hello(id, ClientHello { name: Some(name.to_string()), ..Default::default() });
+        id
+    }
+
+    #[test]
+    fn holder_has_exclusive_command_rights() {
+        let mut registry = registry();
+        let phone = connect(&mut registry, "Phone");
+        let tablet = connect(&mut registry, "Tablet");
+        assert!(registry.check_control(Some(tablet)).is_ok());
+
+        registry.take_control(phone).unwrap();
+
+        assert_eq!(registry.control_holder(), Some(ControlHolder { client_id: phone, name: "Phone".to_string(), detached: false }));
+        assert!(registry.check_control(Some(phone)).is_ok());
+        assert_eq!(registry.check_control(Some(tablet)), Err("Controlled by Phone".to_string()));
+        assert!(registry.check_control(None).is_err());
+    }
+
+    #[test]
+    fn contended_take_fails_until_released() {
+        let mut registry = registry();
+        let phone = connect(&mut registry, "Phone");
+        let tablet = connect(&mut registry, "Tablet");
+        registry.take_control(phone).unwrap();
+
+        assert_eq!(registry.take_control(tablet), Err("Controlled by Phone".to_string()));
+        assert!(!registry.release_control(tablet));
+        assert!(registry.release_control(phone));
+
+        registry.take_control(tablet).unwrap();
+        assert_eq!(registry.control_holder().map(|holder| holder.client_id), Some(tablet));
+    }
+
+    #[test]
+    fn force_release_frees_the_lock() {
+        let mut registry = registry();
+        let phone = connect(&mut registry, "Phone");
+        registry.take_control(phone).unwrap();
+
+        assert!(registry.force_release_control());
+        assert!(!registry.force_release_control());
+        assert_eq!(registry.control_holder(), None);
+    }
+
+    #[test]
+    fn disconnected_holder_keeps_the_lock_through_a_quick_reconnect() {
+        let mut registry = registry();
+        let phone = connect(&mut registry, "Phone");
+        let tablet = connect(&mut registry, "Tablet");
+        registry.take_control(phone).unwrap();
+        let session_id = registry.get(phone).unwrap().session_id.clone();
+        let session_secret = registry.get(phone).unwrap().session_secret.clone();
+
+        assert!(registry.disconnect(phone));
+        assert!(registry.control_holder().is_some_and(|holder| holder.detached));
+        assert!(registry.check_control(Some(tablet)).is_err());
+
+        let (reconnected, _) = registry.connect("192.168.1.20:50001".parse().unwrap());
+        let resumed = registry.hello(reconnected, ClientHello {
+            session_id: Some(session_id),
+            session_secret: Some(session_secret),
+            ..Default::default()
+        });
+        assert!(resumed);
+        assert_eq!(registry.control_holder(), Some(ControlHolder { client_id: reconnected, name: "Phone".to_string(), detached: false }));
+    }
+
+    #[test]
+    fn disconnected_holder_loses_the_lock_after_the_grace_period() {
+        let mut registry = registry();
+        registry.control_grace = Duration::ZERO;
+        let phone = connect(&mut registry, "Phone");
+        let tablet = connect(&mut registry, "Tablet");
+        registry.take_control(phone).unwrap();
+
+        registry.disconnect(phone);
+
+        assert_eq!(registry.control_holder(), None);
+        assert!(registry.check_control(Some(tablet)).is_ok());
+        assert!(registry.check_control(None).is_ok());
+        assert!(registry.expire_control());
+        registry.take_control(tablet).unwrap();
+    }
+
+    #[test]
+    fn alternating_visibility_stays_throttled() {
+        let mut registry = registry();
+        let phone = connect(&mut registry, "Phone");
+        let report = |visible| ClientTelemetry { visible: Some(visible), ..Default::default() };
+
+        assert!(registry.record_telemetry(phone, report(true)).is_some());
+        for visible in [false, true, false, true] {
+            assert!(registry.record_telemetry(phone, report(visible)).is_none());
+        }
+        assert_eq!(registry.get(phone).unwrap().telemetry.visible, Some(true));
+
+        registry.get_mut(phone).unwrap().telemetry.accepted = Some(Instant::now() - TELEMETRY_STATE_CHANGE_MIN_INTERVAL);
+        assert!(registry.record_telemetry(phone, report(false)).is_some());
+        assert!(registry.record_telemetry(phone, report(true)).is_none());
+    }
+
+    #[test]
+    fn last_seen_touched_outside_the_registry_shows_in_the_listing() {
+        let mut registry = registry();
+        let phone = connect(&mut registry, "Phone");
+        let last_seen = registry.get(phone).unwrap().last_seen.clone();
+
+        last_seen.touch(1_700_000_000_000);
+
+        let listed = serde_json::to_value(registry.list()).unwrap();
+        assert_eq!(listed[0]["last_seen"], 1_700_000_000_000_i64);
+    }
+}
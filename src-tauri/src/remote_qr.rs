@@ -0,0 +1,303 @@
+// remote_qr.rs - Styled SVG QR codes for the remote connection URL
+
+use std::fs;
+use std::path::Path;
+
+use base64::Engine;
+use qrcode::{Color, EcLevel, QrCode};
+use serde::Deserialize;
+
+use crate::assets;
+
+const DEFAULT_SIZE: u32 = 200;
+const QUIET_ZONE: usize = 4;
+const DEFAULT_LOGO_SCALE: f64 = 0.2;
+const MAX_LOGO_SCALE: f64 = 0.4;
+const MAX_LOGO_BYTES: usize = 2 * 1024 * 1024;
+/// Background margin around the logo, in modules.
+const LOGO_PADDING: f64 = 0.5;
+/// Share of the correctable codewords the logo may use up; the rest is left
+/// for glare, creases and low-end phone cameras.
+const LOGO_ERROR_BUDGET: f64 = 0.75;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleStyle {
+    #[default]
+    Square,
+    Rounded,
+    Dot,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCorrection {
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl From<ErrorCorrection> for EcLevel {
+    fn from(level: ErrorCorrection) -> Self {
+        match level {
+            ErrorCorrection::L => EcLevel::L,
+            ErrorCorrection::M => EcLevel::M,
+            ErrorCorrection::Q => EcLevel::Q,
+            ErrorCorrection::H => EcLevel::H,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct QrOptions {
+    /// Defaults to `h` with a logo and `m` without.
+    pub error_correction: Option<ErrorCorrection>,
+    pub module_style: ModuleStyle,
+    /// `#rgb`, `#rrggbb` or `#rrggbbaa`; black and white by default.
+    pub foreground: Option<String>,
+    pub background: Option<String>,
+    /// Minimum width and height in pixels.
+    pub size: Option<u32>,
+    pub logo: Option<QrLogo>,
+}
+
+/// A PNG for the middle of the code, given either as bytes or as an asset
+/// path (`global_assets/<file>`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct QrLogo {
+    pub bytes: Option<Vec<u8>>,
+    pub asset_path: Option<String>,
+    /// Logo width as a fraction of the code's width.
+    pub scale: Option<f64>,
+}
+
+struct Logo {
+    png: Vec<u8>,
+    /// Height over width, from the PNG header.
+    aspect: f64,
+    scale: f64,
+}
+
+/// Module block `(x0, y0, x1, y1)`, end-exclusive.
+type Area = (usize, usize, usize, usize);
+
+// ============================================================================
+// GENERATION
+// ============================================================================
+
+/// Renders `data` as an SVG. With a logo, fails rather than return a code
+/// the logo would make unscannable.
+pub fn generate_svg(app_dir: &Path, data: &str, options: &QrOptions) -> Result<String, String> {
+    let foreground = color(options.foreground.as_deref(), "#000000")?;
+    let background = color(options.background.as_deref(), "#ffffff")?;
+    if foreground.eq_ignore_ascii_case(&background) {
+        return Err("Foreground and background colors must differ".to_string());
+    }
+
+    let logo = options.logo.as_ref().map(|logo| load_logo(app_dir, logo)).transpose()?;
+    let default_level = if logo.is_some() { ErrorCorrection::H } else { ErrorCorrection::M };
+    let level = options.error_correction.unwrap_or(default_level);
+
+    let code = QrCode::with_error_correction_level(data.as_bytes(), level.into())
+        .map_err(|e| format!("Failed to generate QR code: {}", e))?;
+
+    let logo = match logo {
+        Some(logo) => {
+            let area = logo_area(&code, &logo)?;
+            Some((logo, area))
+        }
+        None => None,
+    };
+
+    Ok(render(&code, options, &foreground, &background, logo.as_ref()))
+}
+
+fn color(value: Option<&str>, default: &str) -> Result<String, String> {
+    let Some(value) = value else { return Ok(default.to_string()) };
+
+    let hex = value.strip_prefix('#').unwrap_or("");
+    let valid = matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(format!("Invalid color '{}': expected #rgb, #rrggbb or #rrggbbaa", value));
+    }
+    Ok(value.to_string())
+}
+
+fn load_logo(app_dir: &Path, logo: &QrLogo) -> Result<Logo, String> {
+    let png = match (&logo.bytes, &logo.asset_path) {
+        (Some(bytes), None) => bytes.clone(),
+        (None, Some(asset_path)) => {
+            let (_, path) = assets::resolve_asset(app_dir, asset_path).map_err(|e| e.to_string())?;
+            fs::read(&path).map_err(|e| format!("Failed to read logo '{}': {}", asset_path, e))?
+        }
+        _ => return Err("Logo needs exactly one of bytes or asset_path".to_string()),
+    };
+
+    if png.len() > MAX_LOGO_BYTES {
+        return Err(format!("Logo is too large ({} bytes, limit {})", png.len(), MAX_LOGO_BYTES));
+    }
+    // Signature, then the IHDR chunk: length, type, width, height
+    if png.len() < 24 || !png.starts_with(PNG_SIGNATURE) || &png[12..16] != b"IHDR" {
+        return Err("Logo must be a PNG image".to_string());
+    }
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    let height = u32::from_be_bytes([png[20], png[21], png[22], png[23]]);
+    if width == 0 || height == 0 {
+        return Err("Logo PNG has no pixels".to_string());
+    }
+
+    let scale = logo.scale.unwrap_or(DEFAULT_LOGO_SCALE);
+    if !(scale > 0.0 && scale <= MAX_LOGO_SCALE) {
+        return Err(format!("Logo scale must be between 0 and {}", MAX_LOGO_SCALE));
+    }
+
+    Ok(Logo { png, aspect: height as f64 / width as f64, scale })
+}
+
+/// The block of modules the padded logo hides, after checking the code
+/// survives losing them. Hidden modules are assumed to damage one codeword
+/// per four (codewords are 2x4 blocks that rarely line up with the logo's
+/// edges), against a share of what the EC level corrects.
+fn logo_area(code: &QrCode, logo: &Logo) -> Result<Area, String> {
+    let width = code.width();
+    let logo_width = width as f64 * logo.scale + 2.0 * LOGO_PADDING;
+    let logo_height = width as f64 * logo.scale * logo.aspect + 2.0 * LOGO_PADDING;
+    let center = width as f64 / 2.0;
+
+    let x0 = (center - logo_width / 2.0).floor().max(0.0) as usize;
+    let x1 = ((center + logo_width / 2.0).ceil() as usize).min(width);
+    let y0 = (center - logo_height / 2.0).floor().max(0.0) as usize;
+    let y1 = ((center + logo_height / 2.0).ceil() as usize).min(width);
+
+    let mut hidden_data_modules = 0usize;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            if code.is_functional(x, y) {
+                return Err(
+                    "The logo would cover a QR alignment or timing pattern; use a smaller logo or a shorter URL".to_string(),
+                );
+            }
+            hidden_data_modules += 1;
+        }
+    }
+
+    let damaged_codewords = hidden_data_modules.div_ceil(4);
+    let budget = (code.max_allowed_errors() as f64 * LOGO_ERROR_BUDGET).floor() as usize;
+    if damaged_codewords > budget {
+        return Err(format!(
+            "The logo hides too much of the code for error correction level {:?} (about {} damaged codewords, {} tolerated); use a smaller logo or a higher level",
+            code.error_correction_level(),
+            damaged_codewords,
+            budget
+        ));
+    }
+
+    Ok((x0, y0, x1, y1))
+}
+
+// ============================================================================
+// SVG
+// ============================================================================
+
+fn render(
+    code: &QrCode,
+    options: &QrOptions,
+    foreground: &str,
+    background: &str,
+    logo: Option<&(Logo, Area)>,
+) -> String {
+    let width = code.width();
+    let total = width + 2 * QUIET_ZONE;
+    let size = options.size.unwrap_or(DEFAULT_SIZE).max(total as u32);
+    let colors = code.to_colors();
+    let style = options.module_style;
+
+    let mut svg = format!(
+        "<?xml version=\"1.0\" standalone=\"yes\"?>\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {total} {total}\">\
+         <rect width=\"{total}\" height=\"{total}\" fill=\"{background}\"/>\
+         <g fill=\"{foreground}\" transform=\"translate({QUIET_ZONE} {QUIET_ZONE})\">"
+    );
+
+    for y in 0..width {
+        for x in 0..width {
+            let hidden_here = logo.is_some_and(|(_, (x0, y0, x1, y1))| (*x0..*x1).contains(&x) && (*y0..*y1).contains(&y));
+            let in_finder = style != ModuleStyle::Square && finder_origin(x, y, width).is_some();
+            if colors[y * width + x] != Color::Dark || hidden_here || in_finder {
+                continue;
+            }
+
+            svg.push_str(&match style {
+                ModuleStyle::Square => format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\"/>", x, y),
+                ModuleStyle::Rounded => format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" rx=\"0.3\"/>", x, y),
+                ModuleStyle::Dot => format!("<circle cx=\"{}.5\" cy=\"{}.5\" r=\"0.45\"/>", x, y),
+            });
+        }
+    }
+
+    // Finder patterns stay solid shapes in the styled variants; scanners
+    // find a code by them, so they mustn't break up into dots
+    if style != ModuleStyle::Square {
+        for (ox, oy) in [(0, 0), (width - 7, 0), (0, width - 7)] {
+            svg.push_str(&format!(
+                "<path fill-rule=\"evenodd\" d=\"{} {}\"/><rect x=\"{}\" y=\"{}\" width=\"3\" height=\"3\" rx=\"1\"/>",
+                rounded_rect(ox as f64, oy as f64, 7.0, 2.0),
+                rounded_rect(ox as f64 + 1.0, oy as f64 + 1.0, 5.0, 1.5),
+                ox + 2,
+                oy + 2
+            ));
+        }
+    }
+
+    if let Some((logo, (x0, y0, x1, y1))) = logo {
+        let logo_width = width as f64 * logo.scale;
+        let logo_height = logo_width * logo.aspect;
+        let center = width as f64 / 2.0;
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"1\" fill=\"{}\"/>\
+             <image x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" preserveAspectRatio=\"xMidYMid meet\" href=\"data:image/png;base64,{}\"/>",
+            x0,
+            y0,
+            x1 - x0,
+            y1 - y0,
+            background,
+            center - logo_width / 2.0,
+            center - logo_height / 2.0,
+            logo_width,
+            logo_height,
+            base64::engine::general_purpose::STANDARD.encode(&logo.png)
+        ));
+    }
+
+    svg.push_str("</g></svg>");
+    svg
+}
+
+/// Top-left corner of the finder pattern containing the module, if any.
+fn finder_origin(x: usize, y: usize, width: usize) -> Option<(usize, usize)> {
+    [(0, 0), (width - 7, 0), (0, width - 7)]
+        .into_iter()
+        .find(|&(ox, oy)| (ox..ox + 7).contains(&x) && (oy..oy + 7).contains(&y))
+}
+
+fn rounded_rect(x: f64, y: f64, side: f64, radius: f64) -> String {
+    let straight = side - 2.0 * radius;
+    format!(
+        "M{},{}h{}a{r},{r} 0 0 1 {r},{r}v{}a{r},{r} 0 0 1 -{r},{r}h-{}a{r},{r} 0 0 1 -{r},-{r}v-{}a{r},{r} 0 0 1 {r},-{r}z",
+        x + radius,
+        y,
+        straight,
+        straight,
+        straight,
+        straight,
+        r = radius
+    )
+}
@@ -4,19 +4,43 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio_tungstenite::{accept_async, tungstenite::Message};
 use futures_util::{StreamExt, SinkExt};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 // ✅ ADD THESE AXUM IMPORTS AT THE TOP
 use axum::{
     routing::{get, post},
     Router,
-    extract::{State, Multipart, DefaultBodyLimit},
-    response::Json,
+    body::Bytes,
+    extract::{ConnectInfo, State, Multipart, DefaultBodyLimit, Query},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json},
 };
+use sha2::{Digest, Sha256};
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+use crate::rate_limit::RateLimiter;
+use crate::remote_clients::{self, ClientHello, ClientRegistry, ClientTelemetry, ConnectedClient, ControlHolder};
+use crate::remote_sessions::SessionStats;
+
+/// How often each WebSocket client is pinged; the pong gives its round-trip time.
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+/// How often an idle or abandoned control lock is looked for.
+const CONTROL_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// `/command` requests per second per address, and the burst allowed.
+const COMMAND_RATE: (f64, f64) = (10.0, 20.0);
+/// `/status-sync` fires several times a second while playing, so it gets
+/// its own, larger allowance.
+const STATUS_SYNC_RATE: (f64, f64) = (30.0, 60.0);
+const MAX_STATUS_SPEED: f64 = 10.0;
+const MAX_PROJECT_NAME_LEN: usize = 256;
 
 // ============================================================================
 // DATA STRUCTURES
@@ -31,6 +55,25 @@ pub struct RemoteCommand {
     pub timestamp: i64,
 }
 
+/// Where a command came from, for the `remote_audit` log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandOrigin {
+    Websocket,
+    Http,
+    Replay,
+}
+
+impl std::fmt::Display for CommandOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CommandOrigin::Websocket => "websocket",
+            CommandOrigin::Http => "http",
+            CommandOrigin::Replay => "replay",
+        })
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum IncomingMessage {
@@ -38,6 +81,14 @@ pub enum IncomingMessage {
     BrowserRegister,
     #[serde(rename = "status-sync")]
     StatusSync { status: RemoteStatus },
+    /// First leg of an NTP-style exchange; see `TimeSync`.
+    #[serde(rename = "time-sync")]
+    TimeSync { client_time: f64 },
+    #[serde(rename = "client-hello")]
+    ClientHello(ClientHello),
+    /// Battery, visibility and measured latency, every few seconds.
+    #[serde(rename = "client-telemetry")]
+    ClientTelemetry(ClientTelemetry),
     #[serde(other)]
     Other,
 }
@@ -52,17 +103,112 @@ pub struct RemoteStatus {
     pub timestamp: i64,
     pub connected_clients: usize,
     pub is_live: bool,
+    /// Commands are checked and echoed back instead of run. Server-side,
+    /// like `connected_clients`; off after every server start.
+    #[serde(default)]
+    pub test_mode: bool,
+    /// Remote with exclusive command rights, if any. Server-side too.
+    #[serde(default)]
+    pub control_holder: Option<ControlHolder>,
 }
 
-#[derive(Debug, Clone)]
+/// Reply to a time sync, all in epoch milliseconds (fractional). With the
+/// client's own receive time `t3`, offset is
+/// `((server_receive - client_time) + (server_send - t3)) / 2` and the
+/// round trip `(t3 - client_time) - (server_send - server_receive)`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeSync {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub client_time: Option<f64>,
+    pub server_receive: f64,
+    pub server_send: f64,
+}
+
+/// Remote server preferences, persisted in the app settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteSettings {
+    /// How long a dropped remote's session (name, role, subscriptions)
+    /// waits to be resumed before it's forgotten.
+    pub session_idle_secs: u64,
+    /// The control lock frees itself after this many minutes without a
+    /// command from its holder; 0 never.
+    pub control_idle_minutes: u64,
+    /// Bearer token for endpoints that drive the host, like `/status-sync`.
+    /// Generated on the first server start.
+    pub controller_token: Option<String>,
+}
+
+impl Default for RemoteSettings {
+    fn default() -> Self {
+        Self {
+            session_idle_secs: 120,
+            control_idle_minutes: 10,
+            controller_token: None,
+        }
+    }
+}
+
+impl RemoteSettings {
+    pub fn session_idle_window(&self) -> Duration {
+        Duration::from_secs(self.session_idle_secs.clamp(5, 24 * 60 * 60))
+    }
+
+    pub fn control_idle_timeout(&self) -> Option<Duration> {
+        (self.control_idle_minutes > 0).then(|| Duration::from_secs(self.control_idle_minutes.min(24 * 60) * 60))
+    }
+
+    /// Fills in a random controller token if there's none; true if it did.
+    pub fn ensure_controller_token(&mut self) -> bool {
+        if self.controller_token.as_deref().is_some_and(|token| !token.trim().is_empty()) {
+            return false;
+        }
+        self.controller_token = Some(uuid::Uuid::new_v4().simple().to_string());
+        true
+    }
+}
+
+#[derive(Debug)]
 pub struct ServerState {
     pub status: RemoteStatus,
     pub app_handle: AppHandle,
     pub broadcast_tx: tokio::sync::broadcast::Sender<String>,
+    pub stats: SessionStats,
+    pub clients: ClientRegistry,
+    /// Latest prompter scroll position, sampled by each connection
+    /// subscribed to the scroll topic. Kept off the status broadcast so
+    /// 60 Hz updates don't drag every other subscriber along.
+    pub scroll_tx: Arc<tokio::sync::watch::Sender<f64>>,
+    /// Empty means no token is configured and token-only endpoints refuse
+    /// every request.
+    pub controller_token: String,
+    command_limit: RateLimiter,
+    status_sync_limit: RateLimiter,
 }
 
 pub type SharedState = Arc<RwLock<ServerState>>;
 
+impl ServerState {
+    /// `status` with the fields only the server knows (client count, test
+    /// mode, control holder) put back over the sender's.
+    fn with_server_fields(&self, mut status: RemoteStatus) -> RemoteStatus {
+        status.connected_clients = self.clients.count();
+        status.test_mode = self.status.test_mode;
+        status.control_holder = self.clients.control_holder();
+        status
+    }
+
+    /// Brings the server-side fields up to date and broadcasts the status.
+    fn publish_status(&mut self) -> RemoteStatus {
+        self.status = self.with_server_fields(self.status.clone());
+        if let Ok(json) = serde_json::to_string(&self.status) {
+            let _ = self.broadcast_tx.send(json);
+        }
+        self.status.clone()
+    }
+}
+
 // ============================================================================
 // WEBSOCKET SERVER
 // ============================================================================
@@ -73,7 +219,7 @@ pub struct RemoteServer {
 }
 
 impl RemoteServer {
-    pub fn new(app_handle: AppHandle, port: u16) -> Self {
+    pub fn new(app_handle: AppHandle, port: u16, settings: &RemoteSettings) -> Self {
         let initial_status = RemoteStatus {
             is_playing: false,
             current_speed: 1.0,
@@ -83,6 +229,8 @@ impl RemoteServer {
             timestamp: chrono::Utc::now().timestamp_millis(),
             connected_clients: 0,
             is_live: false,
+            test_mode: false,
+            control_holder: None,
         };
 
         let (broadcast_tx, _) = tokio::sync::broadcast::channel(128);
@@ -91,6 +239,12 @@ impl RemoteServer {
             status: initial_status,
             app_handle: app_handle.clone(),
             broadcast_tx,
+            stats: SessionStats::new(),
+            clients: ClientRegistry::new(settings),
+            scroll_tx: Arc::new(tokio::sync::watch::channel(0.0).0),
+            controller_token: settings.controller_token.clone().unwrap_or_default(),
+            command_limit: RateLimiter::new(COMMAND_RATE.0, COMMAND_RATE.1),
+            status_sync_limit: RateLimiter::new(STATUS_SYNC_RATE.0, STATUS_SYNC_RATE.1),
         }));
 
         Self {
@@ -114,25 +268,54 @@ impl RemoteServer {
         log::info!("🚀 WebSocket remote control server listening on port {}", self.port);
 
         let state = self.state.clone();
+        // Owned by this loop so that aborting the server task also drops
+        // every connection it accepted
+        let mut connections = tokio::task::JoinSet::new();
+        let mut control_check = tokio::time::interval(CONTROL_CHECK_INTERVAL);
         
         loop {
-            match listener.accept().await {
+            while connections.try_join_next().is_some() {}
+
+            let accepted = tokio::select! {
+                accepted = listener.accept() => accepted,
+                _ = control_check.tick() => {
+                    let mut state_guard = state.write().await;
+                    if state_guard.clients.expire_control() {
+                        state_guard.publish_status();
+                    }
+                    continue;
+                }
+            };
+
+            match accepted {
                 Ok((stream, peer_addr)) => {
                     log::info!("📱 New remote connection from: {}", peer_addr);
                     
-                    {
+                    let (client_id, kick) = {
                         let mut state_guard = state.write().await;
-                        state_guard.status.connected_clients += 1;
-                    }
+                        let (client_id, kick) = state_guard.clients.connect(peer_addr);
+                        let connected = state_guard.clients.count();
+                        state_guard.status.connected_clients = connected;
+                        state_guard.stats.record_connect(connected);
+                        (client_id, kick)
+                    };
                     
                     let state_clone = state.clone();
-                    tokio::spawn(async move {
-                        if let Err(e) = Self::handle_connection(stream, state_clone.clone(), peer_addr).await {
+                    connections.spawn(async move {
+                        if let Err(e) = Self::handle_connection(stream, state_clone.clone(), peer_addr, client_id, kick).await {
                             log::error!("❌ Error handling remote connection from {}: {}", peer_addr, e);
                         }
                         
+                        // Already gone if a reconnect resumed its session
                         let mut state_guard = state_clone.write().await;
-                        state_guard.status.connected_clients = state_guard.status.connected_clients.saturating_sub(1);
+                        let holder = state_guard.clients.control_holder();
+                        if state_guard.clients.disconnect(client_id) {
+                            state_guard.stats.record_disconnect();
+                        }
+                        state_guard.status.connected_clients = state_guard.clients.count();
+                        if state_guard.clients.control_holder() != holder {
+                            state_guard.publish_status();
+                        }
                         log::info!("📱 Remote disconnected: {} (active connections: {})", peer_addr, state_guard.status.connected_clients);
                     });
                 }
@@ -147,6 +330,8 @@ impl RemoteServer {
         stream: TcpStream,
         state: SharedState,
         peer_addr: SocketAddr,
+        client_id: u64,
+        kick: Arc<tokio::sync::Notify>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ws_stream = accept_async(stream).await
             .map_err(|e| format!("WebSocket handshake failed: {}", e))?;
@@ -155,45 +340,100 @@ impl RemoteServer {
         let (tx, mut rx_local) = tokio::sync::mpsc::unbounded_channel::<Message>();
 
         // Subscribe to status updates
-        let mut rx_broadcast = {
+        let (mut rx_broadcast, mut rx_scroll, bytes_sent, last_seen) = {
             let state_guard = state.read().await;
-            state_guard.broadcast_tx.subscribe()
+            let last_seen = state_guard.clients.get(client_id).map(|client| client.last_seen.clone()).unwrap_or_default();
+            (state_guard.broadcast_tx.subscribe(), state_guard.scroll_tx.subscribe(), state_guard.stats.bytes_counter(), last_seen)
         };
+        // Scroll frame rate, or `None` while not subscribed to scroll frames
+        let (scroll_rate_tx, mut scroll_rate_rx) = tokio::sync::watch::channel::<Option<u32>>(None);
 
         // Dedicated task to push all updates to this specific client
         let peer_addr_clone = peer_addr.clone();
         let writer_task = tokio::spawn(async move {
+            let mut keepalive = tokio::time::interval(PING_INTERVAL);
+            let mut scroll_sampler: Option<tokio::time::Interval> = None;
             loop {
                 tokio::select! {
+                    Ok(()) = scroll_rate_rx.changed() => {
+                        scroll_sampler = scroll_rate_rx.borrow_and_update().map(|hz| {
+                            let mut sampler = tokio::time::interval(Duration::from_secs(1) / hz);
+                            // A client too slow for its rate just gets fewer samples
+                            sampler.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                            sampler
+                        });
+                    }
+                    // Latest position only, and only when it moved
+                    _ = next_tick(&mut scroll_sampler) => {
+                        if !rx_scroll.has_changed().unwrap_or(false) {
+                            continue;
+                        }
+                        let frame = format!("{{\"type\":\"scroll\",\"p\":{:.4}}}", *rx_scroll.borrow_and_update());
+                        let len = frame.len() as u64;
+                        if let Err(e) = write_half.send(Message::Text(frame)).await {
+                            log::warn!("Failed to send scroll position to {}: {}", peer_addr_clone, e);
+                            break;
+                        }
+                        bytes_sent.fetch_add(len, Ordering::Relaxed);
+                    }
+                    // Keepalive carrying its send time, echoed back in the pong
+                    _ = keepalive.tick() => {
+                        let sent_at = chrono::Utc::now().timestamp_micros().to_be_bytes().to_vec();
+                        if let Err(e) = write_half.send(Message::Ping(sent_at)).await {
+                            log::warn!("Failed to ping {}: {}", peer_addr_clone, e);
+                            break;
+                        }
+                    }
                     // Individual messages (initial status, ping/pong, etc)
                     Some(msg) = rx_local.recv() => {
+                        let len = msg.len() as u64;
                         if let Err(e) = write_half.send(msg).await {
                             log::warn!("Failed to send individual message to {}: {}", peer_addr_clone, e);
                             break;
                         }
+                        bytes_sent.fetch_add(len, Ordering::Relaxed);
                     }
                     // Broadcast updates
                     Ok(json) = rx_broadcast.recv() => {
+                        let len = json.len() as u64;
                         if let Err(e) = write_half.send(Message::Text(json)).await {
                             log::warn!("Failed to push broadcast update to {}: {}", peer_addr_clone, e);
                             break;
                         }
+                        bytes_sent.fetch_add(len, Ordering::Relaxed);
                     }
                     else => break,
                 }
             }
         });
 
-        // Send initial status immediately
+        // Welcome with the session id to resume with, then the initial status
         {
             let state_guard = state.read().await;
+            if let Some(client) = state_guard.clients.get(client_id) {
+                let _ = tx.send(Message::Text(remote_clients::welcome(client, false).to_string()));
+            }
             let status = state_guard.status.clone();
             if let Ok(json) = serde_json::to_string(&status) {
                 let _ = tx.send(Message::Text(json));
             }
         }
 
-        while let Some(msg) = read_half.next().await {
+        loop {
+            let msg = tokio::select! {
+                msg = read_half.next() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+                // Its session was resumed on a newer connection
+                _ = kick.notified() => {
+                    log::info!("🔁 Remote {} replaced by its reconnected session", peer_addr);
+                    break;
+                }
+            };
+            let received_at = precise_now_ms();
+            last_seen.touch(received_at as i64);
+
             match msg {
                 Ok(Message::Text(text)) => {
                     log::debug!("Received message from {}: {}", peer_addr, text);
@@ -201,6 +441,42 @@ impl RemoteServer {
                     // First try to parse as a special sync/register message
                     if let Ok(incoming) = serde_json::from_str::<IncomingMessage>(&text) {
                         match incoming {
+                            IncomingMessage::TimeSync { client_time } => {
+                                let reply = time_sync(Some(client_time), received_at);
+                                if let Ok(json) = serde_json::to_string(&reply) {
+                                    let _ = tx.send(Message::Text(json));
+                                }
+                                continue;
+                            }
+                            IncomingMessage::ClientHello(hello) => {
+                                let mut state_guard = state.write().await;
+                                let resumed = state_guard.clients.hello(client_id, hello);
+                                if resumed {
+                                    state_guard.status.connected_clients = state_guard.clients.count();
+                                    state_guard.stats.record_resume();
+                                }
+                                if let Some(client) = state_guard.clients.get(client_id) {
+                                    if resumed {
+                                        log::info!("🔁 Remote {} resumed session {}", peer_addr, client.session_id);
+                                    }
+                                    scroll_rate_tx.send_if_modified(|rate| {
+                                        let changed = *rate != client.scroll_rate();
+                                        *rate = client.scroll_rate();
+                                        changed
+                                    });
+                                    let _ = tx.send(Message::Text(remote_clients::welcome(client, resumed).to_string()));
+                                }
+                                continue;
+                            }
+                            IncomingMessage::ClientTelemetry(telemetry) => {
+                                let mut state_guard = state.write().await;
+                                let warnings = state_guard.clients.record_telemetry(client_id, telemetry).unwrap_or_default();
+                                for warning in warnings {
+                                    log::warn!("⚠️ Remote {} warning: {}", warning.name, warning.kind);
+                                    let _ = state_guard.app_handle.emit("remote-client-warning", &warning);
+                                }
+                                continue;
+                            }
                             IncomingMessage::BrowserRegister => {
                                 log::info!("🖥️ Browser Host registered via WebSocket: {}", peer_addr);
                                 continue;
@@ -208,10 +484,8 @@ impl RemoteServer {
                             IncomingMessage::StatusSync { status } => {
                                 // Update internal state from browser sync
                                 let mut state_guard = state.write().await;
-                                let server_client_count = state_guard.status.connected_clients;
-                                state_guard.status = status;
-                                // Preserve the accurate server-side client count
-                                state_guard.status.connected_clients = server_client_count;
+                                // Preserve the accurate server-side fields
+                                state_guard.status = state_guard.with_server_fields(status);
                                 continue;
                             }
                             IncomingMessage::Other => {}
@@ -221,11 +495,31 @@ impl RemoteServer {
                     // Otherwise, try to parse as a command
                     match serde_json::from_str::<RemoteCommand>(&text) {
                         Ok(command) => {
-                            let app_handle = {
-                                let state_guard = state.read().await;
-                                state_guard.app_handle.clone()
+                            if let Some(reply) = Self::handle_control_command(&state, client_id, &command.command_type).await {
+                                let _ = tx.send(Message::Text(reply.to_string()));
+                                continue;
+                            }
+
+                            let (app_handle, test_mode, allowed) = {
+                                let mut state_guard = state.write().await;
+                                let allowed = state_guard.clients.check_control(Some(client_id));
+                                (state_guard.app_handle.clone(), state_guard.status.test_mode, allowed)
                             };
-                            Self::handle_command(command, &app_handle).await;
+                            if let Err(e) = allowed {
+                                log::info!(target: "remote_audit", "🔒 Refused remote command {} from {}: {}", command.command_type, peer_addr, e);
+                                let _ = tx.send(Message::Text(error_frame("control_locked", &e).to_string()));
+                                continue;
+                            }
+                            let command_type = command.command_type.clone();
+                            match Self::handle_command(command, &app_handle, CommandOrigin::Websocket, test_mode).await {
+                                CommandOutcome::Executed => state.write().await.stats.record_command(&command_type),
+                                CommandOutcome::Tested(report) => {
+                                    if let Ok(json) = serde_json::to_string(&report) {
+                                        let _ = tx.send(Message::Text(json));
+                                    }
+                                }
+                                CommandOutcome::Rejected => {}
+                            }
                             
                             // Send back current status for immediate feedback
                             let state_guard = state.read().await;
@@ -242,6 +536,14 @@ impl RemoteServer {
                 Ok(Message::Ping(data)) => {
                     let _ = tx.send(Message::Pong(data));
                 }
+                Ok(Message::Pong(data)) => {
+                    if let Ok(sent_at) = <[u8; 8]>::try_from(data.as_slice()) {
+                        let ping_ms = (chrono::Utc::now().timestamp_micros() - i64::from_be_bytes(sent_at)) as f64 / 1000.0;
+                        if let Some(client) = state.write().await.clients.get_mut(client_id) {
+                            client.ping_ms = Some(ping_ms);
+                        }
+                    }
+                }
                 Ok(Message::Close(_)) => {
                     log::info!("🔌 Remote connection closed by client: {}", peer_addr);
                     break;
@@ -260,54 +562,200 @@ impl RemoteServer {
         Ok(())
     }
 
-    async fn handle_command(command: RemoteCommand, app_handle: &AppHandle) {
-        log::info!("🎮 Executing remote command: {}", command.command_type);
+    /// `take_control` and `release_control`, which need the sender's
+    /// session; `None` for any other command. The reply says whether the
+    /// sender now holds the lock, or names who does.
+    async fn handle_control_command(state: &SharedState, client_id: u64, command_type: &str) -> Option<serde_json::Value> {
+        let take = match command_type {
+            "take_control" => true,
+            "release_control" => false,
+            _ => return None,
+        };
+
+        let mut state_guard = state.write().await;
+        let holder = state_guard.clients.control_holder();
+        let result = if take {
+            state_guard.clients.take_control(client_id)
+        } else {
+            state_guard.clients.release_control(client_id);
+            Ok(())
+        };
+        if state_guard.clients.control_holder() != holder {
+            state_guard.publish_status();
+        }
+
+        Some(match result {
+            Ok(()) => serde_json::json!({ "type": "control", "has_control": take }),
+            Err(e) => error_frame("control_locked", &e),
+        })
+    }
+
+    /// Runs a command, or in test mode only plans it and reports what would
+    /// have happened. Every command is logged to the `remote_audit` target
+    /// with its origin, and executed ones other than replays go to any
+    /// command recording.
+    pub(crate) async fn handle_command(
+        command: RemoteCommand,
+        app_handle: &AppHandle,
+        origin: CommandOrigin,
+        test_mode: bool,
+    ) -> CommandOutcome {
+        log::info!(
+            target: "remote_audit",
+            "🎮 {} remote command: {} (origin: {})",
+            if test_mode { "Testing" } else { "Executing" },
+            command.command_type,
+            origin
+        );
         
-        let result = match command.command_type.as_str() {
-            "play" => app_handle.emit("remote-play", ()),
-            "pause" => app_handle.emit("remote-pause", ()),
-            "stop" => app_handle.emit("remote-stop", ()),
-            "next_segment" => app_handle.emit("remote-next-segment", ()),
-            "prev_segment" => app_handle.emit("remote-prev-segment", ()),
-            "set_speed" => {
-                if let Some(value) = command.value {
-                    if let Some(speed) = value.as_f64() {
-                        let clamped_speed = speed.max(0.5).min(2.0);
-                        app_handle.emit("remote-set-speed", clamped_speed)
-                    } else {
-                        log::warn!("Invalid speed value: {:?}", value);
-                        return;
-                    }
-                } else {
-                    log::warn!("Missing speed value for set_speed command");
-                    return;
-                }
+        // Keep the talent's display awake for as long as the remote has it playing
+        let keep_awake = match command.command_type.as_str() {
+            "play" => Some(true),
+            "pause" | "stop" => Some(false),
+            _ => None,
+        };
+        let action = plan_command(&command);
+
+        if test_mode {
+            return CommandOutcome::Tested(CommandReport::new(&command, origin, keep_awake, &action));
+        }
+
+        let action = match action {
+            Ok(action) => action,
+            Err(e) => {
+                log::warn!("⚠️ {}", e);
+                return CommandOutcome::Rejected;
             }
-            "toggle_mirror" => app_handle.emit("remote-toggle-mirror", ()),
-            "reset_position" => app_handle.emit("remote-reset-position", ()),
-            "go_live" => app_handle.emit("remote-go-live", ()),
-            "exit_live" => app_handle.emit("remote-exit-live", ()),
-            "seek" => {
-                if let Some(value) = command.value {
-                    if let Some(position) = value.as_f64() {
-                        app_handle.emit("remote-seek", position)
-                    } else {
-                        log::warn!("Invalid seek position: {:?}", value);
-                        return;
-                    }
-                } else {
-                    log::warn!("Missing position value for seek command");
-                    return;
-                }
+        };
+
+        if let Some(keep_awake) = keep_awake {
+            if let Err(e) = app_handle.state::<crate::AppState>().sleep_guard.set(keep_awake).await {
+                log::warn!("Failed to update sleep prevention: {}", e);
             }
-            _ => {
-                log::warn!("⚠️ Unknown remote command: {}", command.command_type);
-                return;
+        }
+
+        let result = match action {
+            CommandAction::Emit { event, payload } => app_handle.emit(event, payload),
+            CommandAction::ReleaseClickThrough => {
+                crate::window_overlay::release_click_through(app_handle);
+                Ok(())
             }
         };
 
         if let Err(e) = result {
             log::error!("Failed to emit event for command {}: {}", command.command_type, e);
+            return CommandOutcome::Rejected;
+        }
+        if origin != CommandOrigin::Replay {
+            app_handle.state::<crate::AppState>().command_recorder.record(&command.command_type, command.value.as_ref());
+        }
+        CommandOutcome::Executed
+    }
+}
+
+fn error_frame(code: &str, message: &str) -> serde_json::Value {
+    serde_json::json!({ "type": "error", "code": code, "message": message })
+}
+
+async fn next_tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// What a command amounts to once parsed, validated and clamped.
+enum CommandAction {
+    Emit { event: &'static str, payload: serde_json::Value },
+    ReleaseClickThrough,
+}
+
+fn plan_command(command: &RemoteCommand) -> Result<CommandAction, String> {
+    let emit = |event: &'static str, payload: serde_json::Value| Ok(CommandAction::Emit { event, payload });
+
+    match command.command_type.as_str() {
+        "play" => emit("remote-play", serde_json::Value::Null),
+        "pause" => emit("remote-pause", serde_json::Value::Null),
+        "stop" => emit("remote-stop", serde_json::Value::Null),
+        "next_segment" => emit("remote-next-segment", serde_json::Value::Null),
+        "prev_segment" => emit("remote-prev-segment", serde_json::Value::Null),
+        "set_speed" => match &command.value {
+            Some(value) => match value.as_f64() {
+                Some(speed) => emit("remote-set-speed", speed.clamp(0.5, 2.0).into()),
+                None => Err(format!("Invalid speed value: {:?}", value)),
+            },
+            None => Err("Missing speed value for set_speed command".to_string()),
+        },
+        "toggle_mirror" => emit("remote-toggle-mirror", serde_json::Value::Null),
+        "reset_position" => emit("remote-reset-position", serde_json::Value::Null),
+        "go_live" => emit("remote-go-live", serde_json::Value::Null),
+        "exit_live" => emit("remote-exit-live", serde_json::Value::Null),
+        "release_click_through" => Ok(CommandAction::ReleaseClickThrough),
+        "seek" => match &command.value {
+            Some(value) => match value.as_f64() {
+                Some(position) => emit("remote-seek", position.into()),
+                None => Err(format!("Invalid seek position: {:?}", value)),
+            },
+            None => Err("Missing position value for seek command".to_string()),
+        },
+        _ => Err(format!("Unknown remote command: {}", command.command_type)),
+    }
+}
+
+pub(crate) enum CommandOutcome {
+    Executed,
+    /// Unknown, invalid, or its event couldn't be emitted.
+    Rejected,
+    /// Test mode: planned but not run.
+    Tested(CommandReport),
+}
+
+impl CommandOutcome {
+    pub fn executed(&self) -> bool {
+        matches!(self, CommandOutcome::Executed)
+    }
+}
+
+/// Echoed to the sender in test mode in place of running the command.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandReport {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub command: String,
+    pub value: Option<serde_json::Value>,
+    pub origin: CommandOrigin,
+    pub valid: bool,
+    pub error: Option<String>,
+    /// Tauri event the prompter would have received, with its payload after
+    /// clamping; `release_click_through` calls the overlay directly instead.
+    pub event: Option<&'static str>,
+    pub payload: serde_json::Value,
+    pub action: Option<&'static str>,
+    /// Sleep prevention the command would have switched on or off.
+    pub keep_awake: Option<bool>,
+}
+
+impl CommandReport {
+    fn new(command: &RemoteCommand, origin: CommandOrigin, keep_awake: Option<bool>, action: &Result<CommandAction, String>) -> Self {
+        let (event, payload, action_name) = match action {
+            Ok(CommandAction::Emit { event, payload }) => (Some(*event), payload.clone(), Some("emit")),
+            Ok(CommandAction::ReleaseClickThrough) => (None, serde_json::Value::Null, Some("release_click_through")),
+            Err(_) => (None, serde_json::Value::Null, None),
+        };
+
+        Self {
+            message_type: "test-result",
+            command: command.command_type.clone(),
+            value: command.value.clone(),
+            origin,
+            valid: action.is_ok(),
+            error: action.as_ref().err().cloned(),
+            event,
+            payload,
+            action: action_name,
+            keep_awake: if action.is_ok() { keep_awake } else { None },
         }
     }
 }
@@ -327,22 +775,17 @@ impl MobileInterfaceServer {
     }
 
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let state_clone = self.state.clone();
-
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any);
-
-        let app = Router::new()
-            .route("/", get(serve_mobile_interface))
-            .route("/remote", get(serve_mobile_interface))
+        // Live state and actions: never served from a cache
+        let api = Router::new()
             .route("/status", get(serve_status))
+            .route("/time", get(serve_time))
             .route("/command", post(handle_command))
+            .route("/status-sync", post(handle_status_sync))
+            .route("/clients", get(serve_clients))
             .route("/upload", post(handle_file_upload))
-            .layer(cors)
-            .layer(DefaultBodyLimit::max(1024 * 1024 * 1024)) // 1GB Limit
-            .with_state(state_clone);
+            .layer(SetResponseHeaderLayer::overriding(header::CACHE_CONTROL, HeaderValue::from_static("no-store")));
+
+        let app = with_http_layers(page_routes().merge(api)).with_state(self.state.clone());
 
         let addr: SocketAddr = format!("0.0.0.0:{}", self.port)
             .parse()
@@ -353,24 +796,78 @@ impl MobileInterfaceServer {
         let listener = tokio::net::TcpListener::bind(addr).await
             .map_err(|e| format!("Failed to bind HTTP server to port {}: {}", self.port, e))?;
         
-        axum::serve(listener, app).await
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await
             .map_err(|e| format!("HTTP server error: {}", e).into())
     }
 }
 
+/// The mobile page itself, revalidated by ETag.
+fn page_routes<S: Clone + Send + Sync + 'static>() -> Router<S> {
+    Router::new()
+        .route("/", get(serve_mobile_interface))
+        .route("/remote", get(serve_mobile_interface))
+}
+
+fn with_http_layers<S: Clone + Send + Sync + 'static>(router: Router<S>) -> Router<S> {
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods(Any)
+        .allow_headers(Any);
+
+    router
+        // Gzip when the client accepts it; the default predicate leaves
+        // images, event streams and tiny bodies alone
+        .layer(CompressionLayer::new())
+        .layer(cors)
+        .layer(DefaultBodyLimit::max(1024 * 1024 * 1024)) // 1GB Limit
+}
+
 // ============================================================================
 // AXUM HANDLER FUNCTIONS (Must be standalone, outside impl block)
 // ============================================================================
 
-async fn serve_mobile_interface() -> impl axum::response::IntoResponse {
-    let html = include_str!("assets/mobile_remote.html").to_string();
+const MOBILE_INTERFACE_HTML: &str = include_str!("assets/mobile_remote.html");
+
+/// Weak, since the compression layer may re-encode the body.
+fn mobile_interface_etag() -> &'static str {
+    static ETAG: OnceLock<String> = OnceLock::new();
+    ETAG.get_or_init(|| {
+        let hash = Sha256::digest(MOBILE_INTERFACE_HTML.as_bytes());
+        format!("W/\"{:x}\"", hash)
+    })
+}
+
+/// Phones revalidate on every load (`no-cache`) but get a `304` without the
+/// page when it hasn't changed.
+async fn serve_mobile_interface(headers: HeaderMap) -> axum::response::Response {
+    let etag = mobile_interface_etag();
+    let cache_headers = [
+        (header::ETAG, etag),
+        (header::CACHE_CONTROL, "no-cache"),
+    ];
+
+    if etag_matches(&headers, etag) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
     (
-        [
-            (axum::http::header::CONTENT_TYPE, "text/html"),
-            (axum::http::header::CACHE_CONTROL, "no-cache, no-store, must-revalidate"),
-        ],
-        html,
-    )
+        cache_headers,
+        [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        MOBILE_INTERFACE_HTML,
+    ).into_response()
+}
+
+/// `If-None-Match` with weak comparison: any listed tag equal to `etag`
+/// ignoring the `W/` prefix, or `*`.
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    let bare = etag.trim_start_matches("W/");
+
+    if_none_match.split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == bare)
 }
 
 async fn serve_status(
@@ -382,25 +879,244 @@ async fn serve_status(
     Json(status)
 }
 
+#[derive(Debug, Deserialize)]
+struct TimeQuery {
+    client_time: Option<f64>,
+}
+
+/// HTTP flavour of the WebSocket `time-sync` exchange, for clients without a
+/// socket: `GET /time?client_time=<epoch ms>`.
+async fn serve_time(Query(query): Query<TimeQuery>) -> Json<TimeSync> {
+    Json(time_sync(query.client_time, precise_now_ms()))
+}
+
+fn time_sync(client_time: Option<f64>, server_receive: f64) -> TimeSync {
+    TimeSync {
+        message_type: "time-sync",
+        client_time,
+        server_receive,
+        server_send: precise_now_ms(),
+    }
+}
+
+/// Epoch milliseconds with microsecond precision, so LAN latencies of a
+/// millisecond or two are still measurable.
+fn precise_now_ms() -> f64 {
+    chrono::Utc::now().timestamp_micros() as f64 / 1000.0
+}
+
 async fn handle_command(
     State(state): State<SharedState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     Json(command): Json<RemoteCommand>,
-) -> Json<serde_json::Value> {
+) -> axum::response::Response {
     log::info!("📨 Received HTTP command: {}", command.command_type);
     
-    let app_handle = {
+    let (app_handle, test_mode) = {
         let state_guard = state.read().await;
-        state_guard.app_handle.clone()
+        if !state_guard.command_limit.check(peer_addr.ip()) {
+            return rate_limited().into_response();
+        }
+        // HTTP senders have no session, so can never hold the lock
+        if let Some(holder) = state_guard.clients.control_holder() {
+            return (StatusCode::LOCKED, Json(serde_json::json!({
+                "success": false,
+                "error": format!("Controlled by {}", holder.name)
+            }))).into_response();
+        }
+        (state_guard.app_handle.clone(), state_guard.status.test_mode)
     };
     
-    RemoteServer::handle_command(command.clone(), &app_handle).await;
+    let outcome = RemoteServer::handle_command(command.clone(), &app_handle, CommandOrigin::Http, test_mode).await;
+    if outcome.executed() {
+        state.write().await.stats.record_command(&command.command_type);
+    }
+    if let CommandOutcome::Tested(report) = outcome {
+        return Json(serde_json::json!({
+            "success": report.valid,
+            "message": "Test mode: command not executed",
+            "command": command.command_type,
+            "test_result": report,
+            "timestamp": chrono::Utc::now().timestamp_millis()
+        })).into_response();
+    }
     
     Json(serde_json::json!({
         "success": true,
         "message": "Command executed",
         "command": command.command_type,
         "timestamp": chrono::Utc::now().timestamp_millis()
-    }))
+    })).into_response()
+}
+
+/// `POST /status-sync` with a `RemoteStatus` body: the HTTP flavour of the
+/// WebSocket `status-sync`, for hosts that can't hold a socket open.
+/// Needs `Authorization: Bearer <controller token>`; answers with the status
+/// as broadcast, or `422` and an error per bad field.
+async fn handle_status_sync(
+    State(state): State<SharedState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> axum::response::Response {
+    {
+        let state_guard = state.read().await;
+        if let Err(rejection) = authorize_status_sync(&state_guard.status_sync_limit, &state_guard.controller_token, peer_addr, &headers) {
+            return rejection.into_response();
+        }
+    }
+
+    let status = match parse_status_sync(&body) {
+        Ok(status) => status,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let merged = update_status(state, status).await;
+    Json(serde_json::json!({ "success": true, "status": merged })).into_response()
+}
+
+/// `429` past the address's allowance, `401` without the controller token.
+fn authorize_status_sync(
+    limit: &RateLimiter,
+    controller_token: &str,
+    peer_addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<(), Rejection> {
+    if !limit.check(peer_addr.ip()) {
+        return Err(rate_limited());
+    }
+    if !bearer_matches(headers, controller_token) {
+        log::warn!("🚫 Rejected status sync from {}: bad controller token", peer_addr);
+        return Err(unauthorized());
+    }
+    Ok(())
+}
+
+/// The status in a `/status-sync` body; `400` if it isn't JSON, `422` and
+/// an error per bad field if it isn't a status.
+fn parse_status_sync(body: &[u8]) -> Result<RemoteStatus, Rejection> {
+    let mut value: serde_json::Value = match serde_json::from_slice(body) {
+        Ok(value) => value,
+        Err(e) => {
+            return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+                "success": false,
+                "error": format!("Invalid JSON: {}", e)
+            }))));
+        }
+    };
+
+    let errors = validate_status(&value);
+    if !errors.is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(serde_json::json!({
+            "success": false,
+            "error": "Invalid status",
+            "errors": errors
+        }))));
+    }
+
+    // Server-maintained or optional for the sender
+    if let Some(fields) = value.as_object_mut() {
+        fields.entry("connected_clients").or_insert(0.into());
+        fields.entry("timestamp").or_insert(chrono::Utc::now().timestamp_millis().into());
+    }
+    serde_json::from_value(value).map_err(|e| {
+        (StatusCode::UNPROCESSABLE_ENTITY, Json(serde_json::json!({
+            "success": false,
+            "error": format!("Invalid status: {}", e)
+        })))
+    })
+}
+
+/// `GET /clients`: what `get_connected_clients` returns, for host pages.
+/// Needs the controller token, as it lists every remote's address.
+async fn serve_clients(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    let state_guard = state.read().await;
+    if !bearer_matches(&headers, &state_guard.controller_token) {
+        return unauthorized().into_response();
+    }
+
+    let clients: Vec<ConnectedClient> = state_guard.clients.list();
+    Json(clients).into_response()
+}
+
+/// An error status with a `{ "success": false, ... }` body.
+type Rejection = (StatusCode, Json<serde_json::Value>);
+
+fn unauthorized() -> Rejection {
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({
+        "success": false,
+        "error": "Missing or invalid controller token"
+    })))
+}
+
+fn rate_limited() -> Rejection {
+    (StatusCode::TOO_MANY_REQUESTS, Json(serde_json::json!({
+        "success": false,
+        "error": "Too many requests"
+    })))
+}
+
+fn bearer_matches(headers: &HeaderMap, expected: &str) -> bool {
+    let token = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    !expected.is_empty() && token == Some(expected)
+}
+
+/// Field-level problems with a status body, by field name. Empty when it's
+/// fit to broadcast.
+fn validate_status(value: &serde_json::Value) -> BTreeMap<&'static str, String> {
+    let mut errors = BTreeMap::new();
+    let Some(fields) = value.as_object() else {
+        errors.insert("body", "must be a JSON object".to_string());
+        return errors;
+    };
+
+    for field in ["is_playing", "is_live"] {
+        if !fields.get(field).is_some_and(serde_json::Value::is_boolean) {
+            errors.insert(field, "must be true or false".to_string());
+        }
+    }
+
+    let speed = fields.get("current_speed").and_then(serde_json::Value::as_f64);
+    if !speed.is_some_and(|speed| speed.is_finite() && speed > 0.0 && speed <= MAX_STATUS_SPEED) {
+        errors.insert("current_speed", format!("must be a number above 0 and at most {}", MAX_STATUS_SPEED));
+    }
+
+    let total_segments = fields.get("total_segments").and_then(serde_json::Value::as_u64);
+    if total_segments.is_none() {
+        errors.insert("total_segments", "must be a whole number, 0 or more".to_string());
+    }
+
+    match fields.get("current_segment") {
+        None | Some(serde_json::Value::Null) => {}
+        Some(segment) => match (segment.as_u64(), total_segments) {
+            (None, _) => {
+                errors.insert("current_segment", "must be null or a whole number, 0 or more".to_string());
+            }
+            (Some(segment), Some(total)) if total > 0 && segment >= total => {
+                errors.insert("current_segment", "must be less than total_segments".to_string());
+            }
+            _ => {}
+        },
+    }
+
+    let name_ok = fields.get("project_name")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|name| name.chars().count() <= MAX_PROJECT_NAME_LEN);
+    if !name_ok {
+        errors.insert("project_name", format!("must be a string of at most {} characters", MAX_PROJECT_NAME_LEN));
+    }
+
+    if fields.get("timestamp").is_some_and(|timestamp| timestamp.as_i64().is_none()) {
+        errors.insert("timestamp", "must be epoch milliseconds".to_string());
+    }
+
+    errors
 }
 
 async fn handle_file_upload(
@@ -483,12 +1199,179 @@ async fn handle_file_upload(
 }
 
 // ✅ NEW HELPER FOR UPDATING STATUS FROM TAURI
-pub async fn update_status(state: SharedState, new_status: RemoteStatus) {
+/// Replaces and broadcasts the status, keeping the server's own client
+/// count, test mode and control holder over whatever the sender had.
+/// Returns the status as broadcast.
+pub async fn update_status(state: SharedState, new_status: RemoteStatus) -> RemoteStatus {
     let mut state_guard = state.write().await;
-    state_guard.status = new_status.clone();
+    state_guard.status = new_status;
     
     // Broadcast to all connected clients
-    if let Ok(json) = serde_json::to_string(&new_status) {
-        let _ = state_guard.broadcast_tx.send(json);
+    state_guard.publish_status()
+}
+
+/// Turns test mode on or off and tells every remote.
+pub async fn set_test_mode(state: SharedState, enabled: bool) -> RemoteStatus {
+    let mut state_guard = state.write().await;
+    state_guard.status.test_mode = enabled;
+    log::info!(target: "remote_audit", "🧪 Remote test mode {}", if enabled { "on" } else { "off" });
+    state_guard.publish_status()
+}
+
+/// Frees the control lock from the desktop, whoever holds it. True if it
+/// was held.
+pub async fn force_release_control(state: &SharedState) -> bool {
+    let mut state_guard = state.write().await;
+    let released = state_guard.clients.force_release_control();
+    if released {
+        log::info!(target: "remote_audit", "🔓 Remote control lock released from the desktop");
+        state_guard.publish_status();
+    }
+    released
+}
+
+/// Publishes the prompter's scroll position (0 to 1) to remotes subscribed
+/// to scroll frames. Never blocks on slow clients: each samples the latest
+/// value at its own rate.
+pub async fn set_scroll_position(state: &SharedState, position: f64) {
+    state.read().await.scroll_tx.send_replace(position);
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn page_app() -> Router {
+        with_http_layers(page_routes())
+    }
+
+    #[tokio::test]
+    async fn page_is_gzipped_when_accepted() {
+        let request = Request::get("/remote")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .body(Body::empty())
+            .unwrap();
+        let response = page_app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[header::ETAG], mobile_interface_etag());
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn page_is_not_gzipped_unless_accepted() {
+        let request = Request::get("/").body(Body::empty()).unwrap();
+        let response = page_app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+
+    #[tokio::test]
+    async fn matching_etag_gets_not_modified() {
+        let request = Request::get("/remote")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .header(header::IF_NONE_MATCH, mobile_interface_etag())
+            .body(Body::empty())
+            .unwrap();
+        let response = page_app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], mobile_interface_etag());
+    }
+
+    #[tokio::test]
+    async fn stale_etag_gets_the_page() {
+        let request = Request::get("/remote")
+            .header(header::IF_NONE_MATCH, "W/\"stale\"")
+            .body(Body::empty())
+            .unwrap();
+        let response = page_app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    const PEER: &str = "192.168.1.20:50000";
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", token)).unwrap());
+        headers
+    }
+
+    fn status_body() -> serde_json::Value {
+        serde_json::json!({
+            "is_playing": true,
+            "current_speed": 1.5,
+            "current_segment": 2,
+            "total_segments": 5,
+            "project_name": "Keynote",
+            "is_live": false
+        })
+    }
+
+    #[test]
+    fn status_sync_needs_the_controller_token() {
+        let limit = RateLimiter::new(STATUS_SYNC_RATE.0, STATUS_SYNC_RATE.1);
+        let peer = PEER.parse().unwrap();
+
+        for headers in [HeaderMap::new(), bearer("wrong")] {
+            let (status, _) = authorize_status_sync(&limit, "secret", peer, &headers).unwrap_err();
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+        // No token configured refuses everyone
+        let (status, _) = authorize_status_sync(&limit, "", peer, &bearer("")).unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        assert!(authorize_status_sync(&limit, "secret", peer, &bearer("secret")).is_ok());
+    }
+
+    #[test]
+    fn status_sync_past_the_allowance_is_rate_limited() {
+        let limit = RateLimiter::new(1.0, 2.0);
+        let peer = PEER.parse().unwrap();
+        let headers = bearer("secret");
+
+        assert!(authorize_status_sync(&limit, "secret", peer, &headers).is_ok());
+        assert!(authorize_status_sync(&limit, "secret", peer, &headers).is_ok());
+        let (status, _) = authorize_status_sync(&limit, "secret", peer, &headers).unwrap_err();
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        // Checked before the token, so bad tokens count too
+        let (status, _) = authorize_status_sync(&limit, "secret", peer, &HeaderMap::new()).unwrap_err();
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn invalid_status_sync_body_is_unprocessable() {
+        let mut body = status_body();
+        body["current_speed"] = serde_json::json!(-1);
+        body["current_segment"] = serde_json::json!(9);
+
+        let (status, Json(response)) = parse_status_sync(body.to_string().as_bytes()).unwrap_err();
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let fields: Vec<&str> = response["errors"].as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(fields, ["current_segment", "current_speed"]);
+    }
+
+    #[test]
+    fn status_sync_body_that_is_not_json_is_a_bad_request() {
+        let (status, _) = parse_status_sync(b"{not json").unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn valid_status_sync_body_gets_server_defaults() {
+        let status = parse_status_sync(status_body().to_string().as_bytes()).unwrap();
+
+        assert_eq!(status.current_segment, Some(2));
+        assert_eq!(status.connected_clients, 0);
+        assert!(status.timestamp > 0);
+        assert!(!status.test_mode);
+    }
+}
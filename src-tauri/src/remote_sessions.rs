@@ -0,0 +1,187 @@
+// remote_sessions.rs - Per-session remote server statistics and their history
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::remote_server::SharedState;
+use crate::write_json_atomic;
+
+const REMOTE_SESSIONS_FILE: &str = "remote_sessions.json";
+/// Oldest sessions are dropped once the history grows past this.
+pub const MAX_REMOTE_SESSIONS: usize = 100;
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Counters for one run of the remote server, from start to stop. All
+/// timestamps are epoch milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub started_at: i64,
+    /// `None` while the session is still running.
+    pub ended_at: Option<i64>,
+    pub connects: u64,
+    pub disconnects: u64,
+    /// Reconnects that resumed an earlier client session; these also count
+    /// in `connects`.
+    #[serde(default)]
+    pub resumes: u64,
+    pub peak_clients: usize,
+    /// When `peak_clients` was first reached.
+    pub peak_clients_at: Option<i64>,
+    pub commands_executed: u64,
+    pub commands_by_type: BTreeMap<String, u64>,
+    /// Bytes pushed to WebSocket clients (status replies and broadcasts).
+    pub bytes_sent: u64,
+    /// Shared with every connection's writer task, which count without
+    /// taking the server lock; folded into `bytes_sent` by `snapshot`.
+    #[serde(skip)]
+    bytes_counter: Arc<AtomicU64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RemoteSessionsFile {
+    /// Oldest first.
+    sessions: Vec<SessionStats>,
+}
+
+/// Serializes appends to the history file.
+#[derive(Default)]
+pub struct SessionHistory {
+    lock: Mutex<()>,
+}
+
+// ============================================================================
+// LIVE STATS
+// ============================================================================
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started_at: chrono::Utc::now().timestamp_millis(),
+            ended_at: None,
+            connects: 0,
+            disconnects: 0,
+            resumes: 0,
+            peak_clients: 0,
+            peak_clients_at: None,
+            commands_executed: 0,
+            commands_by_type: BTreeMap::new(),
+            bytes_sent: 0,
+            bytes_counter: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// `connected_clients` is the count including the new client.
+    pub fn record_connect(&mut self, connected_clients: usize) {
+        self.connects += 1;
+        if connected_clients > self.peak_clients {
+            self.peak_clients = connected_clients;
+            self.peak_clients_at = Some(chrono::Utc::now().timestamp_millis());
+        }
+    }
+
+    pub fn record_disconnect(&mut self) {
+        self.disconnects += 1;
+    }
+
+    pub fn record_resume(&mut self) {
+        self.resumes += 1;
+    }
+
+    pub fn record_command(&mut self, command_type: &str) {
+        self.commands_executed += 1;
+        *self.commands_by_type.entry(command_type.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn bytes_counter(&self) -> Arc<AtomicU64> {
+        self.bytes_counter.clone()
+    }
+
+    /// Copy with `bytes_sent` brought up to date.
+    pub fn snapshot(&self) -> SessionStats {
+        let mut stats = self.clone();
+        stats.bytes_sent = self.bytes_counter.load(Ordering::Relaxed);
+        stats
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// HISTORY
+// ============================================================================
+
+impl SessionHistory {
+    /// Closes the session in `state` and appends it to the history. Clients
+    /// still connected count as disconnected, since stopping the server
+    /// drops them.
+    pub async fn finish(&self, app_dir: &Path, state: &SharedState) -> Result<SessionStats, String> {
+        let stats = {
+            let server = state.read().await;
+            let mut stats = server.stats.snapshot();
+            stats.disconnects += server.status.connected_clients as u64;
+            stats.ended_at = Some(chrono::Utc::now().timestamp_millis());
+            stats
+        };
+
+        self.append(app_dir, &stats)?;
+        log::info!(
+            "📊 Remote session ended: {} connect(s), {} command(s), peak {} client(s)",
+            stats.connects,
+            stats.commands_executed,
+            stats.peak_clients
+        );
+        Ok(stats)
+    }
+
+    fn append(&self, app_dir: &Path, stats: &SessionStats) -> Result<(), String> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut file = load(app_dir);
+
+        file.sessions.push(stats.clone());
+        let excess = file.sessions.len().saturating_sub(MAX_REMOTE_SESSIONS);
+        file.sessions.drain(..excess);
+
+        write_json_atomic(&file_path(app_dir), &file)
+    }
+
+    /// Past sessions, most recent first.
+    pub fn list(&self, app_dir: &Path, limit: Option<usize>) -> Vec<SessionStats> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        load(app_dir).sessions
+            .into_iter()
+            .rev()
+            .take(limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+            .collect()
+    }
+}
+
+fn file_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(REMOTE_SESSIONS_FILE)
+}
+
+fn load(app_dir: &Path) -> RemoteSessionsFile {
+    let path = file_path(app_dir);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return RemoteSessionsFile::default(),
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("⚠️  Ignoring corrupt remote session history {}: {}", path.display(), e);
+        RemoteSessionsFile::default()
+    })
+}
@@ -0,0 +1,207 @@
+// run_sheet.rs - One-page rundown of segments and timings for stage managers
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::reading_time::{self, ReadingEstimate, SegmentText};
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunSheetFormat {
+    Csv,
+    Html,
+}
+
+impl RunSheetFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            RunSheetFormat::Csv => "csv",
+            RunSheetFormat::Html => "html",
+        }
+    }
+}
+
+/// One line of the sheet.
+struct Row {
+    number: usize,
+    title: String,
+    /// Words plus CJK characters, the units the timing was based on.
+    words: usize,
+    duration: String,
+    cumulative: String,
+}
+
+// ============================================================================
+// EXPORT
+// ============================================================================
+
+/// Writes the run sheet to `destination`, adding the format's extension when
+/// the path has none, and returns the path written.
+pub fn export(
+    segments: &[SegmentText],
+    wpm: Option<f64>,
+    title: Option<&str>,
+    destination: &Path,
+    format: RunSheetFormat,
+) -> Result<PathBuf, String> {
+    let estimate = reading_time::estimate(segments, wpm)?;
+    let rows = rows(&estimate);
+
+    let contents = match format {
+        RunSheetFormat::Csv => to_csv(&rows),
+        RunSheetFormat::Html => to_html(&rows, &estimate, title.unwrap_or("Run Sheet")),
+    };
+
+    let mut path = destination.to_path_buf();
+    if path.extension().is_none() {
+        path.set_extension(format.extension());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    fs::write(&path, contents).map_err(|e| format!("Failed to write run sheet '{}': {}", path.display(), e))?;
+
+    log::info!("📋 Exported run sheet ({} segments) to {}", rows.len(), path.display());
+    Ok(path)
+}
+
+fn rows(estimate: &ReadingEstimate) -> Vec<Row> {
+    let mut elapsed = 0.0;
+
+    estimate.segments.iter().enumerate().map(|(index, segment)| {
+        elapsed += segment.seconds;
+        Row {
+            number: index + 1,
+            title: segment.name.clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| format!("Segment {}", index + 1)),
+            words: segment.word_count + segment.cjk_char_count,
+            duration: segment.formatted.clone(),
+            cumulative: reading_time::format_duration(elapsed),
+        }
+    }).collect()
+}
+
+// ============================================================================
+// CSV
+// ============================================================================
+
+/// RFC 4180 with CRLF line ends, plus a BOM so Excel reads it as UTF-8.
+fn to_csv(rows: &[Row]) -> String {
+    let mut out = String::from("\u{FEFF}#,Title,Words,Duration,Cumulative\r\n");
+
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{}\r\n",
+            row.number,
+            csv_field(&row.title),
+            row.words,
+            row.duration,
+            row.cumulative
+        ));
+    }
+    out
+}
+
+/// Quotes fields containing separators, quotes or line breaks, and defuses
+/// titles a spreadsheet would run as a formula (`=`, `+`, `-`, `@`).
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// ============================================================================
+// HTML
+// ============================================================================
+
+/// Standalone page with inline styles. Titles are isolated with `dir="auto"`
+/// so right-to-left text neither flips the table nor garbles neighbouring
+/// cells.
+fn to_html(rows: &[Row], estimate: &ReadingEstimate, title: &str) -> String {
+    let body: String = rows.iter().map(|row| {
+        format!(
+            "      <tr><td class=\"num\">{}</td><td dir=\"auto\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            row.number,
+            html_escape(&row.title),
+            row.words,
+            row.duration,
+            row.cumulative
+        )
+    }).collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>{title}</title>
+  <style>
+    body {{ font-family: -apple-system, "Segoe UI", Roboto, "Noto Sans", sans-serif; margin: 2rem; color: #111; }}
+    h1 {{ font-size: 1.5rem; margin: 0 0 0.25rem; unicode-bidi: plaintext; }}
+    .meta {{ color: #555; margin: 0 0 1.5rem; }}
+    table {{ border-collapse: collapse; width: 100%; }}
+    th, td {{ border-bottom: 1px solid #ddd; padding: 0.4rem 0.6rem; text-align: start; }}
+    th {{ background: #f3f3f3; }}
+    td[dir="auto"] {{ unicode-bidi: plaintext; }}
+    .num {{ text-align: right; font-variant-numeric: tabular-nums; white-space: nowrap; direction: ltr; }}
+    tfoot td {{ font-weight: bold; border-top: 2px solid #111; border-bottom: none; }}
+    @media print {{ body {{ margin: 0; }} th {{ background: none; }} }}
+  </style>
+</head>
+<body>
+  <h1 dir="auto">{title}</h1>
+  <p class="meta">{segment_count} segments &middot; {wpm:.0} words per minute &middot; generated {generated}</p>
+  <table>
+    <thead>
+      <tr><th class="num">#</th><th>Title</th><th class="num">Words</th><th class="num">Duration</th><th class="num">Cumulative</th></tr>
+    </thead>
+    <tbody>
+{body}    </tbody>
+    <tfoot>
+      <tr><td></td><td>Total</td><td class="num">{total_words}</td><td class="num">{total}</td><td class="num">{total}</td></tr>
+    </tfoot>
+  </table>
+</body>
+</html>
+"#,
+        title = html_escape(title),
+        segment_count = rows.len(),
+        wpm = estimate.wpm,
+        generated = chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        body = body,
+        total_words = estimate.total_word_count + estimate.total_cjk_char_count,
+        total = estimate.total_formatted,
+    )
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            // Titles are single-line cells
+            '\n' | '\r' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
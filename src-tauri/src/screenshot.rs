@@ -0,0 +1,219 @@
+// screenshot.rs - PNG snapshots of what a prompter webview is showing
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::error::CommandError;
+use crate::{disk_space, fs_sandbox};
+
+// ============================================================================
+// SAVING
+// ============================================================================
+
+/// Writes a snapshot of `window` as a PNG and returns where it went. Without
+/// a destination (or when it is a directory) a timestamped name is used,
+/// defaulting to the downloads folder.
+pub async fn save_png(app_handle: &AppHandle, window: &WebviewWindow, destination: Option<&str>) -> Result<PathBuf, CommandError> {
+    let png = capture_png(window).await?;
+    let path = resolve_destination(app_handle, destination)?;
+
+    disk_space::ensure_space(&path, png.len() as u64)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+    }
+    fs::write(&path, &png)
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+
+    log::info!("📸 Saved screenshot of '{}' to {} ({} bytes)", window.label(), path.display(), png.len());
+    Ok(path)
+}
+
+fn resolve_destination(app_handle: &AppHandle, destination: Option<&str>) -> Result<PathBuf, String> {
+    let file_name = format!("SegiTelep {}.png", chrono::Local::now().format("%Y-%m-%d %H-%M-%S"));
+
+    let Some(raw) = destination else {
+        let dir = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or("Could not determine the downloads folder")?;
+        return fs_sandbox::resolve_path(app_handle, &dir.join(file_name).to_string_lossy());
+    };
+
+    let path = fs_sandbox::resolve_path(app_handle, raw)?;
+    if path.is_dir() {
+        return Ok(path.join(file_name));
+    }
+    Ok(if has_png_extension(&path) { path } else { path.with_extension("png") })
+}
+
+fn has_png_extension(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+}
+
+// ============================================================================
+// PLATFORM CAPTURE
+// ============================================================================
+
+/// Asks the webview itself to render a snapshot, which works even when the
+/// window is covered and doesn't need screen-recording permission. The
+/// native webview may only be touched on the main thread, so the work is
+/// handed over there and the PNG bytes come back through a channel.
+pub async fn capture_png(window: &WebviewWindow) -> Result<Vec<u8>, CommandError> {
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<Vec<u8>, CommandError>>();
+
+    window.with_webview(move |webview| start_capture(webview, tx))
+        .map_err(|e| format!("Failed to reach the webview: {}", e))?;
+
+    rx.await.map_err(|_| "Screenshot was dropped before it completed".to_string())?
+}
+
+type CaptureSender = tokio::sync::oneshot::Sender<Result<Vec<u8>, CommandError>>;
+
+/// WebView2 `CapturePreview` into an in-memory COM stream.
+#[cfg(target_os = "windows")]
+fn start_capture(webview: tauri::webview::PlatformWebview, tx: CaptureSender) {
+    use webview2_com::CapturePreviewCompletedHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG;
+    use windows::Win32::System::Com::{IStream, STREAM_SEEK_END, STREAM_SEEK_SET};
+    use windows::Win32::UI::Shell::SHCreateMemStream;
+
+    fn read_stream(stream: &IStream) -> windows::core::Result<Vec<u8>> {
+        // SAFETY: the stream is ours and the buffer is sized from its length.
+        unsafe {
+            let mut len = 0u64;
+            stream.Seek(0, STREAM_SEEK_END, Some(&mut len))?;
+            stream.Seek(0, STREAM_SEEK_SET, None)?;
+
+            let mut bytes = vec![0u8; len as usize];
+            let mut read = 0u32;
+            stream.Read(bytes.as_mut_ptr().cast(), bytes.len() as u32, Some(&mut read)).ok()?;
+            bytes.truncate(read as usize);
+            Ok(bytes)
+        }
+    }
+
+    // SAFETY: plain allocation of an empty stream.
+    let Some(stream) = (unsafe { SHCreateMemStream(None) }) else {
+        let _ = tx.send(Err("Failed to allocate an image stream".to_string().into()));
+        return;
+    };
+
+    let target = stream.clone();
+    let handler = CapturePreviewCompletedHandler::create(Box::new(move |result| {
+        let outcome = result
+            .and_then(|()| read_stream(&target))
+            .map_err(|e| format!("WebView2 CapturePreview failed: {}", e).into());
+        let _ = tx.send(outcome);
+        Ok(())
+    }));
+
+    // SAFETY: called on the main thread with the live WebView2 controller. If
+    // this fails the handler (and with it the sender) is dropped, which the
+    // awaiting side reports.
+    let started = unsafe {
+        webview.controller().CoreWebView2()
+            .and_then(|core| core.CapturePreview(COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG, &stream, &handler))
+    };
+    if let Err(e) = started {
+        log::error!("WebView2 CapturePreview failed: {}", e);
+    }
+}
+
+/// `-[WKWebView takeSnapshotWithConfiguration:completionHandler:]`, converted
+/// to PNG through `NSBitmapImageRep`.
+#[cfg(target_os = "macos")]
+fn start_capture(webview: tauri::webview::PlatformWebview, tx: CaptureSender) {
+    use std::cell::Cell;
+
+    use block2::RcBlock;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+
+    const NS_BITMAP_IMAGE_FILE_TYPE_PNG: usize = 4;
+
+    let pending = Cell::new(Some(tx));
+    let completion = RcBlock::new(move |image: *mut AnyObject, error: *mut AnyObject| {
+        let Some(tx) = pending.take() else { return };
+
+        // SAFETY: WebKit calls this on the main thread with a valid NSImage or NSError.
+        let outcome = unsafe {
+            if image.is_null() {
+                let description: *mut AnyObject = if error.is_null() {
+                    std::ptr::null_mut()
+                } else {
+                    msg_send![error, localizedDescription]
+                };
+                Err(format!("WKWebView snapshot failed: {}", ns_string(description)).into())
+            } else {
+                let tiff: *mut AnyObject = msg_send![image, TIFFRepresentation];
+                let rep: *mut AnyObject = msg_send![class!(NSBitmapImageRep), imageRepWithData: tiff];
+                let properties: *mut AnyObject = msg_send![class!(NSDictionary), dictionary];
+                let png: *mut AnyObject = if rep.is_null() {
+                    std::ptr::null_mut()
+                } else {
+                    msg_send![rep, representationUsingType: NS_BITMAP_IMAGE_FILE_TYPE_PNG, properties: properties]
+                };
+
+                if png.is_null() {
+                    Err("Failed to encode the snapshot as PNG".to_string().into())
+                } else {
+                    let length: usize = msg_send![png, length];
+                    let bytes: *const u8 = msg_send![png, bytes];
+                    Ok(std::slice::from_raw_parts(bytes, length).to_vec())
+                }
+            }
+        };
+        let _ = tx.send(outcome);
+    });
+
+    // SAFETY: `inner()` is this window's live WKWebView and we are on the main thread.
+    unsafe {
+        let wk_webview = &*(webview.inner() as *mut AnyObject);
+        let configuration: *mut AnyObject = std::ptr::null_mut();
+        let _: () = msg_send![wk_webview, takeSnapshotWithConfiguration: configuration, completionHandler: &*completion];
+    }
+
+    /// # Safety
+    /// `string` must be null or a valid NSString.
+    unsafe fn ns_string(string: *mut AnyObject) -> String {
+        if string.is_null() {
+            return "unknown error".to_string();
+        }
+        let utf8: *const std::ffi::c_char = msg_send![string, UTF8String];
+        std::ffi::CStr::from_ptr(utf8).to_string_lossy().into_owned()
+    }
+}
+
+/// WebKitGTK renders the snapshot itself, so unlike screen capture this
+/// works under Wayland too; if it still fails there, say so plainly since
+/// compositors don't let us fall back to grabbing the window.
+#[cfg(target_os = "linux")]
+fn start_capture(webview: tauri::webview::PlatformWebview, tx: CaptureSender) {
+    use gtk::cairo::ImageSurface;
+    use gtk::prelude::*;
+    use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+
+    let wk_webview = webview.inner();
+    let on_wayland = wk_webview.display().type_().name() == "GdkWaylandDisplay";
+
+    wk_webview.snapshot(SnapshotRegion::Visible, SnapshotOptions::NONE, None::<&gtk::gio::Cancellable>, move |result| {
+        let outcome = match result {
+            Ok(surface) => ImageSurface::try_from(surface)
+                .map_err(|_| "WebKit returned a snapshot that isn't an image".to_string().into())
+                .and_then(|image| {
+                    let mut png = Vec::new();
+                    image.write_to_png(&mut png)
+                        .map(|()| png)
+                        .map_err(|e| format!("Failed to encode the snapshot as PNG: {}", e).into())
+                }),
+            Err(e) if on_wayland => Err(CommandError::Unsupported {
+                feature: "window_screenshot".to_string(),
+                reason: format!("the Wayland session did not allow the window to be captured ({})", e),
+            }),
+            Err(e) => Err(format!("WebKit snapshot failed: {}", e).into()),
+        };
+        let _ = tx.send(outcome);
+    });
+}
@@ -0,0 +1,591 @@
+// script_import.rs - Scripts from Word, Markdown and plain text files, split into segments
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+use serde::Serialize;
+
+use crate::error::CommandError;
+use crate::text_decoding;
+
+pub const SCRIPT_EXTENSIONS: &[&str] = &["txt", "text", "md", "markdown", "docx"];
+/// Larger "scripts" are almost certainly not scripts.
+const MAX_SCRIPT_BYTES: u64 = 20 * 1024 * 1024;
+/// Uncompressed limit for the XML parts of a .docx (zip bombs).
+const MAX_DOCX_XML_BYTES: u64 = 200 * 1024 * 1024;
+/// OLE compound file header: encrypted Office documents and legacy `.doc`.
+const OLE_SIGNATURE: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptFormat {
+    Docx,
+    Markdown,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextStyle {
+    Bold,
+    Italic,
+    Underline,
+}
+
+/// Styled span of a segment body. Offsets are UTF-16 code units so they
+/// index JavaScript strings directly; `end` is exclusive.
+#[derive(Debug, Clone, Serialize)]
+pub struct StyleRange {
+    pub style: TextStyle,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptSegment {
+    /// Heading text the segment started with; `None` for text before the
+    /// first heading and for plain-text sections.
+    pub title: Option<String>,
+    /// 1 for top-level headings (Word "Title" counts as 1).
+    pub heading_level: Option<u8>,
+    /// Paragraphs separated by a blank line.
+    pub body: String,
+    pub styles: Vec<StyleRange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedScript {
+    pub format: ScriptFormat,
+    pub segments: Vec<ScriptSegment>,
+    /// Detected encoding, for plain text and Markdown.
+    pub encoding: Option<String>,
+}
+
+// ============================================================================
+// IMPORT
+// ============================================================================
+
+/// Reads `path` as a script, dispatching on its extension. Blocking; run it
+/// on a blocking thread.
+pub fn import_script(path: &Path) -> Result<ImportedScript, CommandError> {
+    let display = path.to_string_lossy().to_string();
+    let extension = path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let size = fs::metadata(path).map_err(|e| io_error(&display, e))?.len();
+    if size > MAX_SCRIPT_BYTES {
+        return Err(CommandError::FileTooLarge { path: display, size, limit: MAX_SCRIPT_BYTES });
+    }
+
+    let script = match extension.as_str() {
+        "docx" => import_docx(path, &display)?,
+        "md" | "markdown" => {
+            let decoded = read_text(path, &display)?;
+            ImportedScript {
+                format: ScriptFormat::Markdown,
+                segments: parse_markdown(&decoded.text),
+                encoding: Some(decoded.encoding),
+            }
+        }
+        "txt" | "text" => {
+            let decoded = read_text(path, &display)?;
+            ImportedScript {
+                format: ScriptFormat::Text,
+                segments: parse_plain_text(&decoded.text),
+                encoding: Some(decoded.encoding),
+            }
+        }
+        _ => {
+            return Err(CommandError::Unsupported {
+                feature: format!("Importing .{} files as scripts", extension),
+                reason: format!("supported types are {}", SCRIPT_EXTENSIONS.join(", ")),
+            })
+        }
+    };
+
+    log::info!("📜 Imported script {} ({} segments)", display, script.segments.len());
+    Ok(script)
+}
+
+fn read_text(path: &Path, display: &str) -> Result<text_decoding::DecodedText, CommandError> {
+    let bytes = fs::read(path).map_err(|e| io_error(display, e))?;
+    text_decoding::decode_text(&bytes).ok_or_else(|| CommandError::NotText { path: display.to_string() })
+}
+
+fn io_error(display: &str, e: std::io::Error) -> CommandError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => CommandError::NotFound { path: display.to_string() },
+        std::io::ErrorKind::PermissionDenied => CommandError::PermissionDenied { path: display.to_string() },
+        _ => format!("Failed to read '{}': {}", display, e).into(),
+    }
+}
+
+// ============================================================================
+// SEGMENT BUILDING
+// ============================================================================
+
+/// A piece of paragraph text with one set of character styles.
+#[derive(Debug, Default)]
+struct Run {
+    text: String,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+#[derive(Default)]
+struct Segmenter {
+    segments: Vec<ScriptSegment>,
+    current: Option<ScriptSegment>,
+    /// UTF-16 length of the current body, kept to avoid rescanning it.
+    current_len: usize,
+}
+
+impl Segmenter {
+    fn start_segment(&mut self, title: Option<String>, heading_level: Option<u8>) {
+        self.finish_segment();
+        self.current = Some(ScriptSegment { title, heading_level, body: String::new(), styles: Vec::new() });
+    }
+
+    fn finish_segment(&mut self) {
+        self.current_len = 0;
+        if let Some(segment) = self.current.take() {
+            if segment.title.is_some() || !segment.body.is_empty() {
+                self.segments.push(segment);
+            }
+        }
+    }
+
+    /// Appends one paragraph to the current segment. Whitespace-only
+    /// paragraphs are dropped and surrounding whitespace is trimmed.
+    fn push_paragraph(&mut self, runs: &[Run]) {
+        let text: String = runs.iter().map(|run| run.text.as_str()).collect();
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return;
+        }
+        let lead = utf16_len(&text[..text.len() - text.trim_start().len()]);
+        let trimmed_len = utf16_len(trimmed);
+
+        let segment = self.current.get_or_insert_with(|| ScriptSegment {
+            title: None,
+            heading_level: None,
+            body: String::new(),
+            styles: Vec::new(),
+        });
+        if !segment.body.is_empty() {
+            segment.body.push_str("\n\n");
+            self.current_len += 2;
+        }
+        let base = self.current_len;
+        segment.body.push_str(trimmed);
+        self.current_len += trimmed_len;
+
+        let mut offset = 0usize;
+        for run in runs {
+            let len = utf16_len(&run.text);
+            let start = offset.saturating_sub(lead).min(trimmed_len);
+            let end = (offset + len).saturating_sub(lead).min(trimmed_len);
+            offset += len;
+            if start == end {
+                continue;
+            }
+
+            let styles = [(run.bold, TextStyle::Bold), (run.italic, TextStyle::Italic), (run.underline, TextStyle::Underline)];
+            for (_, style) in styles.iter().filter(|(on, _)| *on) {
+                add_style(&mut segment.styles, *style, base + start, base + end);
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<ScriptSegment> {
+        self.finish_segment();
+        self.segments
+    }
+}
+
+/// Extends the last range of the same style when `start` continues it, so a
+/// bold phrase split over several runs comes out as one range.
+fn add_style(styles: &mut Vec<StyleRange>, style: TextStyle, start: usize, end: usize) {
+    if let Some(last) = styles.iter_mut().rev().find(|range| range.style == style) {
+        if last.end == start {
+            last.end = end;
+            return;
+        }
+    }
+    styles.push(StyleRange { style, start, end });
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+fn runs_text(runs: &[Run]) -> String {
+    runs.iter().map(|run| run.text.as_str()).collect::<String>().trim().to_string()
+}
+
+// ============================================================================
+// PLAIN TEXT
+// ============================================================================
+
+/// Sections are separated by a `---` line or by two or more blank lines; a
+/// single blank line separates paragraphs within a section.
+fn parse_plain_text(text: &str) -> Vec<ScriptSegment> {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut segmenter = Segmenter::default();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut blank_lines = 0;
+
+    let flush = |segmenter: &mut Segmenter, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            segmenter.push_paragraph(&[Run { text: paragraph.join("\n"), ..Run::default() }]);
+            paragraph.clear();
+        }
+    };
+
+    for line in text.split('\n') {
+        let trimmed = line.trim();
+
+        if trimmed.len() >= 3 && trimmed.chars().all(|c| c == '-') {
+            flush(&mut segmenter, &mut paragraph);
+            segmenter.start_segment(None, None);
+            blank_lines = 0;
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            blank_lines += 1;
+            flush(&mut segmenter, &mut paragraph);
+            continue;
+        }
+
+        if blank_lines >= 2 {
+            segmenter.start_segment(None, None);
+        }
+        blank_lines = 0;
+        paragraph.push(line.trim_end());
+    }
+    flush(&mut segmenter, &mut paragraph);
+
+    segmenter.finish()
+}
+
+// ============================================================================
+// MARKDOWN
+// ============================================================================
+
+/// Headings start segments and become their titles; `---` rules start an
+/// untitled one. Emphasis and strong text become style ranges.
+fn parse_markdown(text: &str) -> Vec<ScriptSegment> {
+    use pulldown_cmark::{Event as MdEvent, Options, Parser, Tag, TagEnd};
+
+    let mut segmenter = Segmenter::default();
+    let mut runs: Vec<Run> = Vec::new();
+    let mut heading: Option<u8> = None;
+    let mut bold = 0usize;
+    let mut italic = 0usize;
+
+    let push_text = |runs: &mut Vec<Run>, text: &str, bold: usize, italic: usize| {
+        runs.push(Run { text: text.to_string(), bold: bold > 0, italic: italic > 0, underline: false });
+    };
+
+    for event in Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES) {
+        match event {
+            MdEvent::Start(Tag::Heading { level, .. }) => {
+                segmenter.push_paragraph(&runs);
+                runs.clear();
+                heading = Some(level as u8);
+            }
+            MdEvent::End(TagEnd::Heading(_)) => {
+                let title = runs_text(&runs);
+                runs.clear();
+                segmenter.start_segment((!title.is_empty()).then_some(title), heading.take());
+            }
+            MdEvent::Start(Tag::Strong) => bold += 1,
+            MdEvent::End(TagEnd::Strong) => bold = bold.saturating_sub(1),
+            MdEvent::Start(Tag::Emphasis) => italic += 1,
+            MdEvent::End(TagEnd::Emphasis) => italic = italic.saturating_sub(1),
+            MdEvent::Text(text) | MdEvent::Code(text) => push_text(&mut runs, &text, bold, italic),
+            MdEvent::SoftBreak | MdEvent::HardBreak => push_text(&mut runs, "\n", bold, italic),
+            MdEvent::Rule => {
+                segmenter.push_paragraph(&runs);
+                runs.clear();
+                segmenter.start_segment(None, None);
+            }
+            // Paragraph-like blocks; list items are paragraphs of their own
+            MdEvent::Start(Tag::Item)
+            | MdEvent::End(TagEnd::Paragraph | TagEnd::Item | TagEnd::CodeBlock | TagEnd::BlockQuote(_) | TagEnd::TableRow | TagEnd::TableHead) => {
+                segmenter.push_paragraph(&runs);
+                runs.clear();
+            }
+            MdEvent::End(TagEnd::TableCell) => push_text(&mut runs, "\t", 0, 0),
+            _ => {}
+        }
+    }
+    segmenter.push_paragraph(&runs);
+
+    segmenter.finish()
+}
+
+// ============================================================================
+// WORD (.docx)
+// ============================================================================
+
+fn import_docx(path: &Path, display: &str) -> Result<ImportedScript, CommandError> {
+    let corrupt = |reason: String| CommandError::CorruptDocument { path: display.to_string(), reason };
+
+    let file = File::open(path).map_err(|e| io_error(display, e))?;
+    let mut archive = match zip::ZipArchive::new(file) {
+        Ok(archive) => archive,
+        Err(e) => return Err(classify_non_zip(path, display).unwrap_or_else(|| corrupt(format!("not a zip archive ({})", e)))),
+    };
+
+    // Optional part; without it only inline outline levels mark headings
+    let heading_styles = match open_part(&mut archive, "word/styles.xml", display)? {
+        Some(reader) => parse_heading_styles(reader).map_err(|e| corrupt(format!("unreadable styles: {}", e)))?,
+        None => HashMap::new(),
+    };
+
+    let document = open_part(&mut archive, "word/document.xml", display)?
+        .ok_or_else(|| corrupt("missing word/document.xml".to_string()))?;
+    let segments = parse_docx_document(document, &heading_styles)
+        .map_err(|e| corrupt(format!("unreadable document: {}", e)))?;
+
+    Ok(ImportedScript { format: ScriptFormat::Docx, segments, encoding: None })
+}
+
+/// Distinguishes files Word wrote as something other than a zip: encrypted
+/// documents and old-format `.doc` files renamed to `.docx`.
+fn classify_non_zip(path: &Path, display: &str) -> Option<CommandError> {
+    let bytes = fs::read(path).ok()?;
+    if !bytes.starts_with(OLE_SIGNATURE) {
+        return None;
+    }
+
+    // Stream name as it appears in the OLE directory (UTF-16LE)
+    let marker: Vec<u8> = "EncryptionInfo".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    if bytes.windows(marker.len()).any(|window| window == marker.as_slice()) {
+        return Some(CommandError::PasswordProtected { path: display.to_string() });
+    }
+    Some(CommandError::CorruptDocument {
+        path: display.to_string(),
+        reason: "this is an old-format Word (.doc) file; save it as .docx first".to_string(),
+    })
+}
+
+fn open_part<'a>(
+    archive: &'a mut zip::ZipArchive<File>,
+    name: &str,
+    display: &str,
+) -> Result<Option<BufReader<zip::read::ZipFile<'a>>>, CommandError> {
+    let entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => {
+            return Err(CommandError::CorruptDocument { path: display.to_string(), reason: format!("{}: {}", name, e) })
+        }
+    };
+
+    if entry.size() > MAX_DOCX_XML_BYTES {
+        return Err(CommandError::FileTooLarge { path: display.to_string(), size: entry.size(), limit: MAX_DOCX_XML_BYTES });
+    }
+    Ok(Some(BufReader::new(entry)))
+}
+
+/// Heading level per paragraph style id, from the style's outline level or
+/// its built-in name ("heading 1", "Title"). Style ids are localized by
+/// Word, names are not.
+fn parse_heading_styles(source: impl BufRead) -> Result<HashMap<String, u8>, quick_xml::Error> {
+    let mut reader = quick_xml::Reader::from_reader(source);
+    let mut buf = Vec::new();
+    let mut styles = HashMap::new();
+    let mut current: Option<(String, Option<u8>)> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"style" => {
+                    current = attribute(&e, b"styleId").map(|id| (id, None));
+                }
+                b"name" => {
+                    if let (Some((_, level)), Some(name)) = (current.as_mut(), attribute(&e, b"val")) {
+                        let name = name.to_lowercase();
+                        if name == "title" {
+                            *level = Some(1);
+                        } else if let Some(n) = name.strip_prefix("heading ").and_then(|n| n.trim().parse::<u8>().ok()) {
+                            *level = Some(n);
+                        }
+                    }
+                }
+                b"outlineLvl" => {
+                    if let (Some((_, level)), Some(n)) = (current.as_mut(), outline_level(&e)) {
+                        *level = Some(n);
+                    }
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"style" => {
+                if let Some((id, Some(level))) = current.take() {
+                    styles.insert(id, level);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(styles)
+}
+
+/// Walks `word/document.xml` once. Paragraphs in heading styles start
+/// segments; bold, italic and underline run properties become style ranges.
+/// Text boxes and other paragraphs nested inside a paragraph are skipped.
+fn parse_docx_document(source: impl BufRead, heading_styles: &HashMap<String, u8>) -> Result<Vec<ScriptSegment>, quick_xml::Error> {
+    let mut reader = quick_xml::Reader::from_reader(source);
+    let mut buf = Vec::new();
+    let mut segmenter = Segmenter::default();
+
+    let mut paragraph_depth = 0usize;
+    let mut runs: Vec<Run> = Vec::new();
+    let mut heading: Option<u8> = None;
+    let mut run_style = Run::default();
+    let mut in_run = false;
+    let mut in_text = false;
+    // mc:Fallback repeats the content of mc:Choice for older readers
+    let mut fallback_depth = 0usize;
+
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        let empty = matches!(event, Event::Empty(_));
+
+        match &event {
+            Event::Start(e) | Event::Empty(e) => {
+                let name = e.local_name();
+                let name = name.as_ref();
+
+                if name == b"Fallback" && !empty {
+                    fallback_depth += 1;
+                }
+                if fallback_depth > 0 {
+                    buf.clear();
+                    continue;
+                }
+
+                match name {
+                    b"p" if !empty => {
+                        paragraph_depth += 1;
+                        if paragraph_depth == 1 {
+                            runs.clear();
+                            heading = None;
+                        }
+                    }
+                    _ if paragraph_depth != 1 => {}
+                    b"pStyle" => {
+                        if let Some(level) = attribute(e, b"val").and_then(|id| heading_styles.get(&id).copied()) {
+                            heading = Some(level);
+                        }
+                    }
+                    b"outlineLvl" if !in_run => {
+                        if let Some(level) = outline_level(e) {
+                            heading = Some(level);
+                        }
+                    }
+                    b"r" if !empty => {
+                        in_run = true;
+                        run_style = Run::default();
+                    }
+                    b"b" if in_run => run_style.bold = toggle_on(e),
+                    b"i" if in_run => run_style.italic = toggle_on(e),
+                    b"u" if in_run => run_style.underline = attribute(e, b"val").map_or(true, |val| val != "none"),
+                    b"t" if in_run && !empty => in_text = true,
+                    b"tab" if in_run => push_run_text(&mut runs, &run_style, "\t"),
+                    // Page and column breaks don't belong in a script
+                    b"br" | b"cr" if in_run && attribute(e, b"type").map_or(true, |kind| kind == "textWrapping") => {
+                        push_run_text(&mut runs, &run_style, "\n");
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(text) if in_text && paragraph_depth == 1 && fallback_depth == 0 => {
+                push_run_text(&mut runs, &run_style, &text.decode().map_err(quick_xml::Error::from)?);
+            }
+            Event::GeneralRef(reference) if in_text && paragraph_depth == 1 && fallback_depth == 0 => {
+                let resolved = match reference.resolve_char_ref()? {
+                    Some(c) => c.to_string(),
+                    None => {
+                        let name = reference.decode().map_err(quick_xml::Error::from)?;
+                        quick_xml::escape::resolve_predefined_entity(&name).unwrap_or_default().to_string()
+                    }
+                };
+                push_run_text(&mut runs, &run_style, &resolved);
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"Fallback" => fallback_depth = fallback_depth.saturating_sub(1),
+                _ if fallback_depth > 0 => {}
+                b"t" => in_text = false,
+                b"r" if paragraph_depth == 1 => in_run = false,
+                b"p" => {
+                    if paragraph_depth == 1 {
+                        match heading {
+                            Some(level) => {
+                                let title = runs_text(&runs);
+                                if !title.is_empty() {
+                                    segmenter.start_segment(Some(title), Some(level));
+                                }
+                            }
+                            None => segmenter.push_paragraph(&runs),
+                        }
+                        runs.clear();
+                    }
+                    paragraph_depth = paragraph_depth.saturating_sub(1);
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(segmenter.finish())
+}
+
+fn push_run_text(runs: &mut Vec<Run>, style: &Run, text: &str) {
+    if let Some(last) = runs.last_mut() {
+        if (last.bold, last.italic, last.underline) == (style.bold, style.italic, style.underline) {
+            last.text.push_str(text);
+            return;
+        }
+    }
+    runs.push(Run { text: text.to_string(), bold: style.bold, italic: style.italic, underline: style.underline });
+}
+
+/// Value of the attribute with this local name (namespace prefix ignored).
+fn attribute(element: &BytesStart, local_name: &[u8]) -> Option<String> {
+    element.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == local_name)
+        .and_then(|attr| attr.unescape_value().ok().map(|value| value.into_owned()))
+}
+
+/// `<w:b/>` is on; `<w:b w:val="0"/>` (or "false"/"off") is off.
+fn toggle_on(element: &BytesStart) -> bool {
+    attribute(element, b"val").map_or(true, |val| !matches!(val.as_str(), "0" | "false" | "off"))
+}
+
+/// Word's outline levels are 0-based; 9 means body text.
+fn outline_level(element: &BytesStart) -> Option<u8> {
+    attribute(element, b"val")
+        .and_then(|val| val.parse::<u8>().ok())
+        .filter(|level| *level < 9)
+        .map(|level| level + 1)
+}
@@ -0,0 +1,358 @@
+// script_text.rs - Cleans pasted text (Word, Google Docs, web pages) into prompter script text
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Every rule is on by default; turn one off to keep that kind of character
+/// (e.g. `normalize_quotes: false` keeps typographic quotes).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SanitizeOptions {
+    /// Use the HTML clipboard flavor when present: it keeps paragraph
+    /// structure that the plain-text flavor often flattens.
+    pub prefer_html: bool,
+    /// ‘ ’ ‚ ‛ → '   and   “ ” „ ‟ → "
+    pub normalize_quotes: bool,
+    /// En/em dashes, minus sign and friends → -
+    pub normalize_dashes: bool,
+    /// … → ...
+    pub normalize_ellipsis: bool,
+    /// Zero-width spaces/joiners, BOMs and soft hyphens are removed; exotic
+    /// spaces (no-break, thin, ...) become plain spaces.
+    pub strip_invisible: bool,
+    /// Runs of spaces become one, trailing spaces go, and more than one blank
+    /// line between paragraphs is reduced to one.
+    pub collapse_whitespace: bool,
+    /// Extra substitutions applied last, e.g. `{"&": "and"}`.
+    pub replacements: BTreeMap<String, String>,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        Self {
+            prefer_html: true,
+            normalize_quotes: true,
+            normalize_dashes: true,
+            normalize_ellipsis: true,
+            strip_invisible: true,
+            collapse_whitespace: true,
+            replacements: BTreeMap::new(),
+        }
+    }
+}
+
+/// How many characters each rule touched.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SanitizeReport {
+    /// True when the text came from the HTML flavor.
+    pub converted_from_html: bool,
+    pub line_breaks_normalized: usize,
+    pub quotes_replaced: usize,
+    pub dashes_replaced: usize,
+    pub ellipses_replaced: usize,
+    pub invisible_removed: usize,
+    pub spaces_normalized: usize,
+    pub whitespace_collapsed: usize,
+    pub custom_replacements: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SanitizedText {
+    pub text: String,
+    pub report: SanitizeReport,
+}
+
+// ============================================================================
+// SANITIZING
+// ============================================================================
+
+/// Cleans clipboard contents. `html` is used (converted to plain text) when
+/// present and preferred, otherwise `text`.
+pub fn sanitize(text: Option<&str>, html: Option<&str>, options: &SanitizeOptions) -> SanitizedText {
+    let mut report = SanitizeReport::default();
+
+    let source = match html.filter(|html| options.prefer_html && !html.trim().is_empty()) {
+        Some(html) => {
+            report.converted_from_html = true;
+            html_to_text(html)
+        }
+        None => text.unwrap_or_default().to_string(),
+    };
+
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                // \r\n counts once
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                report.line_breaks_normalized += 1;
+                out.push('\n');
+            }
+            '\u{2028}' | '\u{2029}' | '\u{0085}' | '\u{000B}' | '\u{000C}' => {
+                report.line_breaks_normalized += 1;
+                out.push('\n');
+            }
+            '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' if options.strip_invisible => {
+                report.invisible_removed += 1;
+            }
+            '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' if options.strip_invisible => {
+                report.spaces_normalized += 1;
+                out.push(' ');
+            }
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' if options.normalize_quotes => {
+                report.quotes_replaced += 1;
+                out.push('\'');
+            }
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' if options.normalize_quotes => {
+                report.quotes_replaced += 1;
+                out.push('"');
+            }
+            '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE58}' | '\u{FE63}' | '\u{FF0D}' if options.normalize_dashes => {
+                report.dashes_replaced += 1;
+                out.push('-');
+            }
+            '\u{2026}' if options.normalize_ellipsis => {
+                report.ellipses_replaced += 1;
+                out.push_str("...");
+            }
+            _ => out.push(c),
+        }
+    }
+
+    if options.collapse_whitespace {
+        out = collapse_whitespace(&out, &mut report.whitespace_collapsed);
+    }
+
+    for (from, to) in options.replacements.iter().filter(|(from, _)| !from.is_empty()) {
+        let count = out.matches(from.as_str()).count();
+        if count > 0 {
+            report.custom_replacements += count;
+            out = out.replace(from.as_str(), to);
+        }
+    }
+
+    SanitizedText { text: out, report }
+}
+
+/// One space between words, no trailing spaces, at most one blank line
+/// between paragraphs, nothing leading or trailing. Counts removed characters.
+fn collapse_whitespace(text: &str, removed: &mut usize) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for line in text.split('\n') {
+        let words: Vec<&str> = line.split([' ', '\t']).filter(|w| !w.is_empty()).collect();
+        let collapsed = words.join(" ");
+        *removed += line.chars().count() - collapsed.chars().count();
+
+        if collapsed.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(collapsed);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+
+    let result = paragraphs.join("\n\n");
+    // Line breaks dropped when blank-line runs were merged
+    let kept = result.matches('\n').count();
+    *removed += text.matches('\n').count().saturating_sub(kept);
+    result
+}
+
+// ============================================================================
+// HTML
+// ============================================================================
+
+/// Tags that end a paragraph.
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "h1", "h2", "h3", "h4", "h5", "h6", "li", "tr", "blockquote", "pre", "table", "ul", "ol", "section", "article",
+];
+/// Tags whose content is never visible text.
+const SKIPPED_TAGS: &[&str] = &["head", "style", "script", "title", "xml"];
+
+/// Plain text from clipboard HTML: tags dropped, entities decoded, source
+/// whitespace collapsed as a browser would, block ends as paragraph breaks.
+pub fn html_to_text(html: &str) -> String {
+    // Windows CF_HTML starts with a "Version:0.9 StartHTML:..." header
+    let html = match html.find('<') {
+        Some(start) if html.starts_with("Version:") => &html[start..],
+        _ => html,
+    };
+
+    let mut out = String::with_capacity(html.len() / 2);
+    let mut skipping: Option<String> = None;
+    let mut pending_space = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            // Includes Word's <!--[if ...]> ... <![endif]--> blocks
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        if rest.starts_with('<') {
+            let Some(end) = rest.find('>') else {
+                // A `<` that never closes is text, not an unfinished tag
+                if skipping.is_none() {
+                    push_text(rest, &mut out, &mut pending_space);
+                }
+                break;
+            };
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            let closing = tag.starts_with('/');
+            let name: String = tag.trim_start_matches('/')
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == ':')
+                .collect::<String>()
+                .to_ascii_lowercase();
+
+            if let Some(skipped) = &skipping {
+                if closing && *skipped == name {
+                    skipping = None;
+                }
+                continue;
+            }
+            if !closing && SKIPPED_TAGS.contains(&name.as_str()) && !tag.ends_with('/') {
+                skipping = Some(name);
+                continue;
+            }
+
+            if name == "br" {
+                out.push('\n');
+                pending_space = false;
+            } else if BLOCK_TAGS.contains(&name.as_str()) {
+                if !out.is_empty() && !out.ends_with("\n\n") {
+                    out.push_str(if out.ends_with('\n') { "\n" } else { "\n\n" });
+                }
+                pending_space = false;
+            } else if name == "td" || name == "th" {
+                pending_space = true;
+            }
+            continue;
+        }
+
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..text_end];
+        rest = &rest[text_end..];
+
+        if skipping.is_none() {
+            push_text(text, &mut out, &mut pending_space);
+        }
+    }
+
+    out.trim().to_string()
+}
+
+fn push_text(text: &str, out: &mut String, pending_space: &mut bool) {
+    for word_or_space in split_keep_whitespace(text) {
+        if word_or_space.chars().all(|c| c.is_ascii_whitespace()) {
+            *pending_space = true;
+            continue;
+        }
+        if *pending_space && !out.is_empty() && !out.ends_with(['\n', ' ']) {
+            out.push(' ');
+        }
+        *pending_space = false;
+        decode_entities_into(word_or_space, out);
+    }
+}
+
+fn split_keep_whitespace(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let is_space = first.is_ascii_whitespace();
+        let end = rest.find(|c: char| c.is_ascii_whitespace() != is_space).unwrap_or(rest.len());
+        let (piece, tail) = rest.split_at(end);
+        rest = tail;
+        Some(piece)
+    })
+}
+
+fn decode_entities_into(text: &str, out: &mut String) {
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let decoded = rest.find(';')
+            .filter(|semi| *semi <= 10)
+            .and_then(|semi| decode_entity(&rest[1..semi]).map(|c| (c, semi)));
+
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    }
+    if let Some(dec) = name.strip_prefix('#') {
+        return dec.parse().ok().and_then(char::from_u32);
+    }
+
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{00A0}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201C}',
+        "rdquo" => '\u{201D}',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "shy" => '\u{00AD}',
+        "zwsp" => '\u{200B}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_to_text_keeps_a_lone_trailing_angle_bracket() {
+        assert_eq!(html_to_text("<p>a &lt; b</p>and <"), "a < b\n\nand <");
+        assert_eq!(html_to_text("<"), "<");
+    }
+
+    #[test]
+    fn html_to_text_keeps_an_unclosed_tag_before_multibyte_text() {
+        assert_eq!(html_to_text("Grüße <ü"), "Grüße <ü");
+        assert_eq!(html_to_text("<p>x</p><é mañana"), "x\n\n<é mañana");
+    }
+}
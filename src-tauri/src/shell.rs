@@ -0,0 +1,334 @@
+// shell.rs - Hands files to the OS: default-app launching and file manager reveal
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::CommandError;
+
+/// D-Bus activation of a file manager that isn't running can take a moment,
+/// but a bus that never answers shouldn't hang the command.
+#[cfg(target_os = "linux")]
+const FILE_MANAGER_DBUS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevealOutcome {
+    /// "explorer", "finder", "file_manager1" (D-Bus) or "xdg_open".
+    pub mechanism: &'static str,
+    /// False when only the parent folder could be opened, without the file highlighted.
+    pub selected: bool,
+}
+
+// ============================================================================
+// OPEN WITH DEFAULT APP
+// ============================================================================
+
+/// Opens `path` with the user's default application. The path is passed to
+/// the OS as a single argument and never goes through a shell, so names
+/// containing `&`, `^` or quotes can't smuggle in extra commands.
+pub fn open_with_default_app(path: &Path) -> Result<(), CommandError> {
+    if !path.exists() {
+        return Err(CommandError::NotFound { path: path.to_string_lossy().to_string() });
+    }
+
+    platform_open(path)?;
+    log::info!("📂 Opened file: {}", path.display());
+    Ok(())
+}
+
+/// `ShellExecuteW` with the default verb. Runs synchronously (some handlers
+/// are COM-based), so call it from a blocking thread.
+#[cfg(target_os = "windows")]
+fn platform_open(path: &Path) -> Result<(), CommandError> {
+    use windows_sys::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED, COINIT_DISABLE_OLE1DDE};
+    use windows_sys::Win32::UI::Shell::{
+        ShellExecuteW, SE_ERR_ACCESSDENIED, SE_ERR_ASSOCINCOMPLETE, SE_ERR_FNF, SE_ERR_NOASSOC, SE_ERR_PNF,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let display = path.to_string_lossy().to_string();
+    let file = wide_path(path);
+
+    // SAFETY: `file` is NUL-terminated and outlives the call; null pointers are
+    // valid for the optional parameters. COM is initialised per the
+    // ShellExecute docs and only uninitialised if this call initialised it.
+    let code = unsafe {
+        let com_initialized = CoInitializeEx(std::ptr::null(), (COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE) as u32) >= 0;
+        let result = ShellExecuteW(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            file.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        ) as isize;
+        if com_initialized {
+            CoUninitialize();
+        }
+        result
+    };
+
+    // Legacy contract: values above 32 mean success, anything else is an SE_ERR_* code.
+    if code > 32 {
+        return Ok(());
+    }
+
+    match code as u32 {
+        SE_ERR_NOASSOC | SE_ERR_ASSOCINCOMPLETE => Err(CommandError::NoAssociatedApp { path: display }),
+        SE_ERR_FNF | SE_ERR_PNF => Err(CommandError::NotFound { path: display }),
+        SE_ERR_ACCESSDENIED => Err(CommandError::PermissionDenied { path: display }),
+        other => Err(format!("Failed to open file on Windows (ShellExecute error {}): {}", other, display).into()),
+    }
+}
+
+/// NUL-terminated UTF-16 path for the wide Win32 APIs, without the `\\?\`
+/// prefix `ShellExecuteW` doesn't understand.
+#[cfg(target_os = "windows")]
+fn wide_path(path: &Path) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+
+    dunce::simplified(path).as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// The opener invocation, with `path` as its only argument.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn open_command(path: &Path) -> std::process::Command {
+    let program = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    let mut command = std::process::Command::new(program);
+    command.arg(path);
+    command
+}
+
+/// `open` reports a missing handler on stderr and exits non-zero, after
+/// having handed the file off in every other case.
+#[cfg(target_os = "macos")]
+fn platform_open(path: &Path) -> Result<(), CommandError> {
+    let output = open_command(path)
+        .output()
+        .map_err(|e| format!("Failed to open file on macOS: {}", e))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("No application knows how to open") {
+        Err(CommandError::NoAssociatedApp { path: path.to_string_lossy().to_string() })
+    } else {
+        Err(format!("Failed to open file on macOS: {}", stderr.trim()).into())
+    }
+}
+
+/// xdg-open hands off to the desktop's opener and usually exits at once;
+/// exit code 4 ("action failed") is how a missing handler surfaces. If it is
+/// still running after the grace period the file was launched in-process.
+#[cfg(target_os = "linux")]
+fn platform_open(path: &Path) -> Result<(), CommandError> {
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    const XDG_OPEN_GRACE: Duration = Duration::from_secs(2);
+
+    let mut child = open_command(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to open file on Linux: {}", e))?;
+
+    let started = Instant::now();
+    while started.elapsed() < XDG_OPEN_GRACE {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) if status.code() == Some(4) => {
+                return Err(CommandError::NoAssociatedApp { path: path.to_string_lossy().to_string() });
+            }
+            Ok(Some(status)) => return Err(format!("Failed to open file on Linux: xdg-open exited with {}", status).into()),
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Failed to open file on Linux: {}", e).into()),
+        }
+    }
+
+    // Reap it whenever it does exit so it doesn't linger as a zombie.
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+// ============================================================================
+// SHOW IN FOLDER
+// ============================================================================
+
+/// Opens the platform file manager with `path` selected where the platform
+/// allows it, and reports which mechanism was used.
+pub async fn reveal_in_file_manager(path: &Path) -> Result<RevealOutcome, String> {
+    if !path.exists() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+
+    let outcome = platform_reveal(path).await?;
+    log::info!("📁 Showed file in folder via {}: {}", outcome.mechanism, path.display());
+    Ok(outcome)
+}
+
+#[cfg(target_os = "windows")]
+async fn platform_reveal(path: &Path) -> Result<RevealOutcome, String> {
+    use std::process::Command;
+
+    Command::new("explorer")
+        .args([std::ffi::OsStr::new("/select,"), path.as_os_str()])
+        .spawn()
+        .map_err(|e| format!("Failed to show file in folder on Windows: {}", e))?;
+
+    Ok(RevealOutcome { mechanism: "explorer", selected: true })
+}
+
+#[cfg(target_os = "macos")]
+async fn platform_reveal(path: &Path) -> Result<RevealOutcome, String> {
+    use std::process::Command;
+
+    Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .spawn()
+        .map_err(|e| format!("Failed to show file in folder on macOS: {}", e))?;
+
+    Ok(RevealOutcome { mechanism: "finder", selected: true })
+}
+
+/// Prefers `org.freedesktop.FileManager1.ShowItems` (Nautilus, Dolphin,
+/// Nemo, Thunar...), which highlights the file. Falls back to opening the
+/// parent folder with xdg-open when there's no session bus (headless, WSL)
+/// or no file manager implements the interface.
+#[cfg(target_os = "linux")]
+async fn platform_reveal(path: &Path) -> Result<RevealOutcome, String> {
+    use std::process::Command;
+
+    match show_items_via_dbus(path).await {
+        Ok(()) => return Ok(RevealOutcome { mechanism: "file_manager1", selected: true }),
+        Err(e) => log::warn!("FileManager1.ShowItems unavailable, falling back to xdg-open: {}", e),
+    }
+
+    let parent_dir = path.parent()
+        .ok_or_else(|| "File has no parent directory".to_string())?;
+
+    Command::new("xdg-open")
+        .arg(parent_dir)
+        .spawn()
+        .map_err(|e| format!("Failed to show file in folder on Linux: {}", e))?;
+
+    Ok(RevealOutcome { mechanism: "xdg_open", selected: false })
+}
+
+#[cfg(target_os = "linux")]
+async fn show_items_via_dbus(path: &Path) -> Result<(), String> {
+    let uri = file_uri(path)?;
+
+    let call = async {
+        let connection = zbus::Connection::session().await
+            .map_err(|e| format!("No D-Bus session bus: {}", e))?;
+
+        connection.call_method(
+            Some("org.freedesktop.FileManager1"),
+            "/org/freedesktop/FileManager1",
+            Some("org.freedesktop.FileManager1"),
+            "ShowItems",
+            &(vec![uri.as_str()], ""),
+        ).await
+            .map_err(|e| format!("ShowItems call failed: {}", e))?;
+
+        Ok(())
+    };
+
+    tokio::time::timeout(FILE_MANAGER_DBUS_TIMEOUT, call).await
+        .map_err(|_| "ShowItems call timed out".to_string())?
+}
+
+/// `file://` URI with spaces and non-ASCII bytes percent-encoded, as the
+/// FileManager1 spec requires.
+#[cfg(target_os = "linux")]
+fn file_uri(path: &Path) -> Result<String, String> {
+    url::Url::from_file_path(path)
+        .map(|url| url.to_string())
+        .map_err(|_| format!("Cannot build a file URI for: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Names that would break or be abused if they went through a shell.
+    fn awkward_paths() -> Vec<PathBuf> {
+        let base = std::env::temp_dir().join("segitelep-shell-tests");
+        vec![
+            base.join("Sunday Service Script.pdf"),
+            base.join("Q&A; rm -rf ~ && echo pwned.txt"),
+            base.join("\"quoted\" ^caret^ %PATH%.docx"),
+            base.join("Grüße – 日本語 – مرحبا.txt"),
+            base.join("a".repeat(120)).join("b".repeat(120)).join(format!("{}.json", "c".repeat(120))),
+        ]
+    }
+
+    #[test]
+    fn missing_file_is_not_found_with_its_path() {
+        for path in awkward_paths() {
+            match open_with_default_app(&path) {
+                Err(CommandError::NotFound { path: reported }) => assert_eq!(reported, path.to_string_lossy()),
+                other => panic!("expected NotFound for {}, got {:?}", path.display(), other),
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    #[test]
+    fn open_command_passes_the_path_as_one_argument() {
+        for path in awkward_paths() {
+            let command = open_command(&path);
+            let args: Vec<_> = command.get_args().collect();
+            assert_eq!(args, [path.as_os_str()]);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn file_uri_percent_encodes_reserved_and_non_ascii_bytes() {
+        let cases = [
+            ("/tmp/My Script.txt", "file:///tmp/My%20Script.txt"),
+            ("/tmp/take#2.txt", "file:///tmp/take%232.txt"),
+            ("/tmp/100%.txt", "file:///tmp/100%25.txt"),
+            ("/tmp/what?.txt", "file:///tmp/what%3F.txt"),
+            ("/tmp/Grüße/日本.txt", "file:///tmp/Gr%C3%BC%C3%9Fe/%E6%97%A5%E6%9C%AC.txt"),
+        ];
+        for (path, uri) in cases {
+            assert_eq!(file_uri(Path::new(path)).unwrap(), uri);
+        }
+    }
+
+    /// Drive letters and UNC shares are relative paths on Linux, and a file
+    /// URI needs an absolute one.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn file_uri_rejects_non_absolute_paths() {
+        for path in ["C:\\Users\\me\\script.txt", "\\\\server\\share\\script.txt", "scripts/today.txt"] {
+            assert!(file_uri(Path::new(path)).is_err(), "{}", path);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn wide_path_round_trips_and_is_nul_terminated() {
+        for path in awkward_paths() {
+            let wide = wide_path(&path);
+            assert_eq!(wide.last(), Some(&0));
+            assert_eq!(String::from_utf16(&wide[..wide.len() - 1]).unwrap(), path.to_string_lossy());
+        }
+    }
+}
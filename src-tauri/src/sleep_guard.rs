@@ -0,0 +1,222 @@
+// sleep_guard.rs - Keeps the display and system awake while the prompter plays
+
+use serde::Serialize;
+
+use crate::error::CommandError;
+
+const INHIBIT_REASON: &str = "Teleprompter playback in progress";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SleepPreventionState {
+    pub active: bool,
+    /// "execution_state" (Windows), "iokit_assertion" (macOS) or
+    /// "screensaver_inhibit" (Linux) while active.
+    pub mechanism: Option<&'static str>,
+}
+
+/// The single outstanding assertion, if any. Async mutex because taking or
+/// releasing it talks to D-Bus on Linux.
+#[derive(Default)]
+pub struct SleepGuard {
+    assertion: tokio::sync::Mutex<Option<Assertion>>,
+}
+
+// ============================================================================
+// GUARD
+// ============================================================================
+
+impl SleepGuard {
+    /// Idempotent: enabling twice keeps one assertion, disabling when none is
+    /// held is a no-op.
+    pub async fn set(&self, enabled: bool) -> Result<SleepPreventionState, CommandError> {
+        let mut assertion = self.assertion.lock().await;
+
+        match (enabled, assertion.is_some()) {
+            (true, false) => {
+                *assertion = Some(Assertion::acquire().await?);
+                log::info!("☕ Sleep prevention enabled via {}", MECHANISM);
+            }
+            (false, true) => {
+                if let Some(held) = assertion.take() {
+                    held.release().await;
+                }
+                log::info!("😴 Sleep prevention released");
+            }
+            _ => {}
+        }
+
+        Ok(state_of(assertion.is_some()))
+    }
+
+    pub async fn state(&self) -> SleepPreventionState {
+        state_of(self.assertion.lock().await.is_some())
+    }
+}
+
+fn state_of(active: bool) -> SleepPreventionState {
+    SleepPreventionState {
+        active,
+        mechanism: active.then_some(MECHANISM),
+    }
+}
+
+// ============================================================================
+// PLATFORM ASSERTIONS
+// ============================================================================
+
+#[cfg(target_os = "windows")]
+const MECHANISM: &str = "execution_state";
+
+/// `SetThreadExecutionState` is per-thread and the command runs on a pool
+/// thread, so a dedicated thread holds the state until told to let go.
+#[cfg(target_os = "windows")]
+struct Assertion {
+    release: std::sync::mpsc::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[cfg(target_os = "windows")]
+impl Assertion {
+    async fn acquire() -> Result<Self, CommandError> {
+        use windows_sys::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+        };
+
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+
+        let thread = std::thread::Builder::new()
+            .name("sleep-guard".to_string())
+            .spawn(move || {
+                // SAFETY: plain flag arguments, no pointers involved.
+                let previous = unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED) };
+                let _ = ready_tx.send(previous != 0);
+
+                let _ = released.recv();
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+            })
+            .map_err(|e| format!("Failed to start sleep guard thread: {}", e))?;
+
+        if !ready_rx.await.unwrap_or(false) {
+            let _ = release.send(());
+            return Err("SetThreadExecutionState failed".to_string().into());
+        }
+
+        Ok(Assertion { release, thread })
+    }
+
+    async fn release(self) {
+        let _ = self.release.send(());
+        let _ = tauri::async_runtime::spawn_blocking(move || self.thread.join()).await;
+    }
+}
+
+#[cfg(target_os = "macos")]
+const MECHANISM: &str = "iokit_assertion";
+
+#[cfg(target_os = "macos")]
+struct Assertion {
+    id: u32,
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOPMAssertionCreateWithName(
+        assertion_type: core_foundation::string::CFStringRef,
+        level: u32,
+        name: core_foundation::string::CFStringRef,
+        id: *mut u32,
+    ) -> i32;
+    fn IOPMAssertionRelease(id: u32) -> i32;
+}
+
+#[cfg(target_os = "macos")]
+impl Assertion {
+    async fn acquire() -> Result<Self, CommandError> {
+        use core_foundation::base::TCFType;
+        use core_foundation::string::CFString;
+
+        const ASSERTION_LEVEL_ON: u32 = 255;
+
+        // Display sleep implies system idle sleep is held off too.
+        let assertion_type = CFString::new("PreventUserIdleDisplaySleep");
+        let name = CFString::new(INHIBIT_REASON);
+        let mut id = 0u32;
+
+        // SAFETY: both CFStrings outlive the call and `id` is a valid out-pointer.
+        let status = unsafe {
+            IOPMAssertionCreateWithName(assertion_type.as_concrete_TypeRef(), ASSERTION_LEVEL_ON, name.as_concrete_TypeRef(), &mut id)
+        };
+
+        if status != 0 {
+            return Err(format!("IOPMAssertionCreateWithName failed (IOReturn {:#x})", status).into());
+        }
+        Ok(Assertion { id })
+    }
+
+    async fn release(self) {
+        // SAFETY: `id` came from a successful IOPMAssertionCreateWithName.
+        let status = unsafe { IOPMAssertionRelease(self.id) };
+        if status != 0 {
+            log::warn!("IOPMAssertionRelease failed (IOReturn {:#x})", status);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+const MECHANISM: &str = "screensaver_inhibit";
+
+/// The session bus drops the inhibit if our connection goes away, so the
+/// connection is kept for as long as the assertion is held; a crash can't
+/// leave the screensaver disabled.
+#[cfg(target_os = "linux")]
+struct Assertion {
+    connection: zbus::Connection,
+    cookie: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl Assertion {
+    async fn acquire() -> Result<Self, CommandError> {
+        let unsupported = |reason: String| CommandError::Unsupported {
+            feature: "sleep_prevention".to_string(),
+            reason,
+        };
+
+        let connection = zbus::Connection::session().await
+            .map_err(|e| unsupported(format!("no D-Bus session bus: {}", e)))?;
+
+        let reply = connection.call_method(
+            Some("org.freedesktop.ScreenSaver"),
+            "/org/freedesktop/ScreenSaver",
+            Some("org.freedesktop.ScreenSaver"),
+            "Inhibit",
+            &("SegiTelep", INHIBIT_REASON),
+        ).await
+            .map_err(|e| unsupported(format!("org.freedesktop.ScreenSaver.Inhibit failed: {}", e)))?;
+
+        let cookie: u32 = reply.body().deserialize()
+            .map_err(|e| format!("Unexpected Inhibit reply: {}", e))?;
+
+        Ok(Assertion { connection, cookie })
+    }
+
+    async fn release(self) {
+        let result = self.connection.call_method(
+            Some("org.freedesktop.ScreenSaver"),
+            "/org/freedesktop/ScreenSaver",
+            Some("org.freedesktop.ScreenSaver"),
+            "UnInhibit",
+            &(self.cookie,),
+        ).await;
+
+        if let Err(e) = result {
+            log::warn!("Failed to release screensaver inhibit: {}", e);
+        }
+    }
+}
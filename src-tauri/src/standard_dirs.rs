@@ -0,0 +1,51 @@
+// standard_dirs.rs - Well-known user directories for export/import defaults
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// Each field is `None` (serialized as `null`) when the platform can't
+/// resolve that directory, e.g. no XDG Videos dir on a minimal Linux install.
+#[derive(Debug, Clone, Serialize)]
+pub struct StandardDirs {
+    pub download: Option<String>,
+    pub documents: Option<String>,
+    pub videos: Option<String>,
+    pub pictures: Option<String>,
+    pub music: Option<String>,
+    pub desktop: Option<String>,
+    pub home: Option<String>,
+    pub app_data: Option<String>,
+}
+
+/// Resolves every well-known directory. With `ensure`, ones that resolve but
+/// don't exist yet are created; a directory that can't be created is
+/// reported as `None` rather than as a path that isn't there.
+pub fn standard_dirs(app_handle: &AppHandle, ensure: bool) -> StandardDirs {
+    let resolve = |dir: Option<PathBuf>| dir.and_then(|dir| prepare(dir, ensure));
+
+    StandardDirs {
+        download: resolve(dirs::download_dir()),
+        documents: resolve(dirs::document_dir()),
+        videos: resolve(dirs::video_dir()),
+        pictures: resolve(dirs::picture_dir()),
+        music: resolve(dirs::audio_dir()),
+        desktop: resolve(dirs::desktop_dir()),
+        home: resolve(dirs::home_dir()),
+        app_data: resolve(app_handle.path().app_data_dir().ok()),
+    }
+}
+
+fn prepare(dir: PathBuf, ensure: bool) -> Option<String> {
+    if ensure && !dir.is_dir() {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            log::warn!("Failed to create standard directory {}: {}", dir.display(), e);
+            return None;
+        }
+        log::info!("📁 Created standard directory {}", dir.display());
+    }
+
+    Some(dir.to_string_lossy().to_string())
+}
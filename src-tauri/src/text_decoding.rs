@@ -0,0 +1,110 @@
+// text_decoding.rs - Decodes script files of unknown encoding into UTF-8
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use serde::Serialize;
+
+/// Only the head of the file is inspected by the binary/UTF-16 heuristics.
+const SNIFF_LEN: usize = 8 * 1024;
+/// More NULs than this fraction of the sample means binary (or BOM-less UTF-16).
+const BINARY_NUL_RATIO: f64 = 0.01;
+/// Text uses few C0 controls besides tab/newline/CR/form feed/escape.
+const BINARY_CONTROL_RATIO: f64 = 0.05;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedText {
+    pub text: String,
+    /// WHATWG encoding label, e.g. "UTF-8", "UTF-16LE", "windows-1252".
+    pub encoding: String,
+    pub had_bom: bool,
+    /// True when some byte sequences were invalid and replaced with U+FFFD.
+    pub had_errors: bool,
+}
+
+// ============================================================================
+// DECODING
+// ============================================================================
+
+/// Detects the encoding (BOM, then BOM-less UTF-16, then UTF-8, then a
+/// statistical guess), decodes to UTF-8 and normalizes line endings to `\n`.
+/// Returns `None` when the bytes look like a binary file.
+pub fn decode_text(bytes: &[u8]) -> Option<DecodedText> {
+    let (encoding, body, had_bom) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom_len)) => (encoding, &bytes[bom_len..], true),
+        None => (detect_encoding(bytes)?, bytes, false),
+    };
+
+    let (decoded, had_errors) = encoding.decode_without_bom_handling(body);
+
+    Some(DecodedText {
+        text: normalize_line_endings(&decoded),
+        encoding: encoding.name().to_string(),
+        had_bom,
+        had_errors,
+    })
+}
+
+fn detect_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+
+    if let Some(utf16) = detect_bomless_utf16(sample) {
+        return Some(utf16);
+    }
+
+    if looks_binary(sample) {
+        return None;
+    }
+
+    if std::str::from_utf8(bytes).is_ok() {
+        return Some(UTF_8);
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    Some(detector.guess(None, true))
+}
+
+fn looks_binary(sample: &[u8]) -> bool {
+    if sample.is_empty() {
+        return false;
+    }
+
+    let nul_count = sample.iter().filter(|b| **b == 0).count();
+    let control_count = sample.iter()
+        .filter(|b| matches!(**b, 0x01..=0x08 | 0x0E..=0x1A | 0x1C..=0x1F))
+        .count();
+
+    let len = sample.len() as f64;
+    nul_count as f64 / len > BINARY_NUL_RATIO
+        || (nul_count + control_count) as f64 / len > BINARY_CONTROL_RATIO
+}
+
+/// ASCII-heavy UTF-16 text has a NUL in nearly every other byte, all on the
+/// same side; binary data scatters its NULs across both.
+fn detect_bomless_utf16(sample: &[u8]) -> Option<&'static Encoding> {
+    let pairs = sample.len() / 2;
+    if pairs < 4 {
+        return None;
+    }
+
+    let even_nuls = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_nuls = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+
+    let mostly = |count: usize| count * 10 >= pairs * 7;
+    let rarely = |count: usize| count * 20 <= pairs;
+
+    if mostly(odd_nuls) && rarely(even_nuls) {
+        Some(UTF_16LE)
+    } else if mostly(even_nuls) && rarely(odd_nuls) {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
@@ -0,0 +1,181 @@
+// tray.rs - System tray icon with remote-server controls
+
+use std::sync::Mutex;
+
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::{AppState, RemoteServerState};
+
+const TRAY_ID: &str = "main-tray";
+const MENU_TOGGLE_WINDOW: &str = "tray-toggle-window";
+const MENU_REMOTE: &str = "tray-remote";
+const MENU_COPY_URL: &str = "tray-copy-url";
+const MENU_QUIT: &str = "tray-quit";
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Menu items whose text or state follows the remote server.
+struct Items {
+    remote: CheckMenuItem<tauri::Wry>,
+    url: MenuItem<tauri::Wry>,
+    copy_url: MenuItem<tauri::Wry>,
+}
+
+/// `None` until built, and for good on platforms without a tray.
+#[derive(Default)]
+pub struct TrayMenu {
+    items: Mutex<Option<Items>>,
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Builds the tray icon. Missing tray support (e.g. no AppIndicator library
+/// on Linux) is logged and otherwise ignored.
+pub fn build(app_handle: &AppHandle) {
+    // libappindicator panics instead of returning an error when the shared
+    // library isn't installed. That's not a crash worth reporting.
+    let result = crate::crash_reports::catch_expected_panic(|| try_build(app_handle));
+
+    match result {
+        Ok(Ok(())) => {
+            refresh(app_handle);
+            log::info!("🧭 Tray icon ready");
+        }
+        Ok(Err(e)) => log::info!("🧭 Tray icon unavailable: {}", e),
+        Err(_) => log::info!("🧭 Tray icon unavailable on this system"),
+    }
+}
+
+fn try_build(app_handle: &AppHandle) -> tauri::Result<()> {
+    let toggle = MenuItem::with_id(app_handle, MENU_TOGGLE_WINDOW, "Show / Hide Window", true, None::<&str>)?;
+    let remote = CheckMenuItem::with_id(app_handle, MENU_REMOTE, "Remote Server", true, false, None::<&str>)?;
+    let url = MenuItem::new(app_handle, "Not running", false, None::<&str>)?;
+    let copy_url = MenuItem::with_id(app_handle, MENU_COPY_URL, "Copy Connection URL", false, None::<&str>)?;
+    let quit = MenuItem::with_id(app_handle, MENU_QUIT, "Quit SegiTelep", true, None::<&str>)?;
+
+    let menu = Menu::with_items(app_handle, &[
+        &toggle,
+        &PredefinedMenuItem::separator(app_handle)?,
+        &remote,
+        &url,
+        &copy_url,
+        &PredefinedMenuItem::separator(app_handle)?,
+        &quit,
+    ])?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("SegiTelep")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(on_tray_icon_event);
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app_handle)?;
+
+    *app_handle.state::<AppState>().tray.items.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Items { remote, url, copy_url });
+    Ok(())
+}
+
+// ============================================================================
+// STATE
+// ============================================================================
+
+/// Re-reads the remote server state into the menu. Called wherever the
+/// server is started or stopped; runs on a task since the server state sits
+/// behind an async lock.
+pub fn refresh(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let server = app_handle.state::<AppState>().remote_server.lock().await.clone();
+        apply_server_state(&app_handle, &server);
+    });
+}
+
+fn apply_server_state(app_handle: &AppHandle, server: &RemoteServerState) {
+    let state = app_handle.state::<AppState>();
+    let items = state.tray.items.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(items) = items.as_ref() else { return };
+
+    let url_text = if server.is_running { server.connection_url.as_str() } else { "Not running" };
+    let _ = items.remote.set_checked(server.is_running);
+    let _ = items.url.set_text(url_text);
+    let _ = items.copy_url.set_enabled(server.is_running);
+
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        let tooltip = if server.is_running {
+            format!("SegiTelep - remote at {}", server.connection_url)
+        } else {
+            "SegiTelep".to_string()
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+fn on_menu_event(app_handle: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        MENU_TOGGLE_WINDOW => toggle_main_window(app_handle),
+        MENU_REMOTE => toggle_remote_server(app_handle),
+        MENU_COPY_URL => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let url = app_handle.state::<AppState>().remote_server.lock().await.connection_url.clone();
+                if let Err(e) = app_handle.clipboard().write_text(url) {
+                    log::warn!("Failed to copy connection URL: {}", e);
+                }
+            });
+        }
+        MENU_QUIT => app_handle.exit(0),
+        _ => {}
+    }
+}
+
+fn on_tray_icon_event(tray: &TrayIcon, event: TrayIconEvent) {
+    if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+        toggle_main_window(tray.app_handle());
+    }
+}
+
+fn toggle_main_window(app_handle: &AppHandle) {
+    let Some(window) = app_handle.get_webview_window("main") else { return };
+
+    let visible = window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false);
+    if visible {
+        let _ = window.hide();
+    } else {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn toggle_remote_server(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let running = state.remote_server.lock().await.is_running;
+        let result = if running {
+            crate::stop_remote_server(app_handle.clone(), state.clone()).await.map(|_| ())
+        } else {
+            crate::start_remote_server(app_handle.clone(), state.clone()).await.map(|_| ())
+        };
+
+        if let Err(e) = result {
+            log::error!("Failed to toggle remote server from tray: {}", e);
+        }
+        // The check mark toggles itself on click; put it back in line
+        refresh(&app_handle);
+    });
+}
@@ -0,0 +1,321 @@
+// update_check.rs - Asks GitHub whether a newer release exists
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::{app_data_dir, AppState};
+
+const DEFAULT_REPO: &str = "Burhanali2211/segitelep";
+const GITHUB_API: &str = "https://api.github.com";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(8);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(4);
+/// Answers are reused this long; unauthenticated GitHub allows 60 calls/hour.
+const CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+/// "Unknown" (offline, rate-limited) is retried sooner.
+const UNKNOWN_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Stored under `update_check` in the app settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateCheckSettings {
+    /// Off means no request is ever made, including at startup.
+    pub enabled: bool,
+    pub check_on_startup: bool,
+    /// `owner/name` of the GitHub repository to check.
+    pub repo: Option<String>,
+    /// Full URL returning a GitHub-style release object; overrides `repo`.
+    /// For mirrors and self-hosted builds.
+    pub endpoint: Option<String>,
+}
+
+impl Default for UpdateCheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_on_startup: true,
+            repo: None,
+            endpoint: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateStatus {
+    UpdateAvailable,
+    UpToDate,
+    /// The check couldn't be made (offline, rate-limited, no releases yet).
+    /// Not worth bothering the user about.
+    Unknown,
+    /// Turned off in settings.
+    Disabled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheckResult {
+    pub status: UpdateStatus,
+    pub update_available: bool,
+    pub current_version: String,
+    pub latest_version: Option<String>,
+    pub release_notes: Option<String>,
+    /// Installer for this platform when the release has one, else the
+    /// release page.
+    pub download_url: Option<String>,
+    /// Why the status is `unknown`.
+    pub reason: Option<String>,
+    /// RFC 3339 time the answer was fetched (it may come from the cache).
+    pub checked_at: String,
+}
+
+/// The parts of GitHub's release object we use.
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+    body: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+struct CachedResult {
+    endpoint: String,
+    fetched_at: Instant,
+    result: UpdateCheckResult,
+}
+
+#[derive(Default)]
+pub struct UpdateChecker {
+    cache: Mutex<Option<CachedResult>>,
+}
+
+// ============================================================================
+// CHECKING
+// ============================================================================
+
+impl UpdateChecker {
+    /// Latest-release answer for the configured endpoint, from the cache when
+    /// fresh unless `force`. Offline and rate-limit failures come back as
+    /// `UpdateStatus::Unknown`; only a bad configuration or an unreadable
+    /// response is an error.
+    pub async fn check(&self, app_handle: &AppHandle, force: bool) -> Result<UpdateCheckResult, String> {
+        let settings = load_settings(app_handle)?;
+        let current_version = app_handle.package_info().version.clone();
+
+        if !settings.enabled {
+            return Ok(no_release(&current_version, UpdateStatus::Disabled, None));
+        }
+
+        let endpoint = endpoint_url(&settings)?;
+
+        if !force {
+            if let Some(cached) = self.cached(&endpoint) {
+                return Ok(cached);
+            }
+        }
+
+        let result = match fetch_release(&endpoint, &current_version).await? {
+            Fetched::Release(release) => compare(&release, &current_version)?,
+            Fetched::Unavailable(reason) => {
+                log::info!("🔄 Update check inconclusive: {}", reason);
+                no_release(&current_version, UpdateStatus::Unknown, Some(reason))
+            }
+        };
+
+        *self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(CachedResult {
+            endpoint,
+            fetched_at: Instant::now(),
+            result: result.clone(),
+        });
+        Ok(result)
+    }
+
+    pub fn clear_cache(&self) {
+        *self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+
+    fn cached(&self, endpoint: &str) -> Option<UpdateCheckResult> {
+        let cache = self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let cached = cache.as_ref().filter(|cached| cached.endpoint == endpoint)?;
+
+        let ttl = if cached.result.status == UpdateStatus::Unknown { UNKNOWN_CACHE_TTL } else { CACHE_TTL };
+        (cached.fetched_at.elapsed() < ttl).then(|| cached.result.clone())
+    }
+}
+
+/// Checks once in the background and tells the frontend (once it's ready)
+/// if there is something newer. Does nothing when disabled in settings.
+pub fn spawn_startup_check(app_handle: AppHandle) {
+    let settings = match load_settings(&app_handle) {
+        Ok(settings) => settings,
+        Err(_) => return,
+    };
+    if !settings.enabled || !settings.check_on_startup {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+
+        match state.update_checker.check(&app_handle, false).await {
+            Ok(result) if result.update_available => {
+                log::info!("🔄 Update available: {}", result.latest_version.as_deref().unwrap_or_default());
+                state.frontend_events.emit(&app_handle, "update-available", result);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Startup update check failed: {}", e),
+        }
+    });
+}
+
+fn load_settings(app_handle: &AppHandle) -> Result<UpdateCheckSettings, String> {
+    let state = app_handle.state::<AppState>();
+    Ok(state.settings.load(&app_data_dir(app_handle)?).update_check)
+}
+
+fn endpoint_url(settings: &UpdateCheckSettings) -> Result<String, String> {
+    if let Some(endpoint) = settings.endpoint.as_deref().filter(|e| !e.trim().is_empty()) {
+        let url = reqwest::Url::parse(endpoint.trim())
+            .map_err(|e| format!("Invalid update endpoint '{}': {}", endpoint, e))?;
+        if url.scheme() != "https" && url.scheme() != "http" {
+            return Err(format!("Update endpoint must be an http(s) URL, got '{}'", endpoint));
+        }
+        return Ok(url.to_string());
+    }
+
+    let repo = settings.repo.as_deref().map(str::trim).filter(|r| !r.is_empty()).unwrap_or(DEFAULT_REPO);
+    let valid = repo.split('/').count() == 2
+        && repo.split('/').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        });
+    if !valid {
+        return Err(format!("Invalid update repository '{}', expected 'owner/name'", repo));
+    }
+
+    Ok(format!("{}/repos/{}/releases/latest", GITHUB_API, repo))
+}
+
+// ============================================================================
+// FETCHING
+// ============================================================================
+
+enum Fetched {
+    Release(Release),
+    Unavailable(String),
+}
+
+async fn fetch_release(endpoint: &str, current_version: &semver::Version) -> Result<Fetched, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        // GitHub rejects requests without a User-Agent
+        .user_agent(format!("SegiTelep/{}", current_version))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let response = match client.get(endpoint).header("Accept", "application/vnd.github+json").send().await {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() => return Ok(Fetched::Unavailable("request timed out".to_string())),
+        Err(e) if e.is_connect() || e.is_request() => {
+            return Ok(Fetched::Unavailable(format!("offline or unreachable: {}", e)))
+        }
+        Err(e) => return Err(format!("Update check failed: {}", e)),
+    };
+
+    let status = response.status();
+    let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || (status == reqwest::StatusCode::FORBIDDEN
+            && response.headers().get("x-ratelimit-remaining").is_some_and(|v| v == "0"));
+
+    if rate_limited {
+        return Ok(Fetched::Unavailable("rate limited by the release server".to_string()));
+    }
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Fetched::Unavailable("no published release found".to_string()));
+    }
+    if status.is_server_error() {
+        return Ok(Fetched::Unavailable(format!("release server error ({})", status)));
+    }
+    if !status.is_success() {
+        return Err(format!("Update check failed: server returned {}", status));
+    }
+
+    match response.json::<Release>().await {
+        Ok(release) => Ok(Fetched::Release(release)),
+        Err(e) if e.is_timeout() => Ok(Fetched::Unavailable("request timed out".to_string())),
+        Err(e) => Err(format!("Failed to read release information: {}", e)),
+    }
+}
+
+// ============================================================================
+// COMPARING
+// ============================================================================
+
+fn compare(release: &Release, current_version: &semver::Version) -> Result<UpdateCheckResult, String> {
+    // `releases/latest` already skips these, but custom endpoints may not
+    if release.draft || release.prerelease {
+        return Ok(no_release(current_version, UpdateStatus::UpToDate, None));
+    }
+
+    let tag = release.tag_name.trim();
+    let latest = semver::Version::parse(tag.trim_start_matches(['v', 'V']))
+        .map_err(|e| format!("Release tag '{}' is not a version number: {}", tag, e))?;
+
+    let update_available = latest > *current_version;
+    Ok(UpdateCheckResult {
+        status: if update_available { UpdateStatus::UpdateAvailable } else { UpdateStatus::UpToDate },
+        update_available,
+        current_version: current_version.to_string(),
+        latest_version: Some(latest.to_string()),
+        release_notes: release.body.clone().filter(|notes| !notes.trim().is_empty()),
+        download_url: Some(platform_asset(release).unwrap_or_else(|| release.html_url.clone())),
+        reason: None,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Installer download matching this OS, by file extension.
+fn platform_asset(release: &Release) -> Option<String> {
+    let extensions: &[&str] = if cfg!(target_os = "windows") {
+        &[".msi", "-setup.exe", ".exe"]
+    } else if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else {
+        &[".appimage", ".deb", ".rpm"]
+    };
+
+    extensions.iter().find_map(|extension| {
+        release.assets.iter()
+            .find(|asset| asset.name.to_lowercase().ends_with(extension))
+            .map(|asset| asset.browser_download_url.clone())
+    })
+}
+
+fn no_release(current_version: &semver::Version, status: UpdateStatus, reason: Option<String>) -> UpdateCheckResult {
+    UpdateCheckResult {
+        status,
+        update_available: false,
+        current_version: current_version.to_string(),
+        latest_version: None,
+        release_notes: None,
+        download_url: None,
+        reason,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
@@ -0,0 +1,130 @@
+// window_bounds.rs - Borderless windows sized exactly for screen capture
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{Manager, PhysicalPosition, PhysicalSize, Window};
+
+use crate::AppState;
+
+/// Some window managers apply geometry changes asynchronously; the result is
+/// read back after waiting up to this many polls for them to land.
+const SETTLE_POLLS: u32 = 10;
+const SETTLE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// The window's content area in physical pixels, which is what capture
+/// software measures.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub decorated: bool,
+}
+
+// ============================================================================
+// BOUNDS
+// ============================================================================
+
+pub fn bounds(window: &Window) -> Result<WindowBounds, String> {
+    let position = window.inner_position().map_err(|e| format!("Failed to read window position: {}", e))?;
+    let size = window.inner_size().map_err(|e| format!("Failed to read window size: {}", e))?;
+
+    Ok(WindowBounds {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        scale_factor: window.scale_factor().map_err(|e| e.to_string())?,
+        decorated: window.is_decorated().map_err(|e| e.to_string())?,
+    })
+}
+
+/// Adds or removes the title bar and frame while keeping the content area
+/// where it is. Windows keeps the outer rectangle instead, which would shift
+/// and resize the content.
+pub async fn set_decorations(window: &Window, decorated: bool) -> Result<WindowBounds, String> {
+    let before = bounds(window)?;
+    if before.decorated == decorated {
+        return Ok(before);
+    }
+
+    window.set_decorations(decorated)
+        .map_err(|e| format!("Failed to change window decorations: {}", e))?;
+
+    place_content(window, before.x, before.y, before.width, before.height).await?;
+
+    log::info!("🪟 Window '{}' decorations: {}", window.label(), decorated);
+    bounds(window)
+}
+
+/// Moves and resizes so the content area covers exactly the given physical
+/// rectangle. Leaves fullscreen and maximized state first.
+pub async fn set_bounds(window: &Window, x: i32, y: i32, width: u32, height: u32) -> Result<WindowBounds, String> {
+    if width == 0 || height == 0 {
+        return Err("Window width and height must be greater than zero".to_string());
+    }
+
+    if window.is_fullscreen().unwrap_or(false) {
+        window.state::<AppState>().window_control.set_fullscreen(window, false)?;
+    }
+    if window.is_maximized().unwrap_or(false) {
+        window.unmaximize().map_err(|e| format!("Failed to unmaximize window: {}", e))?;
+    }
+
+    place_content(window, x, y, width, height).await?;
+
+    let result = bounds(window)?;
+    log::info!("🪟 Window '{}' bounds: {}x{} at {},{}", window.label(), result.width, result.height, result.x, result.y);
+    Ok(result)
+}
+
+/// Borderless, with a content area of exactly `width`x`height` physical
+/// pixels, kept at its current top-left corner.
+pub async fn apply_capture_preset(window: &Window, width: u32, height: u32) -> Result<WindowBounds, String> {
+    set_decorations(window, false).await?;
+
+    let current = bounds(window)?;
+    let result = set_bounds(window, current.x, current.y, width, height).await?;
+
+    if (result.width, result.height) != (width, height) {
+        log::warn!(
+            "⚠️  Capture preset asked for {}x{} but window '{}' is {}x{} (constrained by the monitor or window manager)",
+            width, height, window.label(), result.width, result.height
+        );
+    }
+    Ok(result)
+}
+
+/// `set_position` works on the outer (frame) origin, so the frame offset is
+/// measured after resizing and subtracted.
+async fn place_content(window: &Window, x: i32, y: i32, width: u32, height: u32) -> Result<(), String> {
+    window.set_size(PhysicalSize::new(width, height))
+        .map_err(|e| format!("Failed to resize window: {}", e))?;
+    settle(window, |b| (b.width, b.height) == (width, height)).await;
+
+    let outer = window.outer_position().map_err(|e| e.to_string())?;
+    let inner = window.inner_position().map_err(|e| e.to_string())?;
+    let target = PhysicalPosition::new(x - (inner.x - outer.x), y - (inner.y - outer.y));
+
+    window.set_position(target)
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+    settle(window, |b| (b.x, b.y) == (x, y)).await;
+
+    Ok(())
+}
+
+async fn settle(window: &Window, done: impl Fn(&WindowBounds) -> bool) {
+    for _ in 0..SETTLE_POLLS {
+        if bounds(window).is_ok_and(|b| done(&b)) {
+            return;
+        }
+        tokio::time::sleep(SETTLE_POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,220 @@
+// window_control.rs - Window flags that must survive fullscreen transitions
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::Window;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowFlags {
+    pub fullscreen: bool,
+    pub always_on_top: bool,
+    pub kiosk: bool,
+}
+
+/// What the user asked for, per window label. Some platforms drop
+/// always-on-top when a window enters or leaves fullscreen, so this is the
+/// source of truth we re-apply from.
+#[derive(Debug, Clone, Default)]
+struct RequestedFlags {
+    always_on_top: bool,
+    /// Monitor the window was sent fullscreen to, watched for disconnects.
+    pinned_monitor: Option<String>,
+    /// `None` means fully opaque (never changed).
+    opacity: Option<f64>,
+    click_through: bool,
+    /// Set while in kiosk mode; holds what to restore on exit.
+    kiosk: Option<KioskSnapshot>,
+}
+
+#[derive(Debug, Clone)]
+struct KioskSnapshot {
+    decorated: bool,
+    always_on_top: bool,
+    fullscreen: bool,
+}
+
+#[derive(Default)]
+pub struct WindowControl {
+    requested: Mutex<HashMap<String, RequestedFlags>>,
+}
+
+// ============================================================================
+// FLAG CONTROL
+// ============================================================================
+
+impl WindowControl {
+    pub fn set_always_on_top(&self, window: &Window, on_top: bool) -> Result<bool, String> {
+        window.set_always_on_top(on_top)
+            .map_err(|e| format!("Failed to set always-on-top: {}", e))?;
+
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(window.label().to_string())
+            .or_default()
+            .always_on_top = on_top;
+
+        log::info!("📌 Window '{}' always-on-top: {}", window.label(), on_top);
+        Ok(on_top)
+    }
+
+    pub fn toggle_always_on_top(&self, window: &Window) -> Result<bool, String> {
+        let current = window.is_always_on_top()
+            .map_err(|e| format!("Failed to read always-on-top state: {}", e))?;
+        self.set_always_on_top(window, !current)
+    }
+
+    pub fn set_fullscreen(&self, window: &Window, fullscreen: bool) -> Result<(), String> {
+        window.set_fullscreen(fullscreen).map_err(|e| e.to_string())?;
+        if !fullscreen {
+            self.pin_to_monitor(window.label(), None);
+        }
+        self.reapply(window);
+        Ok(())
+    }
+
+    pub fn pin_to_monitor(&self, label: &str, monitor_id: Option<String>) {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(label.to_string())
+            .or_default()
+            .pinned_monitor = monitor_id;
+    }
+
+    pub fn record_opacity(&self, label: &str, opacity: f64) {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(label.to_string())
+            .or_default()
+            .opacity = Some(opacity);
+    }
+
+    pub fn record_click_through(&self, label: &str, enabled: bool) {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(label.to_string())
+            .or_default()
+            .click_through = enabled;
+    }
+
+    pub fn click_through_windows(&self) -> Vec<String> {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter(|(_, flags)| flags.click_through)
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+
+    /// `(opacity, click_through)` for the window.
+    pub fn overlay(&self, label: &str) -> (f64, bool) {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(label)
+            .map(|flags| (flags.opacity.unwrap_or(1.0), flags.click_through))
+            .unwrap_or((1.0, false))
+    }
+
+    /// Drops everything recorded for a destroyed window, so a window later
+    /// created with the same label starts clean.
+    pub fn forget(&self, label: &str) {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(label);
+    }
+
+    /// `(window label, monitor id)` for every window fullscreened on a specific monitor.
+    pub fn pinned_monitors(&self) -> Vec<(String, String)> {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter_map(|(label, flags)| flags.pinned_monitor.clone().map(|id| (label.clone(), id)))
+            .collect()
+    }
+
+    pub fn flags(&self, window: &Window) -> Result<WindowFlags, String> {
+        Ok(WindowFlags {
+            fullscreen: window.is_fullscreen().map_err(|e| e.to_string())?,
+            always_on_top: window.is_always_on_top().map_err(|e| e.to_string())?,
+            kiosk: self.is_kiosk(window.label()),
+        })
+    }
+
+    // ------------------------------------------------------------------------
+    // Kiosk mode
+    // ------------------------------------------------------------------------
+
+    pub fn is_kiosk(&self, label: &str) -> bool {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(label)
+            .is_some_and(|flags| flags.kiosk.is_some())
+    }
+
+    pub fn any_kiosk(&self) -> bool {
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).values().any(|flags| flags.kiosk.is_some())
+    }
+
+    /// Fullscreen, always-on-top and undecorated, with close requests refused
+    /// (see the `on_window_event` handler in `run`). The flag lives here, not
+    /// in the webview, so a reload can't drop out of kiosk mode.
+    pub fn enter_kiosk(&self, window: &Window, hide_cursor: bool) -> Result<(), String> {
+        if self.is_kiosk(window.label()) {
+            return Ok(());
+        }
+
+        let snapshot = KioskSnapshot {
+            decorated: window.is_decorated().map_err(|e| e.to_string())?,
+            always_on_top: window.is_always_on_top().map_err(|e| e.to_string())?,
+            fullscreen: window.is_fullscreen().map_err(|e| e.to_string())?,
+        };
+
+        // Mark first so the close guard is active even if a later step fails.
+        self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(window.label().to_string())
+            .or_default()
+            .kiosk = Some(snapshot);
+
+        window.set_decorations(false)
+            .map_err(|e| format!("Failed to remove decorations: {}", e))?;
+        self.set_always_on_top(window, true)?;
+        self.set_fullscreen(window, true)?;
+        if hide_cursor {
+            window.set_cursor_visible(false)
+                .map_err(|e| format!("Failed to hide cursor: {}", e))?;
+        }
+        let _ = window.set_focus();
+
+        log::info!("🔒 Window '{}' entered kiosk mode", window.label());
+        Ok(())
+    }
+
+    /// Restores the window as it was before `enter_kiosk`. The PIN is checked by the caller.
+    pub fn exit_kiosk(&self, window: &Window) -> Result<(), String> {
+        let snapshot = self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get_mut(window.label())
+            .and_then(|flags| flags.kiosk.take());
+
+        let Some(snapshot) = snapshot else {
+            return Ok(());
+        };
+
+        let _ = window.set_cursor_visible(true);
+        self.set_fullscreen(window, snapshot.fullscreen)?;
+        self.set_always_on_top(window, snapshot.always_on_top)?;
+        window.set_decorations(snapshot.decorated)
+            .map_err(|e| format!("Failed to restore decorations: {}", e))?;
+
+        log::info!("🔓 Window '{}' left kiosk mode", window.label());
+        Ok(())
+    }
+
+    /// Restores requested flags the platform may have reset.
+    fn reapply(&self, window: &Window) {
+        let requested = self.requested.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(window.label())
+            .cloned()
+            .unwrap_or_default();
+
+        if requested.always_on_top {
+            if let Err(e) = window.set_always_on_top(true) {
+                log::warn!("Failed to re-apply always-on-top to '{}': {}", window.label(), e);
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// window_overlay.rs - Semi-transparent, click-through overlay mode for streaming
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Window};
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+use crate::error::CommandError;
+use crate::AppState;
+
+/// Registered only while some window is click-through, since a click-through
+/// window can't be clicked to turn it off again.
+pub const CLICK_THROUGH_ESCAPE_SHORTCUT: &str = "CommandOrControl+Shift+Alt+O";
+/// Below this the window is effectively invisible and easy to lose.
+pub const MIN_WINDOW_OPACITY: f64 = 0.1;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayState {
+    pub opacity: f64,
+    pub click_through: bool,
+    /// Global shortcut that turns click-through off from anywhere.
+    pub escape_shortcut: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ClickThroughChanged {
+    window_label: String,
+    enabled: bool,
+}
+
+// ============================================================================
+// OPACITY
+// ============================================================================
+
+pub async fn set_opacity(window: &Window, alpha: f64) -> Result<f64, CommandError> {
+    if !alpha.is_finite() || !(MIN_WINDOW_OPACITY..=1.0).contains(&alpha) {
+        return Err(format!("Opacity must be between {} and 1.0", MIN_WINDOW_OPACITY).into());
+    }
+
+    // Native window handles may only be touched from the main thread.
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let target = window.clone();
+    window.run_on_main_thread(move || {
+        let _ = tx.send(apply_opacity(&target, alpha));
+    }).map_err(|e| format!("Failed to reach the main thread: {}", e))?;
+
+    rx.await.map_err(|_| "Opacity change was dropped".to_string())??;
+
+    window.state::<AppState>().window_control.record_opacity(window.label(), alpha);
+    log::info!("🌫️  Window '{}' opacity: {:.2}", window.label(), alpha);
+    Ok(alpha)
+}
+
+/// Layered window with a constant alpha.
+#[cfg(target_os = "windows")]
+fn apply_opacity(window: &Window, alpha: f64) -> Result<(), CommandError> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA, WS_EX_LAYERED,
+    };
+
+    let hwnd = window.hwnd().map_err(|e| format!("Failed to get window handle: {}", e))?.0;
+
+    // SAFETY: `hwnd` is the live handle of this window and we are on the UI thread.
+    let ok = unsafe {
+        let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED as isize);
+        SetLayeredWindowAttributes(hwnd, 0, (alpha * 255.0).round() as u8, LWA_ALPHA)
+    };
+
+    if ok == 0 {
+        return Err(format!("SetLayeredWindowAttributes failed: {}", std::io::Error::last_os_error()).into());
+    }
+    Ok(())
+}
+
+/// `-[NSWindow setAlphaValue:]`.
+#[cfg(target_os = "macos")]
+fn apply_opacity(window: &Window, alpha: f64) -> Result<(), CommandError> {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+
+    let ns_window = window.ns_window().map_err(|e| format!("Failed to get NSWindow: {}", e))?;
+
+    // SAFETY: `ns_window` is this window's live NSWindow and we are on the main thread.
+    unsafe {
+        let ns_window = &*(ns_window as *mut AnyObject);
+        let _: () = msg_send![ns_window, setAlphaValue: alpha];
+    }
+    Ok(())
+}
+
+/// GTK toplevel opacity, which needs a compositing manager on X11 and isn't
+/// implemented by GTK 3's Wayland backend.
+#[cfg(target_os = "linux")]
+fn apply_opacity(window: &Window, alpha: f64) -> Result<(), CommandError> {
+    use gtk::prelude::*;
+
+    let gtk_window = window.gtk_window().map_err(|e| format!("Failed to get GTK window: {}", e))?;
+    let unsupported = |reason: &str| CommandError::Unsupported {
+        feature: "window_opacity".to_string(),
+        reason: reason.to_string(),
+    };
+
+    if gtk_window.display().type_().name() == "GdkWaylandDisplay" {
+        return Err(unsupported("window opacity is not available on Wayland"));
+    }
+    if !WidgetExt::screen(&gtk_window).is_some_and(|screen| screen.is_composited()) {
+        return Err(unsupported("no compositing window manager is running"));
+    }
+
+    gtk_window.set_opacity(alpha);
+    Ok(())
+}
+
+// ============================================================================
+// CLICK-THROUGH
+// ============================================================================
+
+pub fn set_click_through(app_handle: &AppHandle, window: &Window, enabled: bool) -> Result<bool, CommandError> {
+    window.set_ignore_cursor_events(enabled).map_err(|e| CommandError::Unsupported {
+        feature: "click_through".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    app_handle.state::<AppState>().window_control.record_click_through(window.label(), enabled);
+    sync_escape_shortcut(app_handle);
+    notify_click_through(app_handle, window.label(), enabled);
+
+    log::info!("🖱️  Window '{}' click-through: {}", window.label(), enabled);
+    Ok(enabled)
+}
+
+/// Turns click-through off on every window; used by the escape shortcut and
+/// the remote, since the overlay itself can no longer receive clicks.
+pub fn release_click_through(app_handle: &AppHandle) {
+    let control = &app_handle.state::<AppState>().window_control;
+
+    for label in control.click_through_windows() {
+        if let Some(window) = app_handle.get_webview_window(&label) {
+            if let Err(e) = window.set_ignore_cursor_events(false) {
+                log::error!("Failed to release click-through on '{}': {}", label, e);
+                continue;
+            }
+        }
+        control.record_click_through(&label, false);
+        notify_click_through(app_handle, &label, false);
+        log::info!("🖱️  Released click-through on '{}'", label);
+    }
+
+    sync_escape_shortcut(app_handle);
+}
+
+pub fn overlay_state(app_handle: &AppHandle, window: &Window) -> OverlayState {
+    let (opacity, click_through) = app_handle.state::<AppState>().window_control.overlay(window.label());
+
+    OverlayState {
+        opacity,
+        click_through,
+        escape_shortcut: CLICK_THROUGH_ESCAPE_SHORTCUT,
+    }
+}
+
+fn sync_escape_shortcut(app_handle: &AppHandle) {
+    let wanted = !app_handle.state::<AppState>().window_control.click_through_windows().is_empty();
+    let shortcuts = app_handle.global_shortcut();
+    let registered = shortcuts.is_registered(CLICK_THROUGH_ESCAPE_SHORTCUT);
+
+    let result = match (wanted, registered) {
+        (true, false) => shortcuts.register(CLICK_THROUGH_ESCAPE_SHORTCUT),
+        (false, true) => shortcuts.unregister(CLICK_THROUGH_ESCAPE_SHORTCUT),
+        _ => Ok(()),
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to update click-through escape shortcut: {}", e);
+    }
+}
+
+fn notify_click_through(app_handle: &AppHandle, label: &str, enabled: bool) {
+    let _ = app_handle.emit("overlay-click-through-changed", ClickThroughChanged {
+        window_label: label.to_string(),
+        enabled,
+    });
+}
@@ -0,0 +1,243 @@
+// window_state.rs - Remembers window geometry across launches
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, Window};
+
+use crate::{app_data_dir, write_json_atomic, AppState};
+
+const WINDOW_STATE_FILE: &str = "window_state.json";
+/// Move/resize events arrive continuously while dragging; changes are kept
+/// in memory and written out at most this often.
+const WINDOW_STATE_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+/// A saved window must overlap a connected monitor by at least this much in
+/// both directions to be considered reachable.
+const MIN_VISIBLE_PX: i64 = 100;
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Physical pixels; position is the outer (frame) origin, size the inner size.
+/// Bounds are those of the normal (restored) window, so a maximized or
+/// fullscreen window comes back to the right place when un-maximized.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub fullscreen: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WindowStateFile {
+    windows: HashMap<String, WindowGeometry>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Loaded lazily on first use.
+    file: Option<WindowStateFile>,
+    dirty: bool,
+}
+
+#[derive(Default)]
+pub struct WindowStateStore {
+    inner: Mutex<Inner>,
+}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+impl WindowStateStore {
+    /// Applies the saved geometry for `window`, pulled back on-screen if its
+    /// monitor is gone. Call before the window is shown.
+    pub fn restore(&self, app_handle: &AppHandle, window: &Window) {
+        let Ok(app_dir) = app_data_dir(app_handle) else { return };
+
+        let saved = {
+            let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            loaded(&mut inner, &app_dir).windows.get(window.label()).copied()
+        };
+        let Some(mut geometry) = saved else { return };
+
+        if let Ok(monitors) = window.available_monitors() {
+            if fit_to_monitors(&mut geometry, &monitors) {
+                log::info!("🪟 Saved position of '{}' is off-screen, moved onto a connected monitor", window.label());
+            }
+        }
+
+        let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+        let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+
+        if geometry.maximized {
+            let _ = window.maximize();
+        }
+        if geometry.fullscreen {
+            let _ = app_handle.state::<AppState>().window_control.set_fullscreen(window, true);
+        }
+
+        log::info!("🪟 Restored window '{}' to {}x{} at {},{}", window.label(), geometry.width, geometry.height, geometry.x, geometry.y);
+    }
+
+    /// Records the window's current state in memory; `flush` persists it.
+    pub fn capture(&self, app_handle: &AppHandle, window: &Window) {
+        // Minimized windows report bogus positions (-32000 on Windows).
+        if window.is_minimized().unwrap_or(true) {
+            return;
+        }
+        let Ok(app_dir) = app_data_dir(app_handle) else { return };
+
+        let maximized = window.is_maximized().unwrap_or(false);
+        let fullscreen = window.is_fullscreen().unwrap_or(false);
+
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let file = loaded(&mut inner, &app_dir);
+        let previous = file.windows.get(window.label()).copied();
+
+        let geometry = match (maximized || fullscreen, previous) {
+            // Keep the restored bounds; only the flags changed.
+            (true, Some(previous)) => WindowGeometry { maximized, fullscreen, ..previous },
+            _ => {
+                let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else { return };
+                WindowGeometry {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                    maximized,
+                    fullscreen,
+                }
+            }
+        };
+
+        file.windows.insert(window.label().to_string(), geometry);
+        inner.dirty = true;
+    }
+
+    pub fn flush(&self, app_handle: &AppHandle) {
+        let mut guard = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let inner = &mut *guard;
+        if !inner.dirty {
+            return;
+        }
+        let (Ok(app_dir), Some(file)) = (app_data_dir(app_handle), inner.file.as_ref()) else { return };
+
+        match write_json_atomic(&file_path(&app_dir), file) {
+            Ok(()) => inner.dirty = false,
+            Err(e) => log::warn!("Failed to save window state: {}", e),
+        }
+    }
+
+    /// Forgets all saved geometry and brings `window` back to a sane state.
+    pub fn reset(&self, app_handle: &AppHandle, window: &Window) -> Result<(), String> {
+        let app_dir = app_data_dir(app_handle)?;
+
+        {
+            let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            inner.file = Some(WindowStateFile::default());
+            inner.dirty = false;
+        }
+
+        match std::fs::remove_file(file_path(&app_dir)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to remove window state file: {}", e)),
+        }
+
+        app_handle.state::<AppState>().window_control.set_fullscreen(window, false)?;
+        window.unmaximize().map_err(|e| e.to_string())?;
+        window.center().map_err(|e| e.to_string())?;
+
+        log::info!("🪟 Window state reset");
+        Ok(())
+    }
+}
+
+fn loaded<'a>(inner: &'a mut Inner, app_dir: &Path) -> &'a mut WindowStateFile {
+    inner.file.get_or_insert_with(|| load(app_dir))
+}
+
+fn file_path(app_dir: &Path) -> PathBuf {
+    app_dir.join(WINDOW_STATE_FILE)
+}
+
+fn load(app_dir: &Path) -> WindowStateFile {
+    let path = file_path(app_dir);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return WindowStateFile::default(),
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("⚠️  Ignoring corrupt window state file {}: {}", path.display(), e);
+        WindowStateFile::default()
+    })
+}
+
+// ============================================================================
+// MONITOR VALIDATION
+// ============================================================================
+
+/// Leaves `geometry` alone if enough of it is on a connected monitor;
+/// otherwise moves (and if needed shrinks) it into the work area of the
+/// nearest one. Returns true when it was adjusted.
+fn fit_to_monitors(geometry: &mut WindowGeometry, monitors: &[Monitor]) -> bool {
+    let (x, y) = (geometry.x as i64, geometry.y as i64);
+    let (w, h) = (geometry.width as i64, geometry.height as i64);
+
+    let overlap = |m: &Monitor| {
+        let area = m.work_area();
+        let (ax, ay) = (area.position.x as i64, area.position.y as i64);
+        let (aw, ah) = (area.size.width as i64, area.size.height as i64);
+        let ox = (x + w).min(ax + aw) - x.max(ax);
+        let oy = (y + h).min(ay + ah) - y.max(ay);
+        (ox, oy)
+    };
+
+    if monitors.iter().any(|m| {
+        let (ox, oy) = overlap(m);
+        ox >= MIN_VISIBLE_PX.min(w) && oy >= MIN_VISIBLE_PX.min(h)
+    }) {
+        return false;
+    }
+
+    let center = (x + w / 2, y + h / 2);
+    let nearest = monitors.iter().min_by_key(|m| {
+        let area = m.work_area();
+        let cx = area.position.x as i64 + area.size.width as i64 / 2;
+        let cy = area.position.y as i64 + area.size.height as i64 / 2;
+        (cx - center.0).pow(2) + (cy - center.1).pow(2)
+    });
+    let Some(monitor) = nearest else { return false };
+
+    let area = monitor.work_area();
+    geometry.width = geometry.width.min(area.size.width);
+    geometry.height = geometry.height.min(area.size.height);
+
+    let max_x = area.position.x + (area.size.width - geometry.width) as i32;
+    let max_y = area.position.y + (area.size.height - geometry.height) as i32;
+    geometry.x = geometry.x.clamp(area.position.x, max_x);
+    geometry.y = geometry.y.clamp(area.position.y, max_y);
+
+    true
+}
+
+/// Background task writing out geometry captured from move/resize events.
+pub fn spawn_flusher(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(WINDOW_STATE_FLUSH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            app_handle.state::<AppState>().window_state.flush(&app_handle);
+        }
+    });
+}
@@ -0,0 +1,451 @@
+// zip_archive.rs - Zip creation and extraction with progress and cancellation
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::disk_space;
+use crate::error::CommandError;
+use crate::jobs::{JobGuard, JobRegistry};
+
+const ZIP_CHUNK_SIZE: usize = 1024 * 1024;
+const ZIP_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// What to do when a destination file already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwritePolicy {
+    /// Fail with `already_exists`.
+    #[default]
+    Error,
+    /// Keep the existing file and carry on (extraction only; a whole archive
+    /// can't be skipped, so `create_zip` treats this like `error`).
+    Skip,
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveSummary {
+    pub archive: String,
+    pub file_count: usize,
+    /// Uncompressed bytes written (extraction) or read (creation).
+    pub total_bytes: u64,
+    /// Entries left alone because of the overwrite policy or because they were symlinks.
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ArchiveProgress {
+    job_id: String,
+    /// "create" or "extract".
+    operation: &'static str,
+    files_done: usize,
+    total_files: usize,
+    bytes_done: u64,
+    total_bytes: u64,
+}
+
+struct SourceEntry {
+    path: PathBuf,
+    /// Forward-slash name inside the archive, relative to the source's parent.
+    name: String,
+    is_dir: bool,
+    size: u64,
+}
+
+struct ProgressTracker<'a> {
+    app_handle: &'a AppHandle,
+    job: &'a JobGuard<'a>,
+    operation: &'static str,
+    total_files: usize,
+    total_bytes: u64,
+    files_done: usize,
+    bytes_done: u64,
+    last_report: Instant,
+}
+
+impl ProgressTracker<'_> {
+    fn add_bytes(&mut self, bytes: u64) {
+        self.bytes_done += bytes;
+        if self.last_report.elapsed() >= ZIP_PROGRESS_INTERVAL {
+            self.emit();
+        }
+    }
+
+    fn finish_file(&mut self) {
+        self.files_done += 1;
+    }
+
+    fn emit(&mut self) {
+        self.last_report = Instant::now();
+        let _ = self.app_handle.emit("zip-progress", ArchiveProgress {
+            job_id: self.job.id().to_string(),
+            operation: self.operation,
+            files_done: self.files_done,
+            total_files: self.total_files,
+            bytes_done: self.bytes_done,
+            total_bytes: self.total_bytes,
+        });
+    }
+}
+
+// ============================================================================
+// CREATION
+// ============================================================================
+
+/// Zips files and directories into `destination`. Each source keeps its own
+/// name at the archive root, with directory contents nested beneath it.
+/// Blocking; run it on a blocking thread.
+pub fn create_zip(
+    app_handle: &AppHandle,
+    jobs: &JobRegistry,
+    job_id: &str,
+    sources: &[PathBuf],
+    destination: &Path,
+    overwrite: OverwritePolicy,
+) -> Result<ArchiveSummary, CommandError> {
+    let job = jobs.register(job_id);
+
+    if destination.exists() && overwrite != OverwritePolicy::Overwrite {
+        return Err(CommandError::AlreadyExists { path: destination.to_string_lossy().to_string() });
+    }
+
+    let entries = collect_sources(sources)?;
+    let total_bytes: u64 = entries.iter().map(|e| e.size).sum();
+    disk_space::ensure_space(destination, total_bytes)?;
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+    }
+
+    // Build next to the destination and rename at the end, so a cancelled or
+    // failed run never leaves a truncated archive under the real name.
+    let mut part_name = destination.as_os_str().to_os_string();
+    part_name.push(".part");
+    let part_path = PathBuf::from(part_name);
+
+    let mut progress = ProgressTracker {
+        app_handle,
+        job: &job,
+        operation: "create",
+        total_files: entries.iter().filter(|e| !e.is_dir).count(),
+        total_bytes,
+        files_done: 0,
+        bytes_done: 0,
+        last_report: Instant::now(),
+    };
+
+    write_archive(&entries, &part_path, &mut progress)
+        .and_then(|_| {
+            fs::rename(&part_path, destination)
+                .map_err(|e| format!("Failed to finalize '{}': {}", destination.display(), e).into())
+        })
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&part_path);
+        })?;
+
+    log::info!("🗜️  Created {} ({} files, {} bytes)", destination.display(), progress.files_done, total_bytes);
+
+    Ok(ArchiveSummary {
+        archive: destination.to_string_lossy().to_string(),
+        file_count: progress.files_done,
+        total_bytes,
+        skipped: 0,
+    })
+}
+
+fn write_archive(entries: &[SourceEntry], part_path: &Path, progress: &mut ProgressTracker) -> Result<(), CommandError> {
+    let file = File::create(part_path)
+        .map_err(|e| format!("Failed to create '{}': {}", part_path.display(), e))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let mut buffer = vec![0u8; ZIP_CHUNK_SIZE];
+
+    for entry in entries {
+        if progress.job.is_cancelled() {
+            log::info!("⏹️  Zip job {} cancelled", progress.job.id());
+            return Err(CommandError::Cancelled);
+        }
+
+        let options = entry_options(&entry.path, entry.size);
+
+        if entry.is_dir {
+            zip.add_directory(entry.name.as_str(), options)
+                .map_err(|e| format!("Failed to add directory '{}': {}", entry.name, e))?;
+            continue;
+        }
+
+        let source = File::open(&entry.path)
+            .map_err(|e| format!("Failed to open '{}': {}", entry.path.display(), e))?;
+        zip.start_file(entry.name.as_str(), options)
+            .map_err(|e| format!("Failed to add '{}': {}", entry.name, e))?;
+        stream(source, &mut zip, &mut buffer, progress, &entry.path)?;
+        progress.finish_file();
+    }
+
+    let writer = zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+    let file = writer.into_inner()
+        .map_err(|e| format!("Failed to flush archive: {}", e.error()))?;
+    file.sync_all()
+        .map_err(|e| format!("Failed to sync '{}': {}", part_path.display(), e))?;
+
+    Ok(())
+}
+
+fn entry_options(path: &Path, size: u64) -> SimpleFileOptions {
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(size >= u32::MAX as u64);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = fs::metadata(path) {
+            return options.unix_permissions(metadata.permissions().mode() & 0o777);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    options
+}
+
+/// Expands directories depth-first. Symlinked directories inside a source are
+/// skipped rather than followed, so link cycles can't blow up the archive.
+fn collect_sources(sources: &[PathBuf]) -> Result<Vec<SourceEntry>, CommandError> {
+    let mut entries = Vec::new();
+    let mut names = std::collections::HashSet::new();
+
+    for source in sources {
+        let metadata = fs::metadata(source).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => CommandError::NotFound { path: source.to_string_lossy().to_string() },
+            _ => format!("Failed to read '{}': {}", source.display(), e).into(),
+        })?;
+        let root_name = source.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Cannot archive a filesystem root: {}", source.display()))?;
+
+        if !names.insert(root_name.clone()) {
+            return Err(format!("Two sources share the name '{}' and would collide in the archive", root_name).into());
+        }
+
+        if !metadata.is_dir() {
+            entries.push(SourceEntry { path: source.clone(), name: root_name, is_dir: false, size: metadata.len() });
+            continue;
+        }
+
+        entries.push(SourceEntry { path: source.clone(), name: format!("{}/", root_name), is_dir: true, size: 0 });
+
+        let mut pending = vec![(source.clone(), root_name)];
+        while let Some((dir, prefix)) = pending.pop() {
+            let read_dir = fs::read_dir(&dir)
+                .map_err(|e| format!("Failed to read directory '{}': {}", dir.display(), e))?;
+
+            for entry in read_dir.flatten() {
+                let path = entry.path();
+                let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+                let is_real_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+
+                if is_real_dir {
+                    entries.push(SourceEntry { path: path.clone(), name: format!("{}/", name), is_dir: true, size: 0 });
+                    pending.push((path, name));
+                } else if let Some(metadata) = fs::metadata(&path).ok().filter(|m| m.is_file()) {
+                    entries.push(SourceEntry { path, name, is_dir: false, size: metadata.len() });
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+// ============================================================================
+// EXTRACTION
+// ============================================================================
+
+/// Extracts `zip_path` into `destination_dir`, keeping the archive's
+/// directory layout. Every entry name is validated before anything is
+/// written, so a malicious archive (`../` or absolute names) is rejected
+/// whole. Files finished before a cancellation are left in place.
+/// Blocking; run it on a blocking thread.
+pub fn extract_zip(
+    app_handle: &AppHandle,
+    jobs: &JobRegistry,
+    job_id: &str,
+    zip_path: &Path,
+    destination_dir: &Path,
+    overwrite: OverwritePolicy,
+) -> Result<ArchiveSummary, CommandError> {
+    let job = jobs.register(job_id);
+    let display = zip_path.to_string_lossy().to_string();
+
+    let file = File::open(zip_path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => CommandError::NotFound { path: display.clone() },
+        ErrorKind::PermissionDenied => CommandError::PermissionDenied { path: display.clone() },
+        _ => format!("Failed to open '{}': {}", display, e).into(),
+    })?;
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|e| format!("Failed to read archive '{}': {}", display, e))?;
+
+    let mut total_files = 0;
+    let mut total_bytes = 0u64;
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)
+            .map_err(|e| format!("Failed to read archive entry {}: {}", index, e))?;
+        if entry.enclosed_name().is_none() {
+            log::warn!("🚫 Rejected archive with unsafe entry path: {}", entry.name());
+            return Err(format!("Archive contains an unsafe path: {}", entry.name()).into());
+        }
+        if !entry.is_dir() {
+            total_files += 1;
+            total_bytes += entry.size();
+        }
+    }
+
+    disk_space::ensure_space(destination_dir, total_bytes)?;
+    fs::create_dir_all(destination_dir)
+        .map_err(|e| format!("Failed to create '{}': {}", destination_dir.display(), e))?;
+    let root = dunce::canonicalize(destination_dir)
+        .map_err(|e| format!("Failed to resolve '{}': {}", destination_dir.display(), e))?;
+
+    let mut progress = ProgressTracker {
+        app_handle,
+        job: &job,
+        operation: "extract",
+        total_files,
+        total_bytes,
+        files_done: 0,
+        bytes_done: 0,
+        last_report: Instant::now(),
+    };
+    let mut skipped = 0;
+    let mut buffer = vec![0u8; ZIP_CHUNK_SIZE];
+
+    for index in 0..archive.len() {
+        if job.is_cancelled() {
+            log::info!("⏹️  Unzip job {} cancelled", job.id());
+            return Err(CommandError::Cancelled);
+        }
+
+        let entry = archive.by_index(index)
+            .map_err(|e| format!("Failed to read archive entry {}: {}", index, e))?;
+        let Some(relative) = entry.enclosed_name() else { continue };
+        let target = root.join(&relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create '{}': {}", target.display(), e))?;
+            continue;
+        }
+
+        if entry.is_symlink() {
+            log::warn!("Skipping symlink entry in archive: {}", entry.name());
+            skipped += 1;
+            progress.finish_file();
+            continue;
+        }
+
+        let parent = target.parent().unwrap_or(&root);
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+
+        // A pre-existing symlinked folder inside the destination could still
+        // redirect the write elsewhere; check where the parent really is.
+        let real_parent = dunce::canonicalize(parent)
+            .map_err(|e| format!("Failed to resolve '{}': {}", parent.display(), e))?;
+        if !real_parent.starts_with(&root) {
+            return Err(format!("Archive entry escapes the destination: {}", entry.name()).into());
+        }
+
+        if fs::symlink_metadata(&target).is_ok() {
+            match overwrite {
+                OverwritePolicy::Error => {
+                    return Err(CommandError::AlreadyExists { path: target.to_string_lossy().to_string() });
+                }
+                OverwritePolicy::Skip => {
+                    skipped += 1;
+                    progress.finish_file();
+                    continue;
+                }
+                OverwritePolicy::Overwrite => {
+                    // Replace links rather than writing through them.
+                    if fs::symlink_metadata(&target).is_ok_and(|m| m.file_type().is_symlink()) {
+                        let _ = fs::remove_file(&target);
+                    }
+                }
+            }
+        }
+
+        let unix_mode = entry.unix_mode();
+        let output = File::create(&target)
+            .map_err(|e| format!("Failed to create '{}': {}", target.display(), e))?;
+        let mut writer = BufWriter::new(output);
+
+        stream(entry, &mut writer, &mut buffer, &mut progress, &target)
+            .and_then(|_| writer.flush().map_err(|e| format!("Failed to write '{}': {}", target.display(), e).into()))
+            .inspect_err(|_| {
+                let _ = fs::remove_file(&target);
+            })?;
+
+        #[cfg(unix)]
+        if let Some(mode) = unix_mode {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o777));
+        }
+        #[cfg(not(unix))]
+        let _ = unix_mode;
+
+        progress.finish_file();
+    }
+
+    log::info!("📂 Extracted {} → {} ({} files, {} skipped)", zip_path.display(), root.display(), progress.files_done - skipped, skipped);
+
+    Ok(ArchiveSummary {
+        archive: display,
+        file_count: progress.files_done - skipped,
+        total_bytes: progress.bytes_done,
+        skipped,
+    })
+}
+
+// ============================================================================
+// STREAMING
+// ============================================================================
+
+/// Copies `reader` into `writer` chunk by chunk, checking for cancellation
+/// between chunks so a single huge entry can still be interrupted.
+fn stream(
+    mut reader: impl Read,
+    writer: &mut impl Write,
+    buffer: &mut [u8],
+    progress: &mut ProgressTracker,
+    path: &Path,
+) -> Result<(), CommandError> {
+    loop {
+        if progress.job.is_cancelled() {
+            log::info!("⏹️  Zip job {} cancelled", progress.job.id());
+            return Err(CommandError::Cancelled);
+        }
+
+        let read = reader.read(buffer)
+            .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        if read == 0 {
+            return Ok(());
+        }
+
+        writer.write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+        progress.add_bytes(read as u64);
+    }
+}
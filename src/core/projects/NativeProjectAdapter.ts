@@ -315,9 +315,12 @@ export class NativeProjectAdapter implements IProjectAdapter {
             // Note: Currently AudioStorage has its own maintenance, but we should unify
             // For now, let's just focus on CAS assets.
 
-            const deletedCount = await cleanupGlobalAssetsNative(Array.from(activeAssets));
-            if (deletedCount > 0) {
-                console.log(`[Storage GC] Deleted ${deletedCount} orphaned assets`);
+            const summary = await cleanupGlobalAssetsNative(Array.from(activeAssets));
+            if (summary && summary.deleted > 0) {
+                console.log(`[Storage GC] Deleted ${summary.deleted} orphaned assets (${summary.bytes_freed} bytes freed)`);
+            }
+            if (summary && summary.failed.length > 0) {
+                console.warn(`[Storage GC] Failed to delete ${summary.failed.length} assets`, summary.failed);
             }
         } catch (error) {
             console.error('Asset cleanup failed', error);
@@ -133,8 +133,10 @@ export async function atomicWriteJsonFile<T>(relativePath: string, data: T): Pro
 export async function storeAssetNative(bytes: Uint8Array, extension: string): Promise<string> {
   requireTauri();
   const { invoke } = await import('@tauri-apps/api/core');
-  // Pass bytes as array for Rust compatibility
-  return invoke('store_asset', { bytes: Array.from(bytes), extension });
+  // Raw body: no JSON number array, the extension travels in a header
+  return invoke('store_asset', bytes, {
+    headers: { 'x-extension': encodeURIComponent(extension) },
+  });
 }
 
 // Get absolute path from relative AppData path (Tauri only)
@@ -144,9 +146,19 @@ export async function getAbsolutePath(relativePath: string): Promise<string> {
   return invoke('get_absolute_path', { relativePath });
 }
 
+// Result of a global asset cleanup pass (mirrors the Rust CleanupSummary)
+export interface AssetCleanupSummary {
+  scanned: number;
+  orphaned: number;
+  deleted: number;
+  bytes_freed: number;
+  failed: string[];
+  cancelled: boolean;
+}
+
 // Cleanup global assets that are not in the provided active list
-export async function cleanupGlobalAssetsNative(activeAssets: string[]): Promise<number> {
-  if (!isTauriApp()) return 0;
+export async function cleanupGlobalAssetsNative(activeAssets: string[]): Promise<AssetCleanupSummary | null> {
+  if (!isTauriApp()) return null;
   const { invoke } = await import('@tauri-apps/api/core');
   return invoke('cleanup_global_assets', { activeAssets });
 }